@@ -1,6 +1,8 @@
-use std::sync::Arc;
+use std::sync::{Arc, LazyLock};
 
 use anyhow::Result;
+use regex::Regex;
+use reqwest::Client;
 use serenity::{
     all::{
         CommandInteraction, CreateInteractionResponse, CreateInteractionResponseMessage,
@@ -11,25 +13,194 @@ use serenity::{
 use tokio::time::{Duration, timeout};
 use url::Url;
 
-use crate::{data::Database, util::parser::parse};
+use crate::{
+    cmd::color::parse_hex,
+    data::{
+        FeedStore,
+        models::{NewFeed, RequestSpec},
+    },
+    util::{
+        blocklist, fetcher,
+        parser::{self, normalize_feed_url, parse},
+    },
+};
+
+static CHANNEL_ID_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^UC[\w-]{22}$").unwrap());
+static CHANNEL_PAGE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?:youtube\.com/channel/|"externalId":"|"channelId":")(UC[\w-]{22})"#).unwrap()
+});
+
+static FEED_LINK_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)<link\b[^>]*\btype\s*=\s*["']application/(?:rss|atom)\+xml["'][^>]*>"#)
+        .unwrap()
+});
+static HREF_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?i)\bhref\s*=\s*["']([^"']+)["']"#).unwrap());
+
+#[derive(Debug, Default)]
+struct AddOptions {
+    title: Option<String>,
+    color: Option<u32>,
+    mention_role_id: Option<u64>,
+    request: Option<RequestSpec>,
+}
+
+#[derive(Debug)]
+pub enum FeedError {
+    Unreachable(String),
+    HttpStatus(u16),
+    TooLarge(usize),
+    NotAFeed { discovered: Option<String> },
+    ContentType(String),
+    PrivateAddress,
+    TooManyItems(usize),
+    Timeout,
+}
+
+impl std::fmt::Display for FeedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FeedError::Unreachable(reason) => write!(f, "unreachable ({})", reason),
+            FeedError::HttpStatus(status) => write!(f, "HTTP {}", status),
+            FeedError::TooLarge(size) => write!(f, "too large ({:.1}MB)", *size as f64 / 1e6),
+            FeedError::NotAFeed { .. } => write!(f, "not a valid feed"),
+            FeedError::ContentType(content_type) => {
+                write!(f, "not a feed content-type ({})", content_type)
+            }
+            FeedError::PrivateAddress => write!(f, "private or reserved address"),
+            FeedError::TooManyItems(count) => write!(f, "too many items ({})", count),
+            FeedError::Timeout => write!(f, "timed out"),
+        }
+    }
+}
+
+impl std::error::Error for FeedError {}
+
+impl FeedError {
+    fn from_request(error: reqwest::Error) -> Self {
+        if error.is_timeout() {
+            FeedError::Timeout
+        } else {
+            FeedError::Unreachable(error.to_string())
+        }
+    }
+
+    fn message(&self) -> String {
+        match self {
+            FeedError::Unreachable(_) => "Couldn't reach that URL. Check that it's spelled \
+                                          correctly and that the site is up, then try again."
+                .to_string(),
+            FeedError::HttpStatus(status @ (404 | 410)) => format!(
+                "The server says that page doesn't exist (HTTP {}). The feed may have moved, so \
+                 check the site for its current feed link.",
+                status
+            ),
+            FeedError::HttpStatus(status @ (401 | 403)) => format!(
+                "The server refused access (HTTP {}). The feed may be private or block bots.",
+                status
+            ),
+            FeedError::HttpStatus(429) => {
+                "The server is rate limiting requests (HTTP 429). Try again in a few minutes."
+                    .to_string()
+            }
+            FeedError::HttpStatus(status) if *status >= 500 => format!(
+                "The server had an error (HTTP {}). This is usually temporary, so try again later.",
+                status
+            ),
+            FeedError::HttpStatus(status) => {
+                format!("The server responded with HTTP {}.", status)
+            }
+            FeedError::TooLarge(size) => format!(
+                "That feed is too large ({:.1}MB, limit is {:.1}MB). Look for a smaller feed, \
+                 such as a category or tag feed.",
+                *size as f64 / 1e6,
+                fetcher::limits().max_feed_bytes as f64 / 1e6
+            ),
+            FeedError::NotAFeed {
+                discovered: Some(feed_url),
+            } => format!(
+                "This looks like a web page, not a feed. It links to a feed at `{}`. Try adding \
+                 that instead.",
+                feed_url
+            ),
+            FeedError::NotAFeed { discovered: None } => {
+                "This looks like a web page, not a feed. Try the site's `/feed` or `/rss` URL, or \
+                 look for an RSS link on the page."
+                    .to_string()
+            }
+            FeedError::ContentType(content_type) => format!(
+                "That URL returned `{}`, not a feed. Check that it points at the RSS or Atom \
+                 feed itself.",
+                content_type
+            ),
+            FeedError::PrivateAddress => "That URL points at a private or reserved network \
+                                           address, which feeds can't be fetched from."
+                .to_string(),
+            FeedError::TooManyItems(count) => format!(
+                "That feed has {} items (limit is {}). Look for a smaller feed, such as a \
+                 category or tag feed.",
+                count,
+                fetcher::limits().max_feed_items
+            ),
+            FeedError::Timeout => "The feed took too long to respond. The server might be slow \
+                                   or the feed too large."
+                .to_string(),
+        }
+    }
+}
 
 pub async fn execute(
     ctx: &Context,
     command: &CommandInteraction,
-    database: &Arc<Database>,
+    database: &Arc<dyn FeedStore>,
+    client: &Client,
 ) -> Result<()> {
     let url = extract_url(command)?;
-    let channel = extract_channel(command);
 
     if !validate_url(&url) {
         return respond_error(command, &ctx.http, "Invalid URL format.").await;
     }
 
     let guild_id = command.guild_id.unwrap().get();
-    let channel_id = channel.get();
+    let options = match extract_options(command, guild_id) {
+        Ok(options) => options,
+        Err(e) => return respond_error(command, &ctx.http, &e.to_string()).await,
+    };
+    let channel_id = extract_channel(command, database, guild_id).await?.get();
+
+    defer_response(command, &ctx.http).await?;
+
+    let (url, resolved) = match resolve_youtube(client, &url).await {
+        Ok(Some(feed_url)) => (feed_url, Some("YouTube channel")),
+        Ok(None) => match resolve_social(&url) {
+            Some((feed_url, source)) => (feed_url, Some(source)),
+            None => match resolve_mastodon(client, &url).await {
+                Some(feed_url) => (feed_url, Some("Mastodon profile")),
+                None => (url, None),
+            },
+        },
+        Err(e) => {
+            return edit_error(
+                command,
+                &ctx.http,
+                &format!("Couldn't resolve that YouTube channel: {}", e),
+            )
+            .await;
+        }
+    };
+
+    let url = if options.request.is_some() {
+        normalize_feed_url(&url)
+    } else {
+        prefer_https(client, &normalize_feed_url(&url)).await
+    };
+
+    if let Some(pattern) = blocklist::blocked(&url) {
+        return edit_error(command, &ctx.http, &blocklist::message(&url, pattern)).await;
+    }
 
     if database.duplicate(guild_id, channel_id, &url).await? {
-        return respond_error(
+        return edit_error(
             command,
             &ctx.http,
             &format!("This feed is already added to <#{}>.", channel_id),
@@ -37,8 +208,155 @@ pub async fn execute(
         .await;
     }
 
-    defer_response(command, &ctx.http).await?;
-    process_feed(ctx, command, database, &url, guild_id, channel_id).await
+    process_feed(
+        ctx, command, database, client, &url, resolved, guild_id, channel_id, &options,
+    )
+    .await
+}
+
+type FeedMapping = fn(&Url, &[&str]) -> Option<String>;
+
+static SOCIAL_FEEDS: &[(&str, FeedMapping)] = &[
+    ("subreddit", |url, segments| {
+        match (url.host_str()?, segments) {
+            (host, ["r", subreddit, ..]) if is_reddit(host) => {
+                Some(format!("https://www.reddit.com/r/{}/.rss", subreddit))
+            }
+            _ => None,
+        }
+    }),
+    ("Reddit user", |url, segments| {
+        match (url.host_str()?, segments) {
+            (host, ["u" | "user", user, ..]) if is_reddit(host) => {
+                Some(format!("https://www.reddit.com/user/{}/.rss", user))
+            }
+            _ => None,
+        }
+    }),
+    ("Medium profile", |url, segments| {
+        match (url.host_str()?, segments) {
+            ("medium.com", [profile]) if profile.starts_with('@') => {
+                Some(format!("https://medium.com/feed/{}", profile))
+            }
+            _ => None,
+        }
+    }),
+];
+
+async fn prefer_https(client: &Client, url: &str) -> String {
+    let Some(rest) = url.strip_prefix("http://") else {
+        return url.to_string();
+    };
+
+    let https = format!("https://{}", rest);
+    match timeout(Duration::from_secs(10), fetcher::single(client, &https)).await {
+        Ok(Ok(_)) => https,
+        _ => url.to_string(),
+    }
+}
+
+fn is_reddit(host: &str) -> bool {
+    matches!(
+        host,
+        "reddit.com" | "www.reddit.com" | "old.reddit.com" | "new.reddit.com" | "m.reddit.com"
+    )
+}
+
+fn resolve_social(url: &str) -> Option<(String, &'static str)> {
+    let parsed = Url::parse(url).ok()?;
+    let segments: Vec<&str> = parsed
+        .path_segments()
+        .map(|segments| segments.filter(|s| !s.is_empty()).collect())
+        .unwrap_or_default();
+
+    SOCIAL_FEEDS.iter().find_map(|(source, mapping)| {
+        mapping(&parsed, &segments).map(|feed_url| (feed_url, *source))
+    })
+}
+
+// `/@name` is also how TikTok, Threads and plenty of other sites link a
+// profile, so the mapping only applies once the host answers the Mastodon
+// instance API.
+async fn resolve_mastodon(client: &Client, url: &str) -> Option<String> {
+    let parsed = Url::parse(url).ok()?;
+    let profile = match parsed
+        .path_segments()?
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        [profile] if profile.starts_with('@') && !profile.contains('.') => profile.to_string(),
+        _ => return None,
+    };
+
+    let origin = parsed.origin().ascii_serialization();
+    let response = client
+        .get(format!("{}/api/v1/instance", origin))
+        .timeout(Duration::from_secs(10))
+        .send()
+        .await
+        .ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+    let instance: serde_json::Value = response.json().await.ok()?;
+    instance.get("uri")?;
+
+    Some(format!("{}/{}.rss", origin, profile))
+}
+
+async fn resolve_youtube(client: &Client, url: &str) -> Result<Option<String>> {
+    let Ok(parsed) = Url::parse(url) else {
+        return Ok(None);
+    };
+
+    let host = parsed.host_str().unwrap_or_default().to_lowercase();
+    let host = host.strip_prefix("www.").unwrap_or(&host);
+    if !matches!(host, "youtube.com" | "m.youtube.com" | "youtu.be") {
+        return Ok(None);
+    }
+
+    if parsed.path().starts_with("/feeds/") {
+        return Ok(None);
+    }
+
+    let segments: Vec<&str> = parsed
+        .path_segments()
+        .map(|segments| segments.filter(|s| !s.is_empty()).collect())
+        .unwrap_or_default();
+
+    let channel_id = match segments.as_slice() {
+        ["channel", id, ..] if CHANNEL_ID_REGEX.is_match(id) => id.to_string(),
+        _ => scrape_channel_id(client, &parsed).await?,
+    };
+
+    Ok(Some(format!(
+        "https://www.youtube.com/feeds/videos.xml?channel_id={}",
+        channel_id
+    )))
+}
+
+async fn scrape_channel_id(client: &Client, url: &Url) -> Result<String> {
+    let mut page_url = url.clone();
+    if page_url.host_str() == Some("m.youtube.com") {
+        let _ = page_url.set_host(Some("www.youtube.com"));
+    }
+
+    let response = client
+        .get(page_url)
+        .header(reqwest::header::COOKIE, "CONSENT=YES+")
+        .timeout(Duration::from_secs(10))
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!("HTTP {}", response.status()));
+    }
+
+    let page = response.text().await?;
+    CHANNEL_PAGE_REGEX
+        .captures(&page)
+        .map(|captures| captures[1].to_string())
+        .ok_or_else(|| anyhow::anyhow!("no channel ID found on the page"))
 }
 
 fn extract_url(command: &CommandInteraction) -> Result<String> {
@@ -52,14 +370,78 @@ fn extract_url(command: &CommandInteraction) -> Result<String> {
         .ok_or_else(|| anyhow::anyhow!("URL is required"))
 }
 
-fn extract_channel(command: &CommandInteraction) -> serenity::model::id::ChannelId {
-    command
+fn extract_options(command: &CommandInteraction, guild_id: u64) -> Result<AddOptions> {
+    let mut options = AddOptions::default();
+
+    for option in &command.data.options {
+        match option.name.as_str() {
+            "title" => {
+                let title = option.value.as_str().unwrap_or_default().trim();
+                if title.is_empty() || title.chars().count() > 256 {
+                    return Err(anyhow::anyhow!(
+                        "Titles must be between 1 and 256 characters."
+                    ));
+                }
+                options.title = Some(title.to_string());
+            }
+            "color" => {
+                let hex = option.value.as_str().unwrap_or_default();
+                options.color = Some(parse_hex(hex).ok_or_else(|| {
+                    anyhow::anyhow!("Invalid color. Use a hex value like `#FF0000`.")
+                })?);
+            }
+            "mention" => {
+                let role = option
+                    .value
+                    .as_role_id()
+                    .ok_or_else(|| anyhow::anyhow!("Invalid role."))?;
+                if role.get() == guild_id {
+                    return Err(anyhow::anyhow!(
+                        "Feeds can't mention @everyone. Pick a specific role."
+                    ));
+                }
+                options.mention_role_id = Some(role.get());
+            }
+            "request" => {
+                let json = option.value.as_str().unwrap_or_default();
+                options.request = Some(fetcher::request_spec(json).map_err(|e| {
+                    anyhow::anyhow!(
+                        "Invalid request spec: {}. Use JSON like `{{\"method\": \"POST\", \
+                         \"body\": {{}}, \"headers\": {{}}}}`.",
+                        e
+                    )
+                })?);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(options)
+}
+
+async fn extract_channel(
+    command: &CommandInteraction,
+    database: &Arc<dyn FeedStore>,
+    guild_id: u64,
+) -> Result<serenity::model::id::ChannelId> {
+    let channel = command
         .data
         .options
         .iter()
         .find(|opt| opt.name == "channel")
-        .and_then(|opt| opt.value.as_channel_id())
-        .unwrap_or(command.channel_id)
+        .and_then(|opt| opt.value.as_channel_id());
+
+    if let Some(channel) = channel {
+        return Ok(channel);
+    }
+
+    let default = database
+        .get_settings(guild_id)
+        .await?
+        .and_then(|settings| settings.rss_channel_id)
+        .map(|channel_id| serenity::model::id::ChannelId::new(channel_id as u64));
+
+    Ok(default.unwrap_or(command.channel_id))
 }
 
 fn validate_url(url: &str) -> bool {
@@ -80,6 +462,16 @@ async fn respond_error(
     Ok(())
 }
 
+async fn edit_error(
+    command: &CommandInteraction,
+    http: &serenity::http::Http,
+    message: &str,
+) -> Result<()> {
+    let edit_response = EditInteractionResponse::new().content(message);
+    command.edit_response(http, edit_response).await?;
+    Ok(())
+}
+
 async fn defer_response(command: &CommandInteraction, http: &serenity::http::Http) -> Result<()> {
     let response =
         CreateInteractionResponse::Defer(CreateInteractionResponseMessage::new().ephemeral(true));
@@ -87,15 +479,23 @@ async fn defer_response(command: &CommandInteraction, http: &serenity::http::Htt
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn process_feed(
     ctx: &Context,
     command: &CommandInteraction,
-    database: &Arc<Database>,
+    database: &Arc<dyn FeedStore>,
+    client: &Client,
     url: &str,
+    resolved: Option<&str>,
     guild_id: u64,
     channel_id: u64,
+    options: &AddOptions,
 ) -> Result<()> {
-    let validation_result = timeout(Duration::from_secs(15), validate_feed(url)).await;
+    let validation_result = timeout(
+        Duration::from_secs(15),
+        validate_feed(client, url, options.request.as_ref()),
+    )
+    .await;
 
     match validation_result {
         Ok(Ok((feed, content_size))) => {
@@ -104,93 +504,155 @@ async fn process_feed(
                 command,
                 database,
                 url,
+                resolved,
                 guild_id,
                 channel_id,
+                options,
                 feed,
                 content_size,
             )
             .await
         }
-        Ok(Err(e)) => {
-            let edit_response = EditInteractionResponse::new()
-                .content(format!("Failed to validate RSS feed: {}", e));
-            command.edit_response(&ctx.http, edit_response).await?;
-            Ok(())
-        }
-        Err(_) => {
-            let edit_response = EditInteractionResponse::new().content(
-                "Feed validation timed out (15s limit). The feed might be too large or slow to \
-                 respond.",
-            );
-            command.edit_response(&ctx.http, edit_response).await?;
-            Ok(())
-        }
+        Ok(Err(e)) => edit_error(command, &ctx.http, &e.message()).await,
+        Err(_) => edit_error(command, &ctx.http, &FeedError::Timeout.message()).await,
     }
 }
 
-async fn validate_feed(url: &str) -> Result<(feed_rs::model::Feed, usize)> {
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(10))
-        .user_agent("Mozilla/5.0 RSS Bot")
-        .build()?;
-
-    let head_response = client.head(url).send().await;
-    if head_response.is_err() {
-        return Err(anyhow::anyhow!("Unable to reach the URL"));
+pub async fn validate_feed(
+    client: &Client,
+    url: &str,
+    request: Option<&RequestSpec>,
+) -> std::result::Result<(feed_rs::model::Feed, usize), FeedError> {
+    if let Err(e) = fetcher::check_destination(url).await {
+        return Err(if e.is::<fetcher::PrivateAddress>() {
+            FeedError::PrivateAddress
+        } else {
+            FeedError::Unreachable(e.to_string())
+        });
+    }
+    if request.is_none() {
+        client
+            .head(url)
+            .timeout(Duration::from_secs(10))
+            .send()
+            .await
+            .map_err(FeedError::from_request)?;
     }
 
-    let response = client.get(url).send().await?;
+    let response = fetcher::request(client, url, request)
+        .timeout(Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(FeedError::from_request)?;
     if !response.status().is_success() {
-        return Err(anyhow::anyhow!("HTTP {}", response.status()));
+        return Err(FeedError::HttpStatus(response.status().as_u16()));
     }
+    fetcher::check_content_type(response.headers(), request.is_some())
+        .map_err(|unsupported| FeedError::ContentType(unsupported.0))?;
 
+    let limits = fetcher::limits();
     if let Some(content_length) = response.content_length() {
-        if content_length > 5_000_000 {
-            return Err(anyhow::anyhow!(
-                "Feed is too large ({} bytes). Please use a smaller feed.",
-                content_length
-            ));
+        if content_length as usize > limits.max_feed_bytes {
+            return Err(FeedError::TooLarge(content_length as usize));
         }
     }
 
-    let content = response.text().await?;
-    if content.len() > 5_000_000 {
-        return Err(anyhow::anyhow!(
-            "Feed content is too large. Please use a smaller feed."
-        ));
+    let content = response.text().await.map_err(FeedError::from_request)?;
+    if content.len() > limits.max_feed_bytes {
+        return Err(FeedError::TooLarge(content.len()));
     }
 
-    let parsed_feed = parse(&content)?;
+    let parsed_feed = parse(&content).map_err(|_| FeedError::NotAFeed {
+        discovered: discover_feed(url, &content),
+    })?;
 
-    if parsed_feed.entries.len() > 500 {
-        return Err(anyhow::anyhow!(
-            "Feed has {} items, which is too many. Please use a feed with fewer items.",
-            parsed_feed.entries.len()
-        ));
+    if parsed_feed.entries.len() > limits.max_feed_items {
+        return Err(FeedError::TooManyItems(parsed_feed.entries.len()));
     }
 
     Ok((parsed_feed, content.len()))
 }
 
+fn update_frequency(feed: &feed_rs::model::Feed) -> Option<String> {
+    let mut dates: Vec<_> = feed
+        .entries
+        .iter()
+        .filter_map(|entry| entry.published.or(entry.updated))
+        .collect();
+    dates.sort_unstable_by(|a, b| b.cmp(a));
+    dates.truncate(20);
+
+    let mut gaps: Vec<i64> = dates
+        .windows(2)
+        .map(|pair| (pair[0] - pair[1]).num_seconds())
+        .collect();
+    if gaps.len() < 2 {
+        return None;
+    }
+    gaps.sort_unstable();
+
+    let median = gaps[gaps.len() / 2];
+    if median <= 0 {
+        return None;
+    }
+
+    let per_day = 86_400.0 / median as f64;
+    Some(if per_day >= 1.5 {
+        format!("~{:.0}×/day", per_day)
+    } else if per_day * 7.0 >= 1.5 {
+        format!("~{:.0}×/week", per_day * 7.0)
+    } else {
+        format!("about every {} days", (median as f64 / 86_400.0).round())
+    })
+}
+
+fn discover_feed(url: &str, content: &str) -> Option<String> {
+    let link = FEED_LINK_REGEX.find(content)?;
+    let href = HREF_REGEX.captures(link.as_str())?.get(1)?.as_str();
+    let href = html_escape::decode_html_entities(href);
+    Url::parse(url)
+        .ok()?
+        .join(&href)
+        .ok()
+        .map(|u| u.to_string())
+}
+
+#[allow(clippy::too_many_arguments)]
 async fn handle_valid_feed(
     ctx: &Context,
     command: &CommandInteraction,
-    database: &Arc<Database>,
+    database: &Arc<dyn FeedStore>,
     url: &str,
+    resolved: Option<&str>,
     guild_id: u64,
     channel_id: u64,
+    options: &AddOptions,
     feed: feed_rs::model::Feed,
     content_size: usize,
 ) -> Result<()> {
-    database
-        .add(
-            guild_id,
-            channel_id,
-            url,
-            feed.title.as_ref().map(|t| t.content.as_str()),
-            None,
-        )
-        .await?;
+    let defaults = database.channel_defaults(channel_id).await?;
+    let new_feed = NewFeed {
+        guild_id,
+        channel_id,
+        url,
+        title: options
+            .title
+            .as_deref()
+            .or(feed.title.as_ref().map(|t| t.content.as_str())),
+        custom_title: options.title.is_some(),
+        added_by: Some(command.user.id.get()),
+        color: options.color,
+        mention_role_id: options.mention_role_id,
+        request: options.request.as_ref(),
+        ..Default::default()
+    }
+    .inherit(defaults.as_ref());
+    let (color, mention_role_id, image_mode) = (
+        new_feed.color,
+        new_feed.mention_role_id,
+        new_feed.image_mode,
+    );
+    database.add(new_feed).await?;
 
     let item_count = feed.entries.len();
 
@@ -200,13 +662,147 @@ async fn handle_valid_feed(
         "Unknown".to_string()
     };
 
-    let edit_response = EditInteractionResponse::new().content(format!(
+    let mut content = format!(
         "Successfully added `{}` → <#{}> | {} items • {:.1}KB",
         domain,
         channel_id,
         item_count,
         content_size as f64 / 1024.0
-    ));
+    );
+    if let Some(source) = resolved {
+        content.push_str(&format!("\nResolved {} URL to `{}`", source, url));
+    }
+    if let Some(title) = &options.title {
+        content.push_str(&format!("\nTitle: {}", title));
+    }
+    if let Some(color) = color {
+        content.push_str(&format!("\nColor: `#{:06X}`", color));
+    }
+    if let Some(role) = mention_role_id {
+        content.push_str(&format!("\nMentions <@&{}> on new posts", role));
+    }
+    if let Some(mode) = image_mode {
+        content.push_str(&format!("\nImages: {}", mode.as_str()));
+    }
+    if let Some(request) = &options.request {
+        content.push_str(&format!("\nFetched with a {} request", request.method));
+    }
+    if let Some(frequency) = update_frequency(&feed) {
+        content.push_str(&format!("\nThis feed posts {}", frequency));
+    }
+    let issues = parser::issues(&feed);
+    if !issues.is_empty() {
+        content.push_str("\n\n⚠️ This feed may not post reliably:");
+        for issue in issues {
+            content.push_str(&format!("\n• {}", issue));
+        }
+    }
+
+    let edit_response = EditInteractionResponse::new().content(content);
     command.edit_response(&ctx.http, edit_response).await?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn social_urls_map_to_their_feeds() {
+        let cases = [
+            (
+                "https://www.reddit.com/r/rust/",
+                "https://www.reddit.com/r/rust/.rss",
+                "subreddit",
+            ),
+            (
+                "https://old.reddit.com/r/rust/comments/abc123/title",
+                "https://www.reddit.com/r/rust/.rss",
+                "subreddit",
+            ),
+            (
+                "https://reddit.com/u/spez",
+                "https://www.reddit.com/user/spez/.rss",
+                "Reddit user",
+            ),
+            (
+                "https://m.reddit.com/user/spez/submitted",
+                "https://www.reddit.com/user/spez/.rss",
+                "Reddit user",
+            ),
+            (
+                "https://medium.com/@bob",
+                "https://medium.com/feed/@bob",
+                "Medium profile",
+            ),
+        ];
+
+        for (input, feed_url, source) in cases {
+            assert_eq!(
+                resolve_social(input),
+                Some((feed_url.to_string(), source)),
+                "mapping {}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn other_urls_are_left_alone() {
+        let cases = [
+            "https://example.com/feed.xml",
+            "https://example.com/r/rust",
+            "https://medium.com/@bob/a-post-title",
+            "https://example.com/@user.name",
+            "https://mastodon.social/@alice",
+            "https://www.tiktok.com/@someone",
+            "https://www.threads.net/@someone",
+            "https://www.reddit.com/",
+            "not a url",
+        ];
+
+        for input in cases {
+            assert_eq!(resolve_social(input), None, "mapping {}", input);
+        }
+    }
+
+    async fn serve(mastodon: bool) -> String {
+        let mut app = axum::Router::new();
+        if mastodon {
+            app = app.route(
+                "/api/v1/instance",
+                axum::routing::get(|| async {
+                    (
+                        [(axum::http::header::CONTENT_TYPE, "application/json")],
+                        r#"{"uri":"social.example","title":"Example"}"#,
+                    )
+                }),
+            );
+        }
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{}", address)
+    }
+
+    #[tokio::test]
+    async fn mastodon_profiles_need_an_instance() {
+        let client = Client::new();
+
+        let base = serve(true).await;
+        assert_eq!(
+            resolve_mastodon(&client, &format!("{}/@alice/", base)).await,
+            Some(format!("{}/@alice.rss", base))
+        );
+        assert_eq!(
+            resolve_mastodon(&client, &format!("{}/@alice/123", base)).await,
+            None
+        );
+
+        let base = serve(false).await;
+        assert_eq!(
+            resolve_mastodon(&client, &format!("{}/@someone", base)).await,
+            None
+        );
+    }
+}
@@ -0,0 +1,91 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use serenity::{
+    all::{CommandInteraction, CreateInteractionResponse, CreateInteractionResponseMessage},
+    prelude::*,
+};
+
+use crate::{
+    data::{FeedStore, models::PostOrder},
+    scheduler::tasks::DEFAULT_MAX_PER_CYCLE,
+};
+
+pub const MAX_PER_CYCLE_LIMIT: u64 = 10;
+
+pub async fn execute(
+    ctx: &Context,
+    command: &CommandInteraction,
+    database: &Arc<dyn FeedStore>,
+) -> Result<()> {
+    let url = command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "url")
+        .and_then(|opt| opt.value.as_str())
+        .map(|s| s.to_string())
+        .ok_or_else(|| anyhow::anyhow!("URL is required"))?;
+    let order = command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "order")
+        .and_then(|opt| opt.value.as_str())
+        .and_then(PostOrder::parse);
+    let max_per_cycle = command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "max_per_cycle")
+        .and_then(|opt| opt.value.as_i64())
+        .map(|max| max.clamp(1, MAX_PER_CYCLE_LIMIT as i64) as i32);
+    let guild_id = command.guild_id.unwrap().get();
+
+    if (order.is_some() || max_per_cycle.is_some())
+        && !database
+            .set_behavior(guild_id, &url, order, max_per_cycle)
+            .await?
+    {
+        return respond(command, &ctx.http, "RSS feed not found.").await;
+    }
+
+    let Some(feed) = database.find_in_guild(guild_id, &url, None).await? else {
+        return respond(command, &ctx.http, "RSS feed not found.").await;
+    };
+
+    let order = match feed.post_order {
+        PostOrder::Newest => "newest first",
+        PostOrder::Oldest => "oldest first",
+    };
+    let max_per_cycle = feed
+        .max_per_cycle
+        .map_or(DEFAULT_MAX_PER_CYCLE, |max| max as usize);
+
+    respond(
+        command,
+        &ctx.http,
+        &format!(
+            "{} posts up to {} new item{} per check, {}.",
+            url,
+            max_per_cycle,
+            if max_per_cycle == 1 { "" } else { "s" },
+            order
+        ),
+    )
+    .await
+}
+
+async fn respond(
+    command: &CommandInteraction,
+    http: &serenity::http::Http,
+    content: &str,
+) -> Result<()> {
+    let response = CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true),
+    );
+    command.create_response(http, response).await?;
+    Ok(())
+}
@@ -0,0 +1,143 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use serenity::{
+    all::{CommandInteraction, CreateInteractionResponse, CreateInteractionResponseMessage},
+    prelude::*,
+};
+
+use crate::{
+    cmd::color::parse_hex,
+    data::{
+        FeedStore,
+        models::{ChannelDefaults, ImageMode},
+    },
+};
+
+pub async fn execute(
+    ctx: &Context,
+    command: &CommandInteraction,
+    database: &Arc<dyn FeedStore>,
+) -> Result<()> {
+    let guild_id = command.guild_id.unwrap().get();
+    let channel_id = command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "channel")
+        .and_then(|opt| opt.value.as_channel_id())
+        .unwrap_or(command.channel_id)
+        .get();
+
+    let clear = command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "clear")
+        .and_then(|opt| opt.value.as_bool())
+        .unwrap_or(false);
+
+    if clear {
+        database
+            .set_channel_defaults(guild_id, channel_id, None)
+            .await?;
+        return respond(
+            command,
+            &ctx.http,
+            &format!("Cleared feed defaults for <#{}>.", channel_id),
+        )
+        .await;
+    }
+
+    let mut defaults = database
+        .channel_defaults(channel_id)
+        .await?
+        .unwrap_or_default();
+    let mut changed = false;
+
+    for option in &command.data.options {
+        match option.name.as_str() {
+            "color" => {
+                let input = option.value.as_str().unwrap_or_default().trim();
+                defaults.color = if input.eq_ignore_ascii_case("default") {
+                    None
+                } else {
+                    match parse_hex(input) {
+                        Some(color) => Some(color),
+                        None => {
+                            return respond(
+                                command,
+                                &ctx.http,
+                                "Invalid color. Use a hex value like `#FF0000`, or `default`.",
+                            )
+                            .await;
+                        }
+                    }
+                };
+                changed = true;
+            }
+            "mention" => {
+                defaults.mention_role_id = option.value.as_role_id().map(|role| role.get() as i64);
+                changed = true;
+            }
+            "image" => {
+                defaults.image_mode = option.value.as_str().and_then(ImageMode::parse);
+                changed = true;
+            }
+            _ => {}
+        }
+    }
+
+    if changed {
+        database
+            .set_channel_defaults(guild_id, channel_id, Some(&defaults))
+            .await?;
+    }
+
+    let content = if defaults.is_empty() {
+        format!(
+            "<#{}> has no feed defaults. New feeds there use the normal settings.",
+            channel_id
+        )
+    } else {
+        format!(
+            "{} for <#{}>, used when a new feed doesn't set its own:\n{}",
+            if changed {
+                "Updated feed defaults"
+            } else {
+                "Feed defaults"
+            },
+            channel_id,
+            describe(&defaults)
+        )
+    };
+    respond(command, &ctx.http, &content).await
+}
+
+fn describe(defaults: &ChannelDefaults) -> String {
+    let mut lines = Vec::new();
+    if let Some(color) = defaults.color {
+        lines.push(format!("• Color: `#{:06X}`", color));
+    }
+    if let Some(role) = defaults.mention_role_id {
+        lines.push(format!("• Mention: <@&{}>", role));
+    }
+    if let Some(mode) = defaults.image_mode {
+        lines.push(format!("• Images: {}", mode.as_str()));
+    }
+    lines.join("\n")
+}
+
+async fn respond(
+    command: &CommandInteraction,
+    http: &serenity::http::Http,
+    content: &str,
+) -> Result<()> {
+    let response = CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true),
+    );
+    command.create_response(http, response).await?;
+    Ok(())
+}
@@ -0,0 +1,324 @@
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use futures::StreamExt;
+use reqwest::Client;
+use serenity::{
+    all::{
+        ButtonStyle, CommandInteraction, ComponentInteraction, CreateActionRow, CreateButton,
+        CreateEmbed, CreateEmbedFooter, CreateInteractionResponse,
+        CreateInteractionResponseMessage, EditInteractionResponse,
+    },
+    prelude::*,
+};
+use tokio::{sync::Mutex, time::timeout};
+use tracing::{info, warn};
+
+use crate::{
+    cmd::remove::cleanup_webhooks,
+    data::{FeedStore, models::RequestSpec},
+    util::{fetcher, parser},
+};
+
+const STATE_TIMEOUT: Duration = Duration::from_secs(600);
+const PAGE_SIZE: usize = 10;
+// Feeds that fail are probed again after this pause, so a single blip
+// doesn't get a feed listed for removal.
+const RETRY_DELAY: Duration = Duration::from_secs(10);
+
+static STATES: std::sync::LazyLock<Mutex<HashMap<String, State>>> =
+    std::sync::LazyLock::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Clone)]
+struct State {
+    dead: Vec<(String, String)>,
+    // (channel_id, url, webhook_url) of every row the probe covered, so
+    // confirming only removes those and not feeds added since.
+    rows: Vec<(u64, String, Option<String>)>,
+    checked: usize,
+    created_at: Instant,
+}
+
+pub async fn execute(
+    ctx: &Context,
+    command: &CommandInteraction,
+    database: &Arc<dyn FeedStore>,
+    client: &Client,
+) -> Result<()> {
+    let guild_id = command.guild_id.unwrap().get();
+    let user_id = command.user.id.get();
+
+    defer(command, &ctx.http).await?;
+
+    let feeds = database.guild(guild_id).await?;
+    let requests: HashMap<String, RequestSpec> = feeds
+        .iter()
+        .filter_map(|feed| Some((feed.url.clone(), feed.request.clone()?)))
+        .collect();
+    let mut urls: Vec<String> = feeds.iter().map(|feed| feed.url.clone()).collect();
+    urls.sort();
+    urls.dedup();
+
+    if urls.is_empty() {
+        let edit_response =
+            EditInteractionResponse::new().content("This server has no feeds to check.");
+        command.edit_response(&ctx.http, edit_response).await?;
+        return Ok(());
+    }
+
+    info!(
+        "Checking {} feeds for cleanup in guild {}",
+        urls.len(),
+        guild_id
+    );
+
+    let failed = probe(client, targets(urls.iter(), &requests)).await;
+    let mut dead = if failed.is_empty() {
+        failed
+    } else {
+        tokio::time::sleep(RETRY_DELAY).await;
+        let retry = targets(failed.iter().map(|(url, _)| url), &requests);
+        probe(client, retry).await
+    };
+    dead.sort();
+
+    if dead.is_empty() {
+        let edit_response = EditInteractionResponse::new().content(format!(
+            "All {} feeds responded and parsed correctly. Nothing to clean up.",
+            urls.len()
+        ));
+        command.edit_response(&ctx.http, edit_response).await?;
+        return Ok(());
+    }
+
+    let rows = feeds
+        .into_iter()
+        .filter(|feed| dead.iter().any(|(url, _)| *url == feed.url))
+        .map(|feed| (feed.channel_id as u64, feed.url, feed.webhook_url))
+        .collect();
+    let (embed, components) = page(&dead, 0, urls.len());
+
+    {
+        let mut states = STATES.lock().await;
+        states.retain(|_, state| state.created_at.elapsed() < STATE_TIMEOUT);
+        states.insert(
+            key(guild_id, user_id),
+            State {
+                dead,
+                rows,
+                checked: urls.len(),
+                created_at: Instant::now(),
+            },
+        );
+    }
+
+    let edit_response = EditInteractionResponse::new()
+        .embed(embed)
+        .components(components);
+    command.edit_response(&ctx.http, edit_response).await?;
+    Ok(())
+}
+
+pub async fn handle_component(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+    database: &Arc<dyn FeedStore>,
+) -> Result<()> {
+    let custom_id = interaction.data.custom_id.as_str();
+    let guild_id = interaction.guild_id.unwrap().get();
+    let state_key = key(guild_id, interaction.user.id.get());
+
+    let state = {
+        let mut states = STATES.lock().await;
+        states.retain(|_, state| state.created_at.elapsed() < STATE_TIMEOUT);
+        states.get(&state_key).cloned()
+    };
+
+    let Some(state) = state else {
+        return update(
+            ctx,
+            interaction,
+            "This cleanup session has expired. Please run `/cleanup` again.",
+        )
+        .await;
+    };
+
+    if custom_id == "cleanup_cancel" {
+        STATES.lock().await.remove(&state_key);
+        return update(
+            ctx,
+            interaction,
+            "Cleanup cancelled. No feeds were removed.",
+        )
+        .await;
+    }
+
+    if custom_id == "cleanup_confirm" {
+        STATES.lock().await.remove(&state_key);
+
+        let mut removed = 0;
+        let mut webhook_urls = Vec::new();
+        for (channel_id, url, webhook_url) in state.rows {
+            match database
+                .remove_from_channel(guild_id, channel_id, &url)
+                .await
+            {
+                Ok(true) => {
+                    removed += 1;
+                    webhook_urls.extend(webhook_url);
+                }
+                Ok(false) => {}
+                Err(e) => warn!("Failed to remove dead feed {}: {}", url, e),
+            }
+        }
+
+        if removed > 0 && !webhook_urls.is_empty() {
+            cleanup_webhooks(ctx, database, guild_id, webhook_urls).await?;
+        }
+
+        return update(
+            ctx,
+            interaction,
+            &format!("Removed {} dead feed(s).", removed),
+        )
+        .await;
+    }
+
+    let page_number = custom_id
+        .strip_prefix("cleanup_page_")
+        .and_then(|page| page.parse::<usize>().ok())
+        .unwrap_or(0);
+
+    let (embed, components) = page(&state.dead, page_number, state.checked);
+
+    let response = CreateInteractionResponseMessage::new()
+        .embed(embed)
+        .components(components);
+    interaction
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::UpdateMessage(response),
+        )
+        .await?;
+    Ok(())
+}
+
+fn targets<'a>(
+    urls: impl Iterator<Item = &'a String>,
+    requests: &HashMap<String, RequestSpec>,
+) -> Vec<(String, Option<RequestSpec>)> {
+    urls.map(|url| (url.clone(), requests.get(url).cloned()))
+        .collect()
+}
+
+async fn probe(
+    client: &Client,
+    targets: Vec<(String, Option<RequestSpec>)>,
+) -> Vec<(String, String)> {
+    futures::stream::iter(targets)
+        .map(|(url, request)| async move {
+            let result = timeout(
+                Duration::from_secs(10),
+                fetcher::with_request(client, &url, request.as_ref(), false),
+            )
+            .await;
+            let reason = match result {
+                Ok(Ok(fetched)) => match parser::parse(&fetched.content) {
+                    Ok(_) => return None,
+                    Err(_) => "not a valid feed".to_string(),
+                },
+                Ok(Err(e)) => e.to_string(),
+                Err(_) => "timed out".to_string(),
+            };
+            Some((url, reason))
+        })
+        .buffer_unordered(8)
+        .filter_map(|result| async move { result })
+        .collect()
+        .await
+}
+
+fn page(
+    dead: &[(String, String)],
+    page: usize,
+    checked: usize,
+) -> (CreateEmbed, Vec<CreateActionRow>) {
+    let total_pages = dead.len().div_ceil(PAGE_SIZE);
+    let page = page.min(total_pages - 1);
+
+    let description = dead
+        .iter()
+        .skip(page * PAGE_SIZE)
+        .take(PAGE_SIZE)
+        .map(|(url, reason)| format!("• `{}`\n  {}", parser::truncate(url, 200), reason))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let embed = CreateEmbed::new()
+        .title("Dead Feeds")
+        .description(description)
+        .footer(CreateEmbedFooter::new(format!(
+            "Page {}/{} • {} of {} feeds failed",
+            page + 1,
+            total_pages,
+            dead.len(),
+            checked
+        )))
+        .color(0xf38ba8);
+
+    let navigation = vec![
+        CreateButton::new(format!("cleanup_page_{}", page.saturating_sub(1)))
+            .label("◀")
+            .style(ButtonStyle::Secondary)
+            .disabled(page == 0),
+        CreateButton::new(format!("cleanup_page_{}", page + 1))
+            .label("▶")
+            .style(ButtonStyle::Secondary)
+            .disabled(page + 1 >= total_pages),
+    ];
+    let actions = vec![
+        CreateButton::new("cleanup_confirm")
+            .label(format!("Remove {} feed(s)", dead.len()))
+            .style(ButtonStyle::Danger),
+        CreateButton::new("cleanup_cancel")
+            .label("Cancel")
+            .style(ButtonStyle::Secondary),
+    ];
+
+    (
+        embed,
+        vec![
+            CreateActionRow::Buttons(navigation),
+            CreateActionRow::Buttons(actions),
+        ],
+    )
+}
+
+async fn update(ctx: &Context, interaction: &ComponentInteraction, content: &str) -> Result<()> {
+    let response = CreateInteractionResponseMessage::new()
+        .content(content)
+        .embeds(vec![])
+        .components(vec![]);
+    interaction
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::UpdateMessage(response),
+        )
+        .await?;
+    Ok(())
+}
+
+async fn defer(command: &CommandInteraction, http: &serenity::http::Http) -> Result<()> {
+    let response =
+        CreateInteractionResponse::Defer(CreateInteractionResponseMessage::new().ephemeral(true));
+    command.create_response(http, response).await?;
+    Ok(())
+}
+
+fn key(guild_id: u64, user_id: u64) -> String {
+    format!("{}:{}", guild_id, user_id)
+}
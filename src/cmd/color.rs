@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use serenity::{
+    all::{CommandInteraction, CreateInteractionResponse, CreateInteractionResponseMessage},
+    prelude::*,
+};
+
+use crate::data::FeedStore;
+
+pub async fn execute(
+    ctx: &Context,
+    command: &CommandInteraction,
+    database: &Arc<dyn FeedStore>,
+) -> Result<()> {
+    let url = extract_option(command, "url").ok_or_else(|| anyhow::anyhow!("URL is required"))?;
+    let hex = extract_option(command, "hex").ok_or_else(|| anyhow::anyhow!("Color is required"))?;
+    let guild_id = command.guild_id.unwrap().get();
+
+    let color = if hex.eq_ignore_ascii_case("default") || hex.eq_ignore_ascii_case("reset") {
+        None
+    } else {
+        match parse_hex(&hex) {
+            Some(color) => Some(color),
+            None => {
+                return respond(
+                    command,
+                    &ctx.http,
+                    "Invalid color. Use a hex value like `#FF0000`, or `default` to reset.",
+                )
+                .await;
+            }
+        }
+    };
+
+    let content = if !database.set_color(guild_id, &url, color).await? {
+        "RSS feed not found.".to_string()
+    } else if let Some(color) = color {
+        format!("Set embed color for {} to `#{:06X}`", url, color)
+    } else {
+        format!("Reset embed color for {} to the default", url)
+    };
+
+    respond(command, &ctx.http, &content).await
+}
+
+pub fn parse_hex(input: &str) -> Option<u32> {
+    let hex = input.trim().trim_start_matches('#');
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    u32::from_str_radix(hex, 16).ok()
+}
+
+fn extract_option(command: &CommandInteraction, name: &str) -> Option<String> {
+    command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == name)
+        .and_then(|opt| opt.value.as_str())
+        .map(|s| s.to_string())
+}
+
+async fn respond(
+    command: &CommandInteraction,
+    http: &serenity::http::Http,
+    content: &str,
+) -> Result<()> {
+    let response = CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true),
+    );
+    command.create_response(http, response).await?;
+    Ok(())
+}
@@ -0,0 +1,293 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use chrono_tz::Tz;
+use serenity::{
+    all::{CommandInteraction, CreateInteractionResponse, CreateInteractionResponseMessage},
+    prelude::*,
+};
+
+use crate::{
+    data::{FeedStore, models::QuietHours},
+    util::footer,
+};
+
+pub async fn execute(
+    ctx: &Context,
+    command: &CommandInteraction,
+    database: &Arc<dyn FeedStore>,
+) -> Result<()> {
+    let guild_id = command.guild_id.unwrap().get();
+
+    let dedup = command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "dedup-channel")
+        .and_then(|opt| opt.value.as_bool());
+
+    if let Some(enabled) = dedup {
+        let channel_id = command.channel_id.get();
+        database
+            .set_channel_dedup(guild_id, channel_id, enabled)
+            .await?;
+
+        let content = if enabled {
+            format!(
+                "Cross-feed dedup enabled for <#{}>. Articles already posted there in the last \
+                 7 days will be skipped.",
+                channel_id
+            )
+        } else {
+            format!("Cross-feed dedup disabled for <#{}>.", channel_id)
+        };
+        return respond(command, &ctx.http, &content).await;
+    }
+
+    let quiet_hours = command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "quiet-hours")
+        .and_then(|opt| opt.value.as_str());
+
+    if let Some(input) = quiet_hours {
+        let content = match parse_quiet_hours(input) {
+            Ok(Some(quiet_hours)) => {
+                database
+                    .set_quiet_hours(guild_id, Some(quiet_hours))
+                    .await?;
+                format!(
+                    "Quiet hours set to {}. New articles found during this window are posted \
+                     once it ends.",
+                    format_quiet_hours(&quiet_hours)
+                )
+            }
+            Ok(None) => {
+                database.set_quiet_hours(guild_id, None).await?;
+                "Quiet hours disabled.".to_string()
+            }
+            Err(e) => e.to_string(),
+        };
+        return respond(command, &ctx.http, &content).await;
+    }
+
+    let footer_template = command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "footer")
+        .and_then(|opt| opt.value.as_str());
+
+    if let Some(input) = footer_template {
+        let input = input.trim();
+        let content = if input.eq_ignore_ascii_case("off") {
+            database.set_footer(guild_id, None).await?;
+            format!(
+                "Footer reset to the default: `{}`",
+                footer::default_template()
+            )
+        } else if input.chars().count() > footer::MAX_TEMPLATE_LENGTH {
+            format!(
+                "Footer templates can be at most {} characters.",
+                footer::MAX_TEMPLATE_LENGTH
+            )
+        } else {
+            database.set_footer(guild_id, Some(input)).await?;
+            format!(
+                "Footer set to `{}`. Example: {}",
+                input,
+                footer::render(
+                    Some(input),
+                    "Example Feed",
+                    Some(chrono::Utc::now()),
+                    database
+                        .get_settings(guild_id)
+                        .await?
+                        .map(|settings| settings.tz())
+                        .unwrap_or(Tz::UTC)
+                )
+            )
+        };
+        return respond(command, &ctx.http, &content).await;
+    }
+
+    let timezone = command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "timezone")
+        .and_then(|opt| opt.value.as_str());
+
+    if let Some(input) = timezone {
+        let input = input.trim();
+        let content = if input.eq_ignore_ascii_case("off") || input.eq_ignore_ascii_case("utc") {
+            database.set_timezone(guild_id, None).await?;
+            "Timezone reset to UTC.".to_string()
+        } else {
+            match input.parse::<Tz>() {
+                Ok(tz) => {
+                    database.set_timezone(guild_id, Some(tz.name())).await?;
+                    format!(
+                        "Timezone set to `{}`. Dates in `/list` and post footers now use it.",
+                        tz.name()
+                    )
+                }
+                Err(_) => format!(
+                    "Unknown timezone `{}`. Use an IANA name like `Europe/Berlin` or \
+                     `America/New_York`, or `off` for UTC.",
+                    input
+                ),
+            }
+        };
+        return respond(command, &ctx.http, &content).await;
+    }
+
+    let channel = command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "default-channel")
+        .and_then(|opt| opt.value.as_channel_id());
+
+    let content = match channel {
+        Some(channel) => {
+            database.set_settings(guild_id, channel.get()).await?;
+            format!(
+                "New feeds will now default to <#{}> when no channel is given.",
+                channel.get()
+            )
+        }
+        None => {
+            let settings = database.get_settings(guild_id).await?;
+
+            let channel = match settings.as_ref().and_then(|s| s.rss_channel_id) {
+                Some(channel_id) => format!("Default feed channel: <#{}>", channel_id),
+                None => "No default feed channel is set. Feeds post to the channel the command \
+                         is used in."
+                    .to_string(),
+            };
+            let quiet = match settings.as_ref().and_then(|s| s.quiet_hours) {
+                Some(quiet_hours) => format!("Quiet hours: {}", format_quiet_hours(&quiet_hours)),
+                None => "Quiet hours: off".to_string(),
+            };
+            let timezone = format!(
+                "Timezone: `{}`",
+                settings.as_ref().map(|s| s.tz()).unwrap_or(Tz::UTC).name()
+            );
+            let footer = match settings.and_then(|s| s.footer) {
+                Some(template) => format!("Footer: `{}`", template),
+                None => format!("Footer: `{}` (default)", footer::default_template()),
+            };
+
+            format!("{}\n{}\n{}\n{}", channel, quiet, timezone, footer)
+        }
+    };
+
+    respond(command, &ctx.http, &content).await
+}
+
+async fn respond(
+    command: &CommandInteraction,
+    http: &serenity::http::Http,
+    content: &str,
+) -> Result<()> {
+    let response = CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true),
+    );
+    command.create_response(http, response).await?;
+    Ok(())
+}
+
+fn parse_quiet_hours(input: &str) -> Result<Option<QuietHours>> {
+    let input = input.trim();
+    if input.eq_ignore_ascii_case("off") {
+        return Ok(None);
+    }
+
+    let usage = "Use the format `22-7 UTC+2` (start hour, end hour, optional UTC offset) or `off`.";
+    let mut parts = input.split_whitespace();
+
+    let (start, end) = parts
+        .next()
+        .and_then(|range| range.split_once('-'))
+        .ok_or_else(|| anyhow::anyhow!(usage))?;
+    let start_hour = parse_hour(start).ok_or_else(|| anyhow::anyhow!(usage))?;
+    let end_hour = parse_hour(end).ok_or_else(|| anyhow::anyhow!(usage))?;
+
+    if start_hour == end_hour {
+        return Err(anyhow::anyhow!(
+            "Quiet hours must start and end at different hours."
+        ));
+    }
+
+    let utc_offset_minutes = match parts.next() {
+        Some(offset) => parse_offset(offset).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Invalid UTC offset `{}`. Use a fixed offset like `UTC+2`, `-05:00` or `UTC`.",
+                offset
+            )
+        })?,
+        None => 0,
+    };
+
+    Ok(Some(QuietHours {
+        start_hour,
+        end_hour,
+        utc_offset_minutes,
+    }))
+}
+
+fn parse_hour(input: &str) -> Option<u8> {
+    input
+        .trim_end_matches(":00")
+        .parse::<u8>()
+        .ok()
+        .filter(|hour| *hour < 24)
+}
+
+fn parse_offset(input: &str) -> Option<i32> {
+    let upper = input.to_uppercase();
+    let offset = upper
+        .strip_prefix("UTC")
+        .or_else(|| upper.strip_prefix("GMT"))
+        .unwrap_or(&upper);
+
+    if offset.is_empty() {
+        return Some(0);
+    }
+
+    let (sign, rest) = if let Some(rest) = offset.strip_prefix('+') {
+        (1, rest)
+    } else if let Some(rest) = offset.strip_prefix('-') {
+        (-1, rest)
+    } else {
+        return None;
+    };
+
+    let (hours, minutes) = rest.split_once(':').unwrap_or((rest, "0"));
+    let hours: i32 = hours.parse().ok()?;
+    let minutes: i32 = minutes.parse().ok()?;
+
+    if hours > 14 || minutes >= 60 {
+        return None;
+    }
+
+    Some(sign * (hours * 60 + minutes))
+}
+
+fn format_quiet_hours(quiet_hours: &QuietHours) -> String {
+    let offset = quiet_hours.utc_offset_minutes;
+    let sign = if offset < 0 { '-' } else { '+' };
+
+    format!(
+        "{:02}:00–{:02}:00 (UTC{}{:02}:{:02})",
+        quiet_hours.start_hour,
+        quiet_hours.end_hour,
+        sign,
+        offset.abs() / 60,
+        offset.abs() % 60
+    )
+}
@@ -0,0 +1,126 @@
+use std::{sync::Arc, time::Instant};
+
+use anyhow::Result;
+use serenity::{
+    all::{
+        CommandInteraction, CreateEmbed, CreateInteractionResponse,
+        CreateInteractionResponseMessage, Permissions,
+    },
+    prelude::*,
+};
+
+use crate::{cmd::opinionated, data::FeedStore};
+
+const AVATAR_PATH: &str = "assets/pfp.png";
+const COLLECTIONS_PATH: &str = "opinionated";
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+pub async fn execute(
+    ctx: &Context,
+    command: &CommandInteraction,
+    database: &Arc<dyn FeedStore>,
+) -> Result<()> {
+    let checks = [
+        ("Webhook avatar", avatar().await),
+        ("Webhook permission", webhook_permission(command)),
+        ("Database", database_status(database).await),
+        ("Collections", collections().await),
+    ];
+
+    let healthy = checks.iter().all(|(_, result)| result.is_ok());
+    let mut embed =
+        CreateEmbed::new()
+            .title("Diagnostics")
+            .color(if healthy { 0xa6e3a1 } else { 0xf38ba8 });
+
+    for (name, result) in checks {
+        let value = match result {
+            Ok(detail) => format!("✅ {}", detail),
+            Err(detail) => format!("❌ {}", detail),
+        };
+        embed = embed.field(name, value, false);
+    }
+
+    let response = CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .embed(embed)
+            .ephemeral(true),
+    );
+    command.create_response(&ctx.http, response).await?;
+    Ok(())
+}
+
+async fn avatar() -> std::result::Result<String, String> {
+    let bytes = tokio::fs::read(AVATAR_PATH)
+        .await
+        .map_err(|e| format!("Couldn't read `{}`: {}", AVATAR_PATH, e))?;
+
+    if !bytes.starts_with(PNG_SIGNATURE) {
+        return Err(format!("`{}` is not a PNG image", AVATAR_PATH));
+    }
+
+    Ok(format!(
+        "`{}` loaded ({:.1}KB)",
+        AVATAR_PATH,
+        bytes.len() as f64 / 1024.0
+    ))
+}
+
+fn webhook_permission(command: &CommandInteraction) -> std::result::Result<String, String> {
+    match command.app_permissions {
+        Some(permissions) if permissions.contains(Permissions::MANAGE_WEBHOOKS) => {
+            Ok(format!("Can manage webhooks in <#{}>", command.channel_id))
+        }
+        Some(_) => Err(format!(
+            "Missing the Manage Webhooks permission in <#{}>",
+            command.channel_id
+        )),
+        None => Err("Discord didn't report the bot's permissions for this channel".to_string()),
+    }
+}
+
+async fn database_status(database: &Arc<dyn FeedStore>) -> std::result::Result<String, String> {
+    let started = Instant::now();
+    database
+        .ping()
+        .await
+        .map_err(|e| format!("Unreachable: {}", e))?;
+
+    Ok(format!("Connected ({}ms)", started.elapsed().as_millis()))
+}
+
+async fn collections() -> std::result::Result<String, String> {
+    if !std::path::Path::new(COLLECTIONS_PATH).is_dir() {
+        return Err(format!(
+            "`{}/` directory not found; /opinionated and /setup have nothing to offer",
+            COLLECTIONS_PATH
+        ));
+    }
+
+    let count = opinionated::topics()
+        .await
+        .map(|topics| topics.len())
+        .unwrap_or_default();
+    let skipped = opinionated::skipped().await;
+
+    if !skipped.is_empty() {
+        Err(format!(
+            "{} collections loaded, {} invalid file{} skipped: {}",
+            count,
+            skipped.len(),
+            if skipped.len() == 1 { "" } else { "s" },
+            skipped
+                .iter()
+                .map(|file| format!("`{}`", file))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))
+    } else if count == 0 {
+        Err(format!(
+            "`{}/` exists but no collections are loaded",
+            COLLECTIONS_PATH
+        ))
+    } else {
+        Ok(format!("{} collections loaded", count))
+    }
+}
@@ -0,0 +1,173 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use serenity::{
+    all::{CommandInteraction, CreateInteractionResponse, CreateInteractionResponseMessage},
+    prelude::*,
+};
+
+use crate::{
+    cmd::color::parse_hex,
+    data::{FeedStore, models::ImageMode},
+};
+
+const MAX_TITLE_LENGTH: usize = 256;
+
+pub async fn execute(
+    ctx: &Context,
+    command: &CommandInteraction,
+    database: &Arc<dyn FeedStore>,
+) -> Result<()> {
+    let url = extract_url(command)?;
+    let title = extract_option(command, "title");
+    let clear_title = extract_clear_title(command);
+    let color = extract_option(command, "color");
+    let format = extract_option(command, "format");
+    let guild_id = command.guild_id.unwrap().get();
+
+    let title = match (title, clear_title) {
+        (Some(_), true) => {
+            return respond(
+                command,
+                &ctx.http,
+                "Use either `title` or `clear_title`, not both.",
+            )
+            .await;
+        }
+        (None, false) => None,
+        (Some(title), false) => {
+            let title = title.trim().to_string();
+            if title.is_empty() {
+                return respond(command, &ctx.http, "Title cannot be empty.").await;
+            }
+            if title.chars().count() > MAX_TITLE_LENGTH {
+                return respond(
+                    command,
+                    &ctx.http,
+                    &format!("Title must be at most {} characters.", MAX_TITLE_LENGTH),
+                )
+                .await;
+            }
+            Some(Some(title))
+        }
+        (None, true) => Some(None),
+    };
+
+    let color = match color {
+        None => None,
+        Some(hex) if hex.eq_ignore_ascii_case("default") || hex.eq_ignore_ascii_case("reset") => {
+            Some(None)
+        }
+        Some(hex) => match parse_hex(&hex) {
+            Some(color) => Some(Some(color)),
+            None => {
+                return respond(
+                    command,
+                    &ctx.http,
+                    "Invalid color. Use a hex value like `#FF0000`, or `default` to reset.",
+                )
+                .await;
+            }
+        },
+    };
+
+    let format = match format {
+        None => None,
+        Some(format) => match ImageMode::parse(&format) {
+            Some(mode) => Some(mode),
+            None => {
+                return respond(
+                    command,
+                    &ctx.http,
+                    "Invalid format. Use `large`, `thumbnail` or `none`.",
+                )
+                .await;
+            }
+        },
+    };
+
+    if title.is_none() && color.is_none() && format.is_none() {
+        return respond(command, &ctx.http, "Nothing to change.").await;
+    }
+
+    let mut changes = Vec::new();
+    if let Some(title) = &title {
+        if !database
+            .update_feed_meta(guild_id, &url, title.as_deref())
+            .await?
+        {
+            return respond(command, &ctx.http, "RSS feed not found.").await;
+        }
+        changes.push(match title {
+            Some(title) => format!("title is now `{}`", title),
+            None => "title cleared, posts will use the domain instead".to_string(),
+        });
+    }
+    if let Some(color) = color {
+        if !database.set_color(guild_id, &url, color).await? {
+            return respond(command, &ctx.http, "RSS feed not found.").await;
+        }
+        changes.push(match color {
+            Some(color) => format!("color is now `#{:06X}`", color),
+            None => "color reset to the default".to_string(),
+        });
+    }
+    if let Some(mode) = format {
+        if !database.set_image_mode(guild_id, &url, mode).await? {
+            return respond(command, &ctx.http, "RSS feed not found.").await;
+        }
+        changes.push(format!("images are shown as `{}`", mode.as_str()));
+    }
+
+    respond(
+        command,
+        &ctx.http,
+        &format!("Updated {}: {}", url, changes.join(", ")),
+    )
+    .await
+}
+
+fn extract_url(command: &CommandInteraction) -> Result<String> {
+    command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "url")
+        .and_then(|opt| opt.value.as_str())
+        .map(|s| s.to_string())
+        .ok_or_else(|| anyhow::anyhow!("URL is required"))
+}
+
+fn extract_option(command: &CommandInteraction, name: &str) -> Option<String> {
+    command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == name)
+        .and_then(|opt| opt.value.as_str())
+        .map(|s| s.to_string())
+}
+
+fn extract_clear_title(command: &CommandInteraction) -> bool {
+    command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "clear_title")
+        .and_then(|opt| opt.value.as_bool())
+        .unwrap_or(false)
+}
+
+async fn respond(
+    command: &CommandInteraction,
+    http: &serenity::http::Http,
+    content: &str,
+) -> Result<()> {
+    let response = CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true),
+    );
+    command.create_response(http, response).await?;
+    Ok(())
+}
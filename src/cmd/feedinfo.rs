@@ -0,0 +1,164 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use serenity::{
+    all::{
+        CommandInteraction, CreateEmbed, CreateInteractionResponse,
+        CreateInteractionResponseMessage,
+    },
+    prelude::*,
+};
+
+use crate::{
+    data::{
+        FeedStore,
+        models::{Feed, ImageMode, PostOrder},
+    },
+    scheduler::tasks::is_nsfw_blocked,
+};
+
+const ERROR_PREVIEW_CHARS: usize = 70;
+
+pub async fn execute(
+    ctx: &Context,
+    command: &CommandInteraction,
+    database: &Arc<dyn FeedStore>,
+) -> Result<()> {
+    let url = command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "url")
+        .and_then(|opt| opt.value.as_str())
+        .map(|s| s.to_string())
+        .ok_or_else(|| anyhow::anyhow!("URL is required"))?;
+    let guild_id = command.guild_id.unwrap().get();
+
+    let message = match database.find_in_guild(guild_id, &url, None).await? {
+        Some(feed) => {
+            let errors = database.recent_errors(feed.id).await.unwrap_or_default();
+            CreateInteractionResponseMessage::new().embed(embed(&feed, &errors))
+        }
+        None => CreateInteractionResponseMessage::new()
+            .content(format!("No feed with the URL `{}` in this server.", url)),
+    };
+
+    command
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::Message(message.ephemeral(true)),
+        )
+        .await?;
+    Ok(())
+}
+
+fn embed(feed: &Feed, errors: &[(String, String)]) -> CreateEmbed {
+    let color = feed
+        .color
+        .map(|color| format!("`#{:06X}`", color))
+        .unwrap_or_else(|| "Default".to_string());
+    let added_by = feed
+        .added_by
+        .map(|user| format!("<@{}>", user))
+        .unwrap_or_else(|| "Unknown".to_string());
+    let mention = feed
+        .mention_role_id
+        .map(|role| format!("<@&{}>", role))
+        .unwrap_or_else(|| "None".to_string());
+    let reactions = if feed.reactions.is_empty() {
+        "None".to_string()
+    } else {
+        feed.reactions.join(" ")
+    };
+
+    let mut flags = Vec::new();
+    if feed.spoiler {
+        flags.push("Spoiler".to_string());
+    }
+    if feed.nsfw {
+        flags.push(if is_nsfw_blocked(feed.id) {
+            "NSFW (blocked: channel is not age-restricted)".to_string()
+        } else {
+            "NSFW".to_string()
+        });
+    }
+    if feed.crosspost {
+        flags.push("Crosspost".to_string());
+    }
+    if feed.image_mode != ImageMode::Large {
+        flags.push(format!("Images: {}", feed.image_mode.as_str()));
+    }
+    if feed.pin_latest {
+        flags.push("Pin latest".to_string());
+    }
+    if feed.repost_on_update {
+        flags.push("Repost on update".to_string());
+    }
+    if feed.post_order != PostOrder::Newest {
+        flags.push(format!("Order: {} first", feed.post_order.as_str()));
+    }
+    if let Some(max) = feed.max_per_cycle {
+        flags.push(format!("Up to {} per check", max));
+    }
+    if let Some(language) = &feed.translate_to {
+        flags.push(format!("Translate to `{}`", language));
+    }
+    let flags = if flags.is_empty() {
+        "None".to_string()
+    } else {
+        flags.join("\n")
+    };
+
+    let mut embed = CreateEmbed::new()
+        .title(feed.title.as_deref().unwrap_or("Untitled"))
+        .description(format!("`{}`", feed.url))
+        .field("Channel", format!("<#{}>", feed.channel_id), true)
+        .field("Color", color, true)
+        .field("Reactions", reactions, true)
+        .field("Mention", mention, true)
+        .field(
+            "Last checked",
+            timestamp(feed.last_checked_at.as_deref(), "Never"),
+            true,
+        )
+        .field(
+            "Last item",
+            timestamp(feed.last_item_date.as_deref(), "Never"),
+            true,
+        )
+        .field("Items posted", feed.items_posted.to_string(), true)
+        .field("Options", flags, true)
+        .field(
+            "Added",
+            timestamp(feed.created_at.as_deref(), "Unknown"),
+            true,
+        )
+        .field("Added by", added_by, true)
+        .color(feed.color.unwrap_or(0x89b4fa));
+
+    if !errors.is_empty() {
+        let errors = errors
+            .iter()
+            .map(|(date, message)| {
+                let message = message.replace('`', "'");
+                let message = if message.chars().count() > ERROR_PREVIEW_CHARS {
+                    let clipped: String = message.chars().take(ERROR_PREVIEW_CHARS - 1).collect();
+                    format!("{}…", clipped)
+                } else {
+                    message
+                };
+                format!("{} `{}`", timestamp(Some(date), "Unknown"), message)
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        embed = embed.field("Recent errors", errors, false);
+    }
+
+    embed
+}
+
+fn timestamp(date: Option<&str>, fallback: &str) -> String {
+    date.and_then(|date| chrono::DateTime::parse_from_rfc3339(date).ok())
+        .map(|date| format!("<t:{}:f>", date.timestamp()))
+        .unwrap_or_else(|| fallback.to_string())
+}
@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use serenity::{
+    all::{CommandInteraction, CreateInteractionResponse, CreateInteractionResponseMessage},
+    prelude::*,
+};
+
+use crate::data::FeedStore;
+
+pub async fn execute(
+    ctx: &Context,
+    command: &CommandInteraction,
+    database: &Arc<dyn FeedStore>,
+) -> Result<()> {
+    let url = command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "url")
+        .and_then(|opt| opt.value.as_str())
+        .map(|s| s.to_string())
+        .ok_or_else(|| anyhow::anyhow!("URL is required"))?;
+    let spoiler = extract_bool(command, "spoiler");
+    let nsfw = extract_bool(command, "nsfw");
+    let guild_id = command.guild_id.unwrap().get();
+
+    if spoiler.is_none() && nsfw.is_none() {
+        return respond(command, &ctx.http, "Set `spoiler`, `nsfw`, or both.").await;
+    }
+
+    if !database.set_flags(guild_id, &url, spoiler, nsfw).await? {
+        return respond(command, &ctx.http, "RSS feed not found.").await;
+    }
+
+    let mut changes = Vec::new();
+    if let Some(spoiler) = spoiler {
+        changes.push(if spoiler {
+            "posts are now hidden behind spoiler tags"
+        } else {
+            "posts are no longer spoilered"
+        });
+    }
+    if let Some(nsfw) = nsfw {
+        changes.push(if nsfw {
+            "marked NSFW, so it only posts in age-restricted channels"
+        } else {
+            "no longer marked NSFW"
+        });
+    }
+
+    respond(
+        command,
+        &ctx.http,
+        &format!("Updated {}: {}.", url, changes.join(", ")),
+    )
+    .await
+}
+
+fn extract_bool(command: &CommandInteraction, name: &str) -> Option<bool> {
+    command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == name)
+        .and_then(|opt| opt.value.as_bool())
+}
+
+async fn respond(
+    command: &CommandInteraction,
+    http: &serenity::http::Http,
+    content: &str,
+) -> Result<()> {
+    let response = CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true),
+    );
+    command.create_response(http, response).await?;
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use anyhow::Result;
+use serenity::{
+    all::{
+        CommandInteraction, CreateEmbed, CreateInteractionResponse,
+        CreateInteractionResponseMessage,
+    },
+    prelude::*,
+};
+
+pub struct CommandHelp {
+    pub name: &'static str,
+    pub summary: &'static str,
+    pub permission: Option<&'static str>,
+    pub options: &'static [(&'static str, &'static str, bool)],
+}
+
+pub async fn execute(ctx: &Context, command: &CommandInteraction) -> Result<()> {
+    let requested = command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "command")
+        .and_then(|opt| opt.value.as_str());
+
+    let embed = match requested {
+        Some(name) => match crate::COMMAND_HELP.iter().find(|help| help.name == name) {
+            Some(help) => detail(help),
+            None => CreateEmbed::new()
+                .title("Help")
+                .description(format!("There's no command called `/{}`.", name))
+                .color(0xf38ba8),
+        },
+        None => overview(),
+    };
+
+    let response = CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .embed(embed)
+            .ephemeral(true),
+    );
+    command.create_response(&ctx.http, response).await?;
+    Ok(())
+}
+
+/// Command names for `/help command:` autocomplete, capped at Discord's 25
+/// choices.
+pub fn matching(current: &str) -> impl Iterator<Item = &'static str> {
+    let current = current.trim().trim_start_matches('/').to_lowercase();
+    crate::COMMAND_HELP
+        .iter()
+        .map(|help| help.name)
+        .filter(move |name| name.contains(&current))
+        .take(25)
+}
+
+fn overview() -> CreateEmbed {
+    let description = crate::COMMAND_HELP
+        .iter()
+        .map(|help| {
+            let permission = help
+                .permission
+                .map(|permission| format!(" · *{}*", permission))
+                .unwrap_or_default();
+            format!("`/{}` {}{}", help.name, help.summary, permission)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    CreateEmbed::new()
+        .title("Commands")
+        .description(description)
+        .footer(serenity::all::CreateEmbedFooter::new(
+            "Use /help command:<name> for a command's options",
+        ))
+        .color(0x89b4fa)
+}
+
+fn detail(help: &CommandHelp) -> CreateEmbed {
+    let options = if help.options.is_empty() {
+        "No options".to_string()
+    } else {
+        help.options
+            .iter()
+            .map(|(name, description, required)| {
+                let required = if *required { " (required)" } else { "" };
+                format!("`{}`{} {}", name, required, description)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    CreateEmbed::new()
+        .title(format!("/{}", help.name))
+        .description(help.summary)
+        .field("Options", options, false)
+        .field("Permission", help.permission.unwrap_or("Everyone"), false)
+        .color(0x89b4fa)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matching_stays_within_the_choice_limit() {
+        assert!(matching("").count() <= 25);
+        assert!(matching("/Refresh").any(|name| name == "refresh-titles"));
+        assert_eq!(matching("no-such-command").count(), 0);
+    }
+}
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use serenity::{
+    all::{CommandInteraction, CreateInteractionResponse, CreateInteractionResponseMessage},
+    prelude::*,
+};
+
+use crate::data::{FeedStore, models::ImageMode};
+
+pub async fn execute(
+    ctx: &Context,
+    command: &CommandInteraction,
+    database: &Arc<dyn FeedStore>,
+) -> Result<()> {
+    let url = extract_option(command, "url").ok_or_else(|| anyhow::anyhow!("URL is required"))?;
+    let mode = extract_option(command, "mode")
+        .and_then(|mode| ImageMode::parse(&mode))
+        .ok_or_else(|| anyhow::anyhow!("Mode is required"))?;
+    let guild_id = command.guild_id.unwrap().get();
+
+    let content = if !database.set_image_mode(guild_id, &url, mode).await? {
+        "RSS feed not found.".to_string()
+    } else {
+        match mode {
+            ImageMode::Large => format!("Posts from {} will show a full-width image", url),
+            ImageMode::Thumbnail => format!("Posts from {} will show a small thumbnail", url),
+            ImageMode::None => format!("Posts from {} will no longer include images", url),
+        }
+    };
+
+    let response = CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true),
+    );
+    command.create_response(&ctx.http, response).await?;
+    Ok(())
+}
+
+fn extract_option(command: &CommandInteraction, name: &str) -> Option<String> {
+    command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == name)
+        .and_then(|opt| opt.value.as_str())
+        .map(|s| s.to_string())
+}
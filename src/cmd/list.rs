@@ -1,63 +1,162 @@
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    sync::{Arc, LazyLock},
+    time::{Duration, Instant},
+};
 
 use anyhow::Result;
+use chrono_tz::Tz;
 use serenity::{
     all::{
         ButtonStyle, CommandInteraction, ComponentInteraction, ComponentInteractionDataKind,
         CreateActionRow, CreateButton, CreateEmbed, CreateInputText, CreateInteractionResponse,
         CreateInteractionResponseMessage, CreateModal, CreateSelectMenu, CreateSelectMenuKind,
         CreateSelectMenuOption, EditInteractionResponse, InputTextStyle, ModalInteraction,
+        ReactionType,
     },
     prelude::*,
 };
+use tokio::sync::Mutex;
 use tracing::{info, warn};
 
-use crate::data::Database;
+use crate::{
+    data::{FeedStore, models::Feed},
+    scheduler::tasks::is_nsfw_blocked,
+};
 
-const FEEDS_PER_PAGE: usize = 10;
+const NOT_OWNER_MESSAGE: &str = "This isn't your list. Run `/list` to browse feeds yourself.";
+const CACHE_TTL: Duration = Duration::from_secs(300);
+pub const SELECT_OPTION_LIMIT: usize = 25;
+const DESCRIPTION_LIMIT: usize = 4096;
+pub const FIRST_EMOJI: &str = "⏮";
+pub const PREV_EMOJI: &str = "⬅";
+const JUMP_EMOJI: &str = "🔢";
+pub const NEXT_EMOJI: &str = "➡";
+pub const LAST_EMOJI: &str = "⏭";
+
+static FEED_CACHE: LazyLock<Mutex<HashMap<u64, CachedFeeds>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ListMode {
+    Flat,
+    Grouped,
+    Collapsed,
+}
+
+impl ListMode {
+    fn from_command(command: &CommandInteraction) -> Self {
+        let enabled = |name: &str| {
+            command
+                .data
+                .options
+                .iter()
+                .find(|opt| opt.name == name)
+                .and_then(|opt| opt.value.as_bool())
+                .unwrap_or(false)
+        };
+
+        if enabled("grouped") {
+            ListMode::Grouped
+        } else if enabled("collapse") {
+            ListMode::Collapsed
+        } else {
+            ListMode::Flat
+        }
+    }
+
+    fn from_custom_id(custom_id: &str) -> Self {
+        custom_id
+            .split('_')
+            .find_map(|part| match part {
+                "grouped" => Some(ListMode::Grouped),
+                "collapsed" => Some(ListMode::Collapsed),
+                _ => None,
+            })
+            .unwrap_or(ListMode::Flat)
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            ListMode::Flat => "flat",
+            ListMode::Grouped => "grouped",
+            ListMode::Collapsed => "collapsed",
+        }
+    }
+}
+
+struct CachedFeeds {
+    feeds: Arc<Vec<Feed>>,
+    created_at: Instant,
+}
 
 pub async fn execute(
     ctx: &Context,
     command: &CommandInteraction,
-    database: &Arc<Database>,
+    database: &Arc<dyn FeedStore>,
+    feeds_per_page: usize,
 ) -> Result<()> {
     let guild_id = command.guild_id.unwrap().get();
-    let feeds = database.guild(guild_id).await?;
+    let mode = ListMode::from_command(command);
+    let mut feeds = database.guild(guild_id).await?;
 
     if feeds.is_empty() {
         return respond_empty(command, &ctx.http).await;
     }
 
+    sort_feeds(&mut feeds, mode);
+
     defer_response(command, &ctx.http).await?;
 
     let page = 0;
-    let total_pages = (feeds.len() + FEEDS_PER_PAGE - 1) / FEEDS_PER_PAGE;
+    let timezone = guild_timezone(database, guild_id).await;
+    let starts = page_starts(&feeds, mode, feeds_per_page, timezone);
+    let total_pages = starts.len();
 
-    let (embed, components) = build_page_fast(&feeds, page, total_pages);
+    let (embed, components) =
+        build_page_fast(&feeds, page, &starts, mode, command.user.id.get(), timezone);
 
     let mut response = EditInteractionResponse::new().embed(embed);
     if total_pages > 1 {
         response = response.components(components);
     }
 
-    command.edit_response(&ctx.http, response).await?;
+    let message = command.edit_response(&ctx.http, response).await?;
+    if total_pages > 1 {
+        cache_feeds(message.id.get(), Arc::new(feeds)).await;
+    }
     Ok(())
 }
 
 pub async fn handle_component(
     ctx: &Context,
     interaction: &ComponentInteraction,
-    database: &Arc<Database>,
+    database: &Arc<dyn FeedStore>,
+    feeds_per_page: usize,
 ) -> Result<()> {
+    let user_id = interaction.user.id.get();
+    if extract_owner_from_custom_id(&interaction.data.custom_id) != Some(user_id) {
+        let response = CreateInteractionResponse::Message(
+            CreateInteractionResponseMessage::new()
+                .content(NOT_OWNER_MESSAGE)
+                .ephemeral(true),
+        );
+        interaction.create_response(&ctx.http, response).await?;
+        return Ok(());
+    }
+
     let guild_id = interaction.guild_id.unwrap().get();
-    let feeds = database.guild(guild_id).await?;
+    let mode = ListMode::from_custom_id(&interaction.data.custom_id);
+    let feeds = cached_feeds(database, guild_id, Some(interaction.message.id.get()), mode).await?;
 
     if feeds.is_empty() {
         warn!("No feeds found for guild {}", guild_id);
         return Ok(());
     }
 
-    let total_pages = (feeds.len() + FEEDS_PER_PAGE - 1) / FEEDS_PER_PAGE;
+    let timezone = guild_timezone(database, guild_id).await;
+    let starts = page_starts(&feeds, mode, feeds_per_page, timezone);
+    let total_pages = starts.len();
     info!(
         "Handling component interaction: {} (total pages: {})",
         interaction.data.custom_id, total_pages
@@ -74,6 +173,15 @@ pub async fn handle_component(
             );
 
             let new_page = match custom_id.split('_').next() {
+                Some("first") => {
+                    info!("Going to first page: {} -> 0", current_page);
+                    0
+                }
+                Some("last") => {
+                    let new_page = total_pages - 1;
+                    info!("Going to last page: {} -> {}", current_page, new_page);
+                    new_page
+                }
                 Some("prev") => {
                     let new_page = current_page.saturating_sub(1);
                     info!("Going to previous page: {} -> {}", current_page, new_page);
@@ -85,16 +193,17 @@ pub async fn handle_component(
                     new_page
                 }
                 Some("jump") => {
-                    let modal =
-                        CreateModal::new("page_jump_modal", "Jump to Page").components(vec![
-                            CreateActionRow::InputText(
-                                CreateInputText::new(InputTextStyle::Short, "page", "Page Number")
-                                    .placeholder(&format!("1-{}", total_pages))
-                                    .min_length(1)
-                                    .max_length(3)
-                                    .required(true),
-                            ),
-                        ]);
+                    let modal = CreateModal::new(
+                        format!("page_jump_modal_{}_{}", mode.as_str(), user_id),
+                        "Jump to Page",
+                    )
+                    .components(vec![CreateActionRow::InputText(
+                        CreateInputText::new(InputTextStyle::Short, "page", "Page Number")
+                            .placeholder(format!("1-{}", total_pages))
+                            .min_length(1)
+                            .max_length(3)
+                            .required(true),
+                    )]);
 
                     interaction
                         .create_response(&ctx.http, CreateInteractionResponse::Modal(modal))
@@ -107,7 +216,8 @@ pub async fn handle_component(
                 }
             };
 
-            let (embed, components) = build_page_fast(&feeds, new_page, total_pages);
+            let (embed, components) =
+                build_page_fast(&feeds, new_page, &starts, mode, user_id, timezone);
 
             let response_message = CreateInteractionResponseMessage::new()
                 .embed(embed)
@@ -126,7 +236,8 @@ pub async fn handle_component(
                     let page = page.saturating_sub(1);
                     info!("Selected page from dropdown: {}", page + 1);
 
-                    let (embed, components) = build_page_fast(&feeds, page, total_pages);
+                    let (embed, components) =
+                        build_page_fast(&feeds, page, &starts, mode, user_id, timezone);
 
                     let response_message = CreateInteractionResponseMessage::new()
                         .embed(embed)
@@ -150,20 +261,36 @@ pub async fn handle_component(
 pub async fn handle_modal(
     ctx: &Context,
     interaction: &ModalInteraction,
-    database: &Arc<Database>,
+    database: &Arc<dyn FeedStore>,
+    feeds_per_page: usize,
 ) -> Result<()> {
-    if interaction.data.custom_id != "page_jump_modal" {
+    if !interaction.data.custom_id.starts_with("page_jump_modal") {
+        return Ok(());
+    }
+
+    let user_id = interaction.user.id.get();
+    if extract_owner_from_custom_id(&interaction.data.custom_id) != Some(user_id) {
+        let response = CreateInteractionResponse::Message(
+            CreateInteractionResponseMessage::new()
+                .content(NOT_OWNER_MESSAGE)
+                .ephemeral(true),
+        );
+        interaction.create_response(&ctx.http, response).await?;
         return Ok(());
     }
 
     let guild_id = interaction.guild_id.unwrap().get();
-    let feeds = database.guild(guild_id).await?;
+    let mode = ListMode::from_custom_id(&interaction.data.custom_id);
+    let message_id = interaction.message.as_ref().map(|message| message.id.get());
+    let feeds = cached_feeds(database, guild_id, message_id, mode).await?;
 
     if feeds.is_empty() {
         return Ok(());
     }
 
-    let total_pages = (feeds.len() + FEEDS_PER_PAGE - 1) / FEEDS_PER_PAGE;
+    let timezone = guild_timezone(database, guild_id).await;
+    let starts = page_starts(&feeds, mode, feeds_per_page, timezone);
+    let total_pages = starts.len();
 
     let page_input = interaction
         .data
@@ -184,7 +311,7 @@ pub async fn handle_modal(
                     &ctx.http,
                     CreateInteractionResponse::Message(
                         CreateInteractionResponseMessage::new()
-                            .content(&format!(
+                            .content(format!(
                                 "Invalid page number. Please enter a number between 1 and {}.",
                                 total_pages
                             ))
@@ -196,7 +323,7 @@ pub async fn handle_modal(
         }
     };
 
-    let (embed, components) = build_page_fast(&feeds, page, total_pages);
+    let (embed, components) = build_page_fast(&feeds, page, &starts, mode, user_id, timezone);
 
     let response_message = CreateInteractionResponseMessage::new()
         .embed(embed)
@@ -210,25 +337,40 @@ pub async fn handle_modal(
 }
 
 fn build_page_fast(
-    feeds: &[crate::data::models::Feed],
+    feeds: &[Feed],
     page: usize,
-    total_pages: usize,
+    starts: &[usize],
+    mode: ListMode,
+    user_id: u64,
+    timezone: Tz,
 ) -> (CreateEmbed, Vec<CreateActionRow>) {
-    let start_idx = page * FEEDS_PER_PAGE;
-    let end_idx = std::cmp::min(start_idx + FEEDS_PER_PAGE, feeds.len());
-    let page_feeds = &feeds[start_idx..end_idx];
+    let total_pages = starts.len();
+    let page = page.min(total_pages.saturating_sub(1));
+    let entries = entry_count(feeds, mode);
+    let start_idx = starts.get(page).copied().unwrap_or(0);
+    let end_idx = starts.get(page + 1).copied().unwrap_or(entries);
+
+    let description = match mode {
+        ListMode::Flat => build_description_fast(&feeds[start_idx..end_idx], start_idx, timezone),
+        ListMode::Grouped => build_grouped_description(feeds, start_idx, end_idx, timezone),
+        ListMode::Collapsed => build_collapsed_description(feeds, start_idx, end_idx, timezone),
+    };
 
-    let description = build_description_fast(page_feeds, start_idx);
+    let total = if mode == ListMode::Collapsed {
+        format!("{} unique feeds, {} subscriptions", entries, feeds.len())
+    } else {
+        format!("{} total feeds", feeds.len())
+    };
 
     let embed = CreateEmbed::new()
         .title("RSS Feeds")
         .description(description)
         .color(0x7289da)
         .footer(serenity::all::CreateEmbedFooter::new(format!(
-            "Page {} of {} • {} total feeds",
+            "Page {} of {} • {}",
             page + 1,
             total_pages,
-            feeds.len()
+            total
         )));
 
     let mut components = Vec::new();
@@ -237,22 +379,36 @@ fn build_page_fast(
         let mut buttons = Vec::new();
 
         buttons.push(
-            CreateButton::new(format!("prev_{}", page))
-                .emoji('◀')
+            CreateButton::new(format!("first_{}_{}_{}", page, mode.as_str(), user_id))
+                .emoji(button_emoji(FIRST_EMOJI))
                 .style(ButtonStyle::Secondary)
                 .disabled(page == 0),
         );
 
         buttons.push(
-            CreateButton::new(format!("jump_{}", page))
-                .emoji('🎚')
+            CreateButton::new(format!("prev_{}_{}_{}", page, mode.as_str(), user_id))
+                .emoji(button_emoji(PREV_EMOJI))
+                .style(ButtonStyle::Secondary)
+                .disabled(page == 0),
+        );
+
+        buttons.push(
+            CreateButton::new(format!("jump_{}_{}_{}", page, mode.as_str(), user_id))
+                .emoji(button_emoji(JUMP_EMOJI))
                 .style(ButtonStyle::Primary)
-                .label(&format!("{}/{}", page + 1, total_pages)),
+                .label(format!("{}/{}", page + 1, total_pages)),
         );
 
         buttons.push(
-            CreateButton::new(format!("next_{}", page))
-                .emoji('▶')
+            CreateButton::new(format!("next_{}_{}_{}", page, mode.as_str(), user_id))
+                .emoji(button_emoji(NEXT_EMOJI))
+                .style(ButtonStyle::Secondary)
+                .disabled(page >= total_pages - 1),
+        );
+
+        buttons.push(
+            CreateButton::new(format!("last_{}_{}_{}", page, mode.as_str(), user_id))
+                .emoji(button_emoji(LAST_EMOJI))
                 .style(ButtonStyle::Secondary)
                 .disabled(page >= total_pages - 1),
         );
@@ -260,20 +416,19 @@ fn build_page_fast(
         components.push(CreateActionRow::Buttons(buttons));
 
         if total_pages > 5 {
-            let mut options = Vec::new();
-            let start_page = if page < 5 { 0 } else { page - 4 };
-            let end_page = std::cmp::min(start_page + 10, total_pages);
-
-            for i in start_page..end_page {
-                options.push(
+            let options = page_window(page, total_pages)
+                .into_iter()
+                .map(|i| {
                     CreateSelectMenuOption::new(format!("Page {}", i + 1), (i + 1).to_string())
-                        .default_selection(i == page),
-                );
-            }
+                        .default_selection(i == page)
+                })
+                .collect();
 
-            let select_menu =
-                CreateSelectMenu::new("page_select", CreateSelectMenuKind::String { options })
-                    .placeholder("Jump to page...");
+            let select_menu = CreateSelectMenu::new(
+                format!("page_select_{}_{}", mode.as_str(), user_id),
+                CreateSelectMenuKind::String { options },
+            )
+            .placeholder(format!("Page {} of {}", page + 1, total_pages));
 
             components.push(CreateActionRow::SelectMenu(select_menu));
         }
@@ -282,35 +437,316 @@ fn build_page_fast(
     (embed, components)
 }
 
-fn build_description_fast(feeds: &[crate::data::models::Feed], start_idx: usize) -> String {
+pub fn button_emoji(emoji: &str) -> ReactionType {
+    let mut emoji = emoji.trim().trim_end_matches('\u{fe0f}').to_string();
+    if emoji.chars().count() == 1 {
+        emoji.push('\u{fe0f}');
+    }
+    ReactionType::Unicode(emoji)
+}
+
+pub fn page_window(page: usize, total_pages: usize) -> Vec<usize> {
+    if total_pages <= SELECT_OPTION_LIMIT {
+        return (0..total_pages).collect();
+    }
+
+    let window = SELECT_OPTION_LIMIT - 2;
+    let start = page.saturating_sub(window / 2).min(total_pages - window);
+    let end = start + window;
+
+    let mut pages = Vec::with_capacity(SELECT_OPTION_LIMIT);
+    if start > 0 {
+        pages.push(0);
+    }
+    pages.extend(start..end);
+    if end < total_pages {
+        pages.push(total_pages - 1);
+    }
+    pages
+}
+
+fn build_description_fast(feeds: &[Feed], start_idx: usize, timezone: Tz) -> String {
     let mut description = String::new();
 
     for (i, feed) in feeds.iter().enumerate() {
         let channel_mention = format!("<#{}>", feed.channel_id);
         let domain = extract_domain(&feed.url);
 
-        let last_updated = if let Some(ref last_date) = feed.last_item_date {
-            if let Ok(parsed) = chrono::DateTime::parse_from_rfc3339(last_date) {
-                parsed.format("%b %d, %Y").to_string()
-            } else {
-                "Recently".to_string()
-            }
-        } else {
-            "Never".to_string()
-        };
-
         description.push_str(&format!(
-            "{}. `{}` → {} | Last updated: {}\n",
+            "{}. `{}` → {} | Checked: {} | New item: {}{}{}{}\n",
             start_idx + i + 1,
             domain,
             channel_mention,
-            last_updated
+            last_checked(feed, timezone),
+            last_item(feed, timezone),
+            color_label(feed),
+            flag_label(feed),
+            added_label(feed, timezone)
+        ));
+    }
+
+    description
+}
+
+fn build_grouped_description(
+    feeds: &[Feed],
+    start_idx: usize,
+    end_idx: usize,
+    timezone: Tz,
+) -> String {
+    (start_idx..end_idx)
+        .map(|i| grouped_section(feeds, i, i == start_idx, timezone))
+        .collect()
+}
+
+fn grouped_section(feeds: &[Feed], i: usize, page_start: bool, timezone: Tz) -> String {
+    let feed = &feeds[i];
+    let mut section = String::new();
+
+    if page_start || feeds[i - 1].channel_id != feed.channel_id {
+        let channel_count = feeds
+            .iter()
+            .filter(|f| f.channel_id == feed.channel_id)
+            .count();
+        let continued = i > 0 && feeds[i - 1].channel_id == feed.channel_id;
+
+        if !page_start {
+            section.push('\n');
+        }
+        section.push_str(&format!(
+            "**<#{}>** ({} feed{}{})\n",
+            feed.channel_id,
+            channel_count,
+            if channel_count == 1 { "" } else { "s" },
+            if continued { ", continued" } else { "" }
         ));
     }
 
+    section.push_str(&format!(
+        "{}. `{}` | Checked: {} | New item: {}{}{}{}\n",
+        i + 1,
+        extract_domain(&feed.url),
+        last_checked(feed, timezone),
+        last_item(feed, timezone),
+        color_label(feed),
+        flag_label(feed),
+        added_label(feed, timezone)
+    ));
+
+    section
+}
+
+fn build_collapsed_description(
+    feeds: &[Feed],
+    start_idx: usize,
+    end_idx: usize,
+    timezone: Tz,
+) -> String {
+    let mut description = String::new();
+
+    for (i, group) in feeds
+        .chunk_by(|a, b| a.url == b.url)
+        .enumerate()
+        .take(end_idx)
+        .skip(start_idx)
+    {
+        let channels = group
+            .iter()
+            .map(|feed| format!("<#{}>", feed.channel_id))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let checked = group
+            .iter()
+            .filter_map(|feed| feed.last_checked_at.as_deref())
+            .max();
+        let newest = group
+            .iter()
+            .filter_map(|feed| feed.last_item_date.as_deref())
+            .max();
+
+        let line = format!(
+            "{}. `{}` → {} | Checked: {} | New item: {}\n",
+            i + 1,
+            extract_domain(&group[0].url),
+            channels,
+            short_date(checked, timezone),
+            short_date(newest, timezone)
+        );
+
+        if description.len() + line.len() > DESCRIPTION_LIMIT - 1 {
+            description.push('…');
+            break;
+        }
+        description.push_str(&line);
+    }
+
     description
 }
 
+pub async fn guild_timezone(database: &Arc<dyn FeedStore>, guild_id: u64) -> Tz {
+    database
+        .get_settings(guild_id)
+        .await
+        .ok()
+        .flatten()
+        .map(|settings| settings.tz())
+        .unwrap_or(Tz::UTC)
+}
+
+pub fn last_item(feed: &Feed, timezone: Tz) -> String {
+    short_date(feed.last_item_date.as_deref(), timezone)
+}
+
+pub fn last_checked(feed: &Feed, timezone: Tz) -> String {
+    short_date(feed.last_checked_at.as_deref(), timezone)
+}
+
+fn short_date(date: Option<&str>, timezone: Tz) -> String {
+    if let Some(date) = date {
+        if let Ok(parsed) = chrono::DateTime::parse_from_rfc3339(date) {
+            parsed
+                .with_timezone(&timezone)
+                .format("%b %d, %Y")
+                .to_string()
+        } else {
+            "Recently".to_string()
+        }
+    } else {
+        "Never".to_string()
+    }
+}
+
+fn color_label(feed: &Feed) -> String {
+    feed.color
+        .map(|color| format!(" | `#{:06X}`", color))
+        .unwrap_or_default()
+}
+
+fn added_label(feed: &Feed, timezone: Tz) -> String {
+    let date = feed
+        .created_at
+        .as_deref()
+        .and_then(|created| chrono::DateTime::parse_from_rfc3339(created).ok())
+        .map(|created| {
+            created
+                .with_timezone(&timezone)
+                .format("%b %d, %Y")
+                .to_string()
+        });
+
+    match (date, feed.added_by) {
+        (Some(date), Some(user)) => format!(" | Added {} by <@{}>", date, user),
+        (Some(date), None) => format!(" | Added {}", date),
+        (None, Some(user)) => format!(" | Added by <@{}>", user),
+        (None, None) => String::new(),
+    }
+}
+
+fn flag_label(feed: &Feed) -> String {
+    let mut label = String::new();
+    if feed.spoiler {
+        label.push_str(" | spoiler");
+    }
+    if feed.nsfw {
+        label.push_str(" | NSFW");
+        if is_nsfw_blocked(feed.id) {
+            label.push_str(" ⚠️ blocked: channel is not age-restricted");
+        }
+    }
+    label
+}
+
+fn sort_feeds(feeds: &mut [Feed], mode: ListMode) {
+    match mode {
+        ListMode::Flat => {}
+        ListMode::Grouped => feeds.sort_by_key(|feed| (feed.channel_id, feed.id)),
+        ListMode::Collapsed => {
+            feeds.sort_by(|a, b| a.url.cmp(&b.url).then(a.channel_id.cmp(&b.channel_id)))
+        }
+    }
+}
+
+fn entry_count(feeds: &[Feed], mode: ListMode) -> usize {
+    if mode == ListMode::Collapsed {
+        feeds.chunk_by(|a, b| a.url == b.url).count()
+    } else {
+        feeds.len()
+    }
+}
+
+fn page_starts(feeds: &[Feed], mode: ListMode, feeds_per_page: usize, timezone: Tz) -> Vec<usize> {
+    if mode != ListMode::Grouped {
+        return (0..entry_count(feeds, mode).max(1))
+            .step_by(feeds_per_page)
+            .collect();
+    }
+
+    // Grouped sections vary in length, so break pages early rather than cut a page off at the
+    // description limit.
+    let mut starts = vec![0];
+    let (mut length, mut count) = (0, 0);
+    for i in 0..feeds.len() {
+        let mut section = grouped_section(feeds, i, count == 0, timezone).len();
+        if count == feeds_per_page || (count > 0 && length + section > DESCRIPTION_LIMIT) {
+            starts.push(i);
+            (length, count) = (0, 0);
+            section = grouped_section(feeds, i, true, timezone).len();
+        }
+        length += section;
+        count += 1;
+    }
+    starts
+}
+
+async fn cached_feeds(
+    database: &Arc<dyn FeedStore>,
+    guild_id: u64,
+    message_id: Option<u64>,
+    mode: ListMode,
+) -> Result<Arc<Vec<Feed>>> {
+    let Some(message_id) = message_id else {
+        return Ok(Arc::new(query_feeds(database, guild_id, mode).await?));
+    };
+
+    {
+        let mut cache = FEED_CACHE.lock().await;
+        cache.retain(|_, entry| entry.created_at.elapsed() < CACHE_TTL);
+        if let Some(entry) = cache.get(&message_id) {
+            return Ok(entry.feeds.clone());
+        }
+    }
+
+    info!(
+        "Feed cache miss for message {}, querying database",
+        message_id
+    );
+    let feeds = Arc::new(query_feeds(database, guild_id, mode).await?);
+    cache_feeds(message_id, feeds.clone()).await;
+    Ok(feeds)
+}
+
+async fn query_feeds(
+    database: &Arc<dyn FeedStore>,
+    guild_id: u64,
+    mode: ListMode,
+) -> Result<Vec<Feed>> {
+    let mut feeds = database.guild(guild_id).await?;
+    sort_feeds(&mut feeds, mode);
+    Ok(feeds)
+}
+
+async fn cache_feeds(message_id: u64, feeds: Arc<Vec<Feed>>) {
+    let mut cache = FEED_CACHE.lock().await;
+    cache.retain(|_, entry| entry.created_at.elapsed() < CACHE_TTL);
+    cache.insert(
+        message_id,
+        CachedFeeds {
+            feeds,
+            created_at: Instant::now(),
+        },
+    );
+}
+
 fn extract_page_from_custom_id(custom_id: &str) -> usize {
     custom_id
         .split('_')
@@ -319,6 +755,10 @@ fn extract_page_from_custom_id(custom_id: &str) -> usize {
         .unwrap_or(0)
 }
 
+fn extract_owner_from_custom_id(custom_id: &str) -> Option<u64> {
+    custom_id.rsplit('_').next().and_then(|s| s.parse().ok())
+}
+
 async fn respond_empty(command: &CommandInteraction, http: &serenity::http::Http) -> Result<()> {
     let response = CreateInteractionResponse::Message(
         CreateInteractionResponseMessage::new()
@@ -336,7 +776,7 @@ async fn defer_response(command: &CommandInteraction, http: &serenity::http::Htt
     Ok(())
 }
 
-fn extract_domain(url: &str) -> String {
+pub fn extract_domain(url: &str) -> String {
     if let Ok(parsed_url) = url::Url::parse(url) {
         parsed_url.host_str().unwrap_or("Unknown").to_string()
     } else {
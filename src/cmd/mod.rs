@@ -1,6 +1,25 @@
 pub mod add;
+pub mod behavior;
+pub mod channel_config;
+pub mod cleanup;
+pub mod color;
+pub mod config;
+pub mod crosspost;
+pub mod diagnostics;
+pub mod edit;
+pub mod feedinfo;
+pub mod flag;
+pub mod help;
+pub mod image;
 pub mod list;
+pub mod r#move;
 pub mod opinionated;
+pub mod pin;
+pub mod reactions;
+pub mod refresh;
+pub mod reload;
 pub mod remove;
+pub mod repost;
 pub mod setup;
 pub mod sync;
+pub mod translate;
@@ -0,0 +1,196 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use serenity::{
+    all::{
+        ChannelType, CommandInteraction, CreateInteractionResponse,
+        CreateInteractionResponseMessage,
+    },
+    model::id::ChannelId,
+    prelude::*,
+};
+use tracing::error;
+
+use crate::data::FeedStore;
+
+pub async fn execute(
+    ctx: &Context,
+    command: &CommandInteraction,
+    database: &Arc<dyn FeedStore>,
+) -> Result<()> {
+    let url = extract_url(command)?;
+    let target = extract_channel(command, "channel")
+        .ok_or_else(|| anyhow::anyhow!("Channel is required"))?;
+    let source = extract_channel(command, "from");
+    let guild_id = command.guild_id.unwrap().get();
+
+    let matches: Vec<u64> = database
+        .guild(guild_id)
+        .await?
+        .into_iter()
+        .filter(|feed| feed.url == url)
+        .map(|feed| feed.channel_id as u64)
+        .collect();
+
+    let source_channel_id = match (source, matches.as_slice()) {
+        (_, []) => return respond(command, &ctx.http, "RSS feed not found.").await,
+        (Some(source), matches) => {
+            if !matches.contains(&source.get()) {
+                return respond(
+                    command,
+                    &ctx.http,
+                    &format!("This feed is not posting to <#{}>.", source.get()),
+                )
+                .await;
+            }
+            source.get()
+        }
+        (None, [channel_id]) => *channel_id,
+        (None, matches) => {
+            let channels = matches
+                .iter()
+                .map(|id| format!("<#{}>", id))
+                .collect::<Vec<_>>()
+                .join(", ");
+            return respond(
+                command,
+                &ctx.http,
+                &format!(
+                    "This feed posts to multiple channels ({}). Use the `from` option to pick \
+                     which one to move.",
+                    channels
+                ),
+            )
+            .await;
+        }
+    };
+
+    if source_channel_id == target.get() {
+        return respond(
+            command,
+            &ctx.http,
+            &format!("This feed already posts to <#{}>.", target.get()),
+        )
+        .await;
+    }
+
+    if database.duplicate(guild_id, target.get(), &url).await? {
+        return respond(
+            command,
+            &ctx.http,
+            &format!("This feed is already added to <#{}>.", target.get()),
+        )
+        .await;
+    }
+
+    if let Err(message) = validate_channel(ctx, command, target).await {
+        return respond(command, &ctx.http, &message).await;
+    }
+
+    if !database
+        .move_feed(guild_id, source_channel_id, &url, target.get())
+        .await?
+    {
+        return respond(command, &ctx.http, "RSS feed not found.").await;
+    }
+
+    respond(
+        command,
+        &ctx.http,
+        &format!(
+            "Moved RSS feed from <#{}> to <#{}>: {}",
+            source_channel_id,
+            target.get(),
+            url
+        ),
+    )
+    .await
+}
+
+async fn validate_channel(
+    ctx: &Context,
+    command: &CommandInteraction,
+    channel_id: ChannelId,
+) -> std::result::Result<(), String> {
+    let guild_id = command.guild_id.unwrap();
+
+    let channel = match channel_id.to_channel(&ctx.http).await {
+        Ok(channel) => match channel.guild() {
+            Some(channel) if channel.guild_id == guild_id => channel,
+            _ => return Err("That channel doesn't belong to this server.".to_string()),
+        },
+        Err(e) => {
+            error!("Failed to fetch channel {}: {}", channel_id, e);
+            return Err("Failed to access that channel.".to_string());
+        }
+    };
+
+    if !matches!(
+        channel.kind,
+        ChannelType::Text | ChannelType::News | ChannelType::Forum
+    ) {
+        return Err(format!("<#{}> is not a text or forum channel.", channel_id));
+    }
+
+    let bot_user_id = ctx.cache.current_user().id;
+    let permissions = match guild_id.to_partial_guild(&ctx.http).await {
+        Ok(guild) => guild
+            .member(&ctx.http, bot_user_id)
+            .await
+            .map(|bot_member| guild.user_permissions_in(&channel, &bot_member)),
+        Err(e) => Err(e),
+    };
+
+    match permissions {
+        Ok(permissions)
+            if permissions.view_channel()
+                && permissions.send_messages()
+                && permissions.embed_links() =>
+        {
+            Ok(())
+        }
+        Ok(_) => Err(format!(
+            "Missing permissions in <#{}>: View Channel, Send Messages and Embed Links are \
+             required.",
+            channel_id
+        )),
+        Err(e) => {
+            error!("Failed to check permissions for {}: {}", channel_id, e);
+            Err("Failed to check permissions for that channel.".to_string())
+        }
+    }
+}
+
+fn extract_url(command: &CommandInteraction) -> Result<String> {
+    command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "url")
+        .and_then(|opt| opt.value.as_str())
+        .map(|s| s.to_string())
+        .ok_or_else(|| anyhow::anyhow!("URL is required"))
+}
+
+fn extract_channel(command: &CommandInteraction, name: &str) -> Option<ChannelId> {
+    command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == name)
+        .and_then(|opt| opt.value.as_channel_id())
+}
+
+async fn respond(
+    command: &CommandInteraction,
+    http: &serenity::http::Http,
+    content: &str,
+) -> Result<()> {
+    let response = CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true),
+    );
+    command.create_response(http, response).await?;
+    Ok(())
+}
@@ -1,6 +1,11 @@
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    sync::{Arc, LazyLock},
+};
 
 use anyhow::Result;
+use futures::StreamExt;
+use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serenity::{
     all::{
@@ -9,9 +14,21 @@ use serenity::{
     },
     prelude::*,
 };
-use tracing::{error, info};
+use tokio::{
+    sync::RwLock,
+    time::{Duration, timeout},
+};
+use tracing::{error, info, warn};
+
+use crate::{
+    cmd::{add::validate_feed, remove::cleanup_webhooks},
+    data::{FeedStore, models::NewFeed, url_variants},
+    util::{blocklist, parser::normalize_feed_url},
+};
 
-use crate::data::Database;
+static COLLECTIONS: LazyLock<RwLock<HashMap<String, OpinionatedCollection>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+static SKIPPED: LazyLock<RwLock<Vec<String>>> = LazyLock::new(|| RwLock::new(Vec::new()));
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OpinionatedFeed {
@@ -30,12 +47,18 @@ pub struct OpinionatedCollection {
 pub async fn execute(
     ctx: &Context,
     command: &CommandInteraction,
-    database: &Arc<Database>,
+    database: &Arc<dyn FeedStore>,
+    client: &Client,
 ) -> Result<()> {
+    if !installed().await {
+        return respond_error(command, &ctx.http, NO_COLLECTIONS_MESSAGE).await;
+    }
+
     let topic = extract_topic(command)?;
-    let channel = extract_channel(command);
+    let validate = extract_validate(command);
+    let dry_run = extract_dry_run(command);
     let guild_id = command.guild_id.unwrap().get();
-    let channel_id = channel.get();
+    let channel_id = extract_channel(command, database, guild_id).await?.get();
 
     info!(
         "Processing opinionated command: topic={}, channel={}",
@@ -57,9 +80,9 @@ pub async fn execute(
 
     defer_response(command, &ctx.http).await?;
 
-    let mut added_count = 0;
-    let mut skipped_count = 0;
-    let mut failed_feeds = Vec::new();
+    if extract_action(command) == "remove" {
+        return remove_collection(ctx, command, database, &collection, guild_id, dry_run).await;
+    }
 
     info!(
         "Processing {} feeds from '{}' collection",
@@ -67,40 +90,73 @@ pub async fn execute(
         collection.topic
     );
 
-    for feed in &collection.feeds {
-        info!("Processing feed: {}", feed.name);
+    let client = validate.then_some(client);
+    let result = subscribe(
+        database.as_ref(),
+        client,
+        &collection.feeds,
+        guild_id,
+        channel_id,
+        command.user.id.get(),
+        dry_run,
+    )
+    .await?;
+    let added_count = result.added;
+    let skipped_count = result.skipped;
+    let planned_feeds: Vec<String> = result
+        .planned
+        .iter()
+        .map(|name| format!("• {}", name))
+        .collect();
+    let blocked_feeds: Vec<String> = result
+        .blocked
+        .iter()
+        .map(|(name, pattern)| format!("• {} (blocked by `{}`)", name, pattern))
+        .collect();
+    let unreachable_feeds: Vec<String> = result
+        .unreachable
+        .iter()
+        .map(|(name, reason)| format!("• {} ({})", name, reason))
+        .collect();
+    let failed_feeds: Vec<String> = result
+        .failed
+        .iter()
+        .map(|(name, error)| format!("• {} ({})", name, error))
+        .collect();
+
+    let mut summary = if dry_run {
+        format!(
+            "Dry run: would add {} feeds from '{}' collection to <#{}>\n• {} would be added\n• {} \
+             skipped (already in server)",
+            added_count, collection.topic, channel_id, added_count, skipped_count
+        )
+    } else {
+        format!(
+            "Added {} feeds from '{}' collection to <#{}>\n• {} added\n• {} skipped (already in \
+             server)",
+            added_count, collection.topic, channel_id, added_count, skipped_count
+        )
+    };
 
-        if database.exists(guild_id, &feed.url).await? {
-            info!(
-                "Skipping feed '{}' - already exists in this server",
-                feed.name
-            );
-            skipped_count += 1;
-            continue;
+    if !unreachable_feeds.is_empty() {
+        summary.push_str(&format!("\n• {} unreachable:", unreachable_feeds.len()));
+        for unreachable in unreachable_feeds.iter().take(5) {
+            summary.push_str(&format!("\n  {}", unreachable));
         }
-
-        match add_feed(database, feed, guild_id, channel_id).await {
-            Ok(()) => {
-                info!("Successfully added feed: {}", feed.name);
-                added_count += 1;
-            }
-            Err(e) => {
-                error!("Failed to add feed '{}': {}", feed.name, e);
-                let error_msg = if e.to_string().contains("UNIQUE constraint") {
-                    "already exists".to_string()
-                } else {
-                    e.to_string()
-                };
-                failed_feeds.push(format!("• {} ({})", feed.name, error_msg));
-            }
+        if unreachable_feeds.len() > 5 {
+            summary.push_str(&format!("\n  ... and {} more", unreachable_feeds.len() - 5));
         }
     }
 
-    let mut summary = format!(
-        "Added {} feeds from '{}' collection to <#{}>\n• {} added\n• {} skipped (already in \
-         server)",
-        added_count, collection.topic, channel_id, added_count, skipped_count
-    );
+    if !blocked_feeds.is_empty() {
+        summary.push_str(&format!("\n• {} blocked:", blocked_feeds.len()));
+        for blocked in blocked_feeds.iter().take(5) {
+            summary.push_str(&format!("\n  {}", blocked));
+        }
+        if blocked_feeds.len() > 5 {
+            summary.push_str(&format!("\n  ... and {} more", blocked_feeds.len() - 5));
+        }
+    }
 
     if !failed_feeds.is_empty() {
         summary.push_str(&format!("\n• {} failed:", failed_feeds.len()));
@@ -112,10 +168,25 @@ pub async fn execute(
         }
     }
 
+    if !planned_feeds.is_empty() {
+        summary.push_str("\n\nWould add:");
+        for planned in planned_feeds.iter().take(20) {
+            summary.push_str(&format!("\n  {}", planned));
+        }
+        if planned_feeds.len() > 20 {
+            summary.push_str(&format!("\n  ... and {} more", planned_feeds.len() - 20));
+        }
+    }
+
+    if dry_run {
+        summary.push_str("\n\nNothing was changed.");
+    }
+
     info!(
-        "Opinionated command completed: {} added, {} skipped, {} failed",
+        "Opinionated command completed: {} added, {} skipped, {} unreachable, {} failed",
         added_count,
         skipped_count,
+        unreachable_feeds.len(),
         failed_feeds.len()
     );
 
@@ -124,34 +195,177 @@ pub async fn execute(
     Ok(())
 }
 
+async fn remove_collection(
+    ctx: &Context,
+    command: &CommandInteraction,
+    database: &Arc<dyn FeedStore>,
+    collection: &OpinionatedCollection,
+    guild_id: u64,
+    dry_run: bool,
+) -> Result<()> {
+    let existing = database.guild(guild_id).await?;
+    let collection_urls: Vec<String> = collection
+        .feeds
+        .iter()
+        .flat_map(|feed| url_variants(&feed.url))
+        .collect();
+    let webhook_urls: Vec<String> = existing
+        .iter()
+        .filter(|feed| collection_urls.contains(&feed.url))
+        .filter_map(|feed| feed.webhook_url.clone())
+        .collect();
+
+    let mut removed_count = 0;
+    let mut missing_count = 0;
+
+    if dry_run {
+        let present = collection
+            .feeds
+            .iter()
+            .filter(|feed| {
+                let variants = url_variants(&feed.url);
+                existing.iter().any(|f| variants.contains(&f.url))
+            })
+            .count();
+
+        let edit_response = EditInteractionResponse::new().content(format!(
+            "Dry run: would remove {} feeds from '{}' collection\n• {} would be removed\n• {} not \
+             present in server\n\nNothing was changed.",
+            present,
+            collection.topic,
+            present,
+            collection.feeds.len() - present
+        ));
+        command.edit_response(&ctx.http, edit_response).await?;
+        return Ok(());
+    }
+
+    for feed in &collection.feeds {
+        match database.remove(guild_id, &feed.url).await {
+            Ok(true) => removed_count += 1,
+            Ok(false) => missing_count += 1,
+            Err(e) => error!("Failed to remove feed '{}': {}", feed.name, e),
+        }
+    }
+
+    if removed_count > 0 && !webhook_urls.is_empty() {
+        cleanup_webhooks(ctx, database, guild_id, webhook_urls).await?;
+    }
+
+    info!(
+        "Removed {} feeds from '{}' collection ({} not present)",
+        removed_count, collection.topic, missing_count
+    );
+
+    let edit_response = EditInteractionResponse::new().content(format!(
+        "Removed {} feeds from '{}' collection\n• {} removed\n• {} not present in server",
+        removed_count, collection.topic, removed_count, missing_count
+    ));
+    command.edit_response(&ctx.http, edit_response).await?;
+    Ok(())
+}
+
+pub const NO_COLLECTIONS_MESSAGE: &str = "No curated collections are installed on this bot.";
+
+pub async fn installed() -> bool {
+    !COLLECTIONS.read().await.is_empty()
+}
+
 pub async fn topics() -> Result<Vec<String>> {
-    let mut topics = Vec::new();
+    let collections = COLLECTIONS.read().await;
+    let mut topics: Vec<String> = collections.values().map(|c| c.topic.clone()).collect();
+
+    topics.sort();
+    Ok(topics)
+}
+
+pub async fn skipped() -> Vec<String> {
+    SKIPPED.read().await.clone()
+}
+
+pub async fn reload() -> Result<usize> {
+    let mut loaded = HashMap::new();
+    let mut skipped = Vec::new();
     let opinionated_dir = std::path::Path::new("opinionated");
 
-    if !opinionated_dir.exists() {
-        return Ok(topics);
-    }
+    if opinionated_dir.exists() {
+        let mut entries = tokio::fs::read_dir(opinionated_dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().and_then(|s| s.to_str()) != Some("json") {
+                continue;
+            }
 
-    let mut entries = tokio::fs::read_dir(opinionated_dir).await?;
-    while let Some(entry) = entries.next_entry().await? {
-        let path = entry.path();
-        if path.extension().and_then(|s| s.to_str()) == Some("json") {
+            let file = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
             match load_collection_from_path(&path).await {
                 Ok(collection) => {
-                    topics.push(collection.topic);
+                    let key = collection.topic.to_lowercase();
+                    if loaded.contains_key(&key) {
+                        warn!(
+                            "Skipping collection {}: topic \"{}\" is already defined by another \
+                             file",
+                            path.display(),
+                            collection.topic
+                        );
+                        skipped.push(file);
+                        continue;
+                    }
+                    loaded.insert(key, collection);
                 }
                 Err(e) => {
-                    error!("Failed to parse {}: {}, using filename", path.display(), e);
-                    if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
-                        topics.push(stem.to_string());
-                    }
+                    error!("Skipping invalid collection {}: {}", path.display(), e);
+                    skipped.push(file);
                 }
             }
         }
     }
 
-    topics.sort();
-    Ok(topics)
+    let count = loaded.len();
+    skipped.sort();
+    if !skipped.is_empty() {
+        warn!(
+            "Skipped {} invalid collection files: {}",
+            skipped.len(),
+            skipped.join(", ")
+        );
+    }
+    *COLLECTIONS.write().await = loaded;
+    *SKIPPED.write().await = skipped;
+    info!("Loaded {} opinionated collections", count);
+    Ok(count)
+}
+
+fn extract_action(command: &CommandInteraction) -> &str {
+    command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "action")
+        .and_then(|opt| opt.value.as_str())
+        .unwrap_or("add")
+}
+
+fn extract_dry_run(command: &CommandInteraction) -> bool {
+    command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "dry_run")
+        .and_then(|opt| opt.value.as_bool())
+        .unwrap_or(false)
+}
+
+fn extract_validate(command: &CommandInteraction) -> bool {
+    command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "validate")
+        .and_then(|opt| opt.value.as_bool())
+        .unwrap_or(true)
 }
 
 fn extract_topic(command: &CommandInteraction) -> Result<String> {
@@ -165,56 +379,185 @@ fn extract_topic(command: &CommandInteraction) -> Result<String> {
         .ok_or_else(|| anyhow::anyhow!("Topic is required"))
 }
 
-fn extract_channel(command: &CommandInteraction) -> serenity::model::id::ChannelId {
-    command
+async fn extract_channel(
+    command: &CommandInteraction,
+    database: &Arc<dyn FeedStore>,
+    guild_id: u64,
+) -> Result<serenity::model::id::ChannelId> {
+    let channel = command
         .data
         .options
         .iter()
         .find(|opt| opt.name == "channel")
-        .and_then(|opt| opt.value.as_channel_id())
-        .unwrap_or(command.channel_id)
+        .and_then(|opt| opt.value.as_channel_id());
+
+    if let Some(channel) = channel {
+        return Ok(channel);
+    }
+
+    let default = database
+        .get_settings(guild_id)
+        .await?
+        .and_then(|settings| settings.rss_channel_id)
+        .map(|channel_id| serenity::model::id::ChannelId::new(channel_id as u64));
+
+    Ok(default.unwrap_or(command.channel_id))
 }
 
 pub async fn load_collection(topic: &str) -> Result<OpinionatedCollection> {
-    let opinionated_dir = std::path::Path::new("opinionated");
+    COLLECTIONS
+        .read()
+        .await
+        .get(&topic.to_lowercase())
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("Collection '{}' not found", topic))
+}
 
-    if !opinionated_dir.exists() {
-        return Err(anyhow::anyhow!("Opinionated directory not found"));
+async fn load_collection_from_path(path: &std::path::Path) -> Result<OpinionatedCollection> {
+    let content = tokio::fs::read_to_string(path).await?;
+    let mut collection: OpinionatedCollection = serde_json::from_str(&content)?;
+    if collection.topic.trim().is_empty() {
+        return Err(anyhow::anyhow!("topic is empty"));
     }
 
-    let mut entries = tokio::fs::read_dir(opinionated_dir).await?;
-    while let Some(entry) = entries.next_entry().await? {
-        let path = entry.path();
-        if path.extension().and_then(|s| s.to_str()) == Some("json") {
-            if let Ok(collection) = load_collection_from_path(&path).await {
-                if collection.topic.to_lowercase() == topic.to_lowercase() {
-                    return Ok(collection);
-                }
-            }
+    let total = collection.feeds.len();
+    collection.feeds.retain(|feed| {
+        let valid = url::Url::parse(&feed.url).is_ok();
+        if !valid {
+            warn!(
+                "Ignoring feed \"{}\" in {}: invalid URL {}",
+                feed.name,
+                path.display(),
+                feed.url
+            );
         }
+        valid
+    });
+    if collection.feeds.is_empty() {
+        return Err(anyhow::anyhow!("no valid feeds (found {} entries)", total));
     }
 
-    let path = format!("opinionated/{}.json", topic.to_lowercase());
-    load_collection_from_path(std::path::Path::new(&path)).await
+    for feed in &mut collection.feeds {
+        feed.url = normalize_feed_url(&feed.url);
+    }
+    Ok(collection)
 }
 
-async fn load_collection_from_path(path: &std::path::Path) -> Result<OpinionatedCollection> {
-    let content = tokio::fs::read_to_string(path).await?;
-    let collection: OpinionatedCollection = serde_json::from_str(&content)?;
-    Ok(collection)
+#[derive(Debug, Default)]
+pub struct Subscription {
+    pub added: usize,
+    pub skipped: usize,
+    pub planned: Vec<String>,
+    pub unreachable: Vec<(String, String)>,
+    pub blocked: Vec<(String, String)>,
+    pub failed: Vec<(String, String)>,
 }
 
-async fn add_feed(
-    database: &Arc<Database>,
-    feed: &OpinionatedFeed,
+pub async fn subscribe(
+    database: &dyn FeedStore,
+    client: Option<&Client>,
+    feeds: &[OpinionatedFeed],
     guild_id: u64,
     channel_id: u64,
-) -> Result<()> {
-    database
-        .add(guild_id, channel_id, &feed.url, Some(&feed.name), None)
-        .await?;
+    added_by: u64,
+    dry_run: bool,
+) -> Result<Subscription> {
+    let mut result = Subscription::default();
+    let defaults = database.channel_defaults(channel_id).await?;
+
+    let mut candidates = Vec::new();
+    for feed in feeds {
+        if database.exists(guild_id, &feed.url).await? {
+            info!(
+                "Skipping feed '{}' - already exists in this server",
+                feed.name
+            );
+            result.skipped += 1;
+            continue;
+        }
 
-    Ok(())
+        if let Some(pattern) = blocklist::blocked(&feed.url) {
+            info!("Skipping blocked feed '{}' ({})", feed.name, pattern);
+            result
+                .blocked
+                .push((feed.name.clone(), pattern.to_string()));
+            continue;
+        }
+
+        candidates.push(feed);
+    }
+
+    let unreachable = match client {
+        Some(client) => {
+            let urls = candidates.iter().map(|feed| feed.url.clone()).collect();
+            find_unreachable(client, urls).await
+        }
+        None => HashMap::new(),
+    };
+
+    let mut pending = Vec::new();
+    for feed in candidates {
+        if let Some(reason) = unreachable.get(&feed.url) {
+            info!("Skipping unreachable feed '{}': {}", feed.name, reason);
+            result.unreachable.push((feed.name.clone(), reason.clone()));
+            continue;
+        }
+
+        if dry_run {
+            result.planned.push(feed.name.clone());
+            result.added += 1;
+            continue;
+        }
+
+        pending.push(
+            NewFeed {
+                guild_id,
+                channel_id,
+                url: &feed.url,
+                title: Some(&feed.name),
+                custom_title: true,
+                added_by: Some(added_by),
+                ..Default::default()
+            }
+            .inherit(defaults.as_ref()),
+        );
+    }
+
+    if !pending.is_empty() {
+        match database.add_many(&pending).await {
+            Ok(inserted) => {
+                info!("Added {} of {} feeds", inserted, pending.len());
+                result.added += inserted as usize;
+                result.skipped += pending.len() - inserted as usize;
+            }
+            Err(e) => {
+                error!("Failed to add feeds to <#{}>: {}", channel_id, e);
+                result.failed.extend(
+                    pending
+                        .iter()
+                        .map(|feed| (feed.title.unwrap_or(feed.url).to_string(), e.to_string())),
+                );
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+pub async fn find_unreachable(client: &Client, urls: Vec<String>) -> HashMap<String, String> {
+    futures::stream::iter(urls)
+        .map(|url| async move {
+            let result = timeout(Duration::from_secs(10), validate_feed(client, &url, None)).await;
+            match result {
+                Ok(Ok(_)) => None,
+                Ok(Err(e)) => Some((url, e.to_string())),
+                Err(_) => Some((url, "timed out".to_string())),
+            }
+        })
+        .buffer_unordered(8)
+        .filter_map(|result| async move { result })
+        .collect()
+        .await
 }
 
 async fn respond_error(
@@ -237,3 +580,144 @@ async fn defer_response(command: &CommandInteraction, http: &serenity::http::Htt
     command.create_response(http, response).await?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{mock::MockStore, models::ChannelDefaults};
+
+    const GUILD: u64 = 1;
+    const CHANNEL: u64 = 10;
+    const USER: u64 = 100;
+
+    fn feeds(urls: &[&str]) -> Vec<OpinionatedFeed> {
+        urls.iter()
+            .enumerate()
+            .map(|(i, url)| OpinionatedFeed {
+                name: format!("Feed {}", i),
+                url: url.to_string(),
+                description: None,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn subscribe_adds_new_feeds_and_skips_existing_ones() {
+        let store = MockStore::new();
+        store
+            .add(NewFeed {
+                guild_id: GUILD,
+                channel_id: 99,
+                url: "https://example.com/feed.xml",
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let collection = feeds(&[
+            "http://EXAMPLE.com/feed.xml/",
+            "https://one.example/rss",
+            "https://two.example/atom",
+        ]);
+        let result = subscribe(&store, None, &collection, GUILD, CHANNEL, USER, false)
+            .await
+            .unwrap();
+
+        assert_eq!(result.added, 2);
+        assert_eq!(result.skipped, 1);
+        assert!(result.failed.is_empty());
+
+        let added: Vec<_> = store
+            .guild(GUILD)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|feed| feed.channel_id == CHANNEL as i64)
+            .collect();
+        assert_eq!(added.len(), 2);
+        assert!(added.iter().all(|feed| feed.added_by == Some(USER as i64)));
+        assert_eq!(added[0].title.as_deref(), Some("Feed 1"));
+    }
+
+    #[tokio::test]
+    async fn subscribe_counts_duplicates_within_a_collection_as_skipped() {
+        let store = MockStore::new();
+        let collection = feeds(&["https://one.example/rss", "https://one.example/rss/"]);
+
+        let result = subscribe(&store, None, &collection, GUILD, CHANNEL, USER, false)
+            .await
+            .unwrap();
+
+        assert_eq!(result.added, 1);
+        assert_eq!(result.skipped, 1);
+        assert_eq!(store.guild(GUILD).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn subscribe_dry_run_changes_nothing() {
+        let store = MockStore::new();
+        let collection = feeds(&["https://one.example/rss", "https://two.example/atom"]);
+
+        let result = subscribe(&store, None, &collection, GUILD, CHANNEL, USER, true)
+            .await
+            .unwrap();
+
+        assert_eq!(result.added, 2);
+        assert_eq!(result.planned, vec!["Feed 0", "Feed 1"]);
+        assert!(store.guild(GUILD).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn subscribe_inherits_channel_defaults() {
+        let store = MockStore::new();
+        let defaults = ChannelDefaults {
+            color: Some(0xff0000),
+            mention_role_id: Some(42),
+            image_mode: Some(crate::data::models::ImageMode::Thumbnail),
+        };
+        store
+            .set_channel_defaults(GUILD, CHANNEL, Some(&defaults))
+            .await
+            .unwrap();
+
+        subscribe(
+            &store,
+            None,
+            &feeds(&["https://one.example/rss"]),
+            GUILD,
+            CHANNEL,
+            USER,
+            false,
+        )
+        .await
+        .unwrap();
+
+        let feed = &store.guild(GUILD).await.unwrap()[0];
+        assert_eq!(feed.color, Some(0xff0000));
+        assert_eq!(feed.mention_role_id, Some(42));
+        assert_eq!(feed.image_mode, crate::data::models::ImageMode::Thumbnail);
+    }
+
+    #[tokio::test]
+    async fn subscribe_skips_blocklisted_feeds() {
+        blocklist::init(vec!["*.blocked.test".to_string()]);
+        let store = MockStore::new();
+        let collection = feeds(&["https://news.blocked.test/rss", "https://one.example/rss"]);
+
+        let result = subscribe(&store, None, &collection, GUILD, CHANNEL, USER, false)
+            .await
+            .unwrap();
+
+        assert_eq!(result.added, 1);
+        assert_eq!(
+            result.blocked,
+            vec![("Feed 0".to_string(), "*.blocked.test".to_string())]
+        );
+        assert!(
+            !store
+                .exists(GUILD, "https://news.blocked.test/rss")
+                .await
+                .unwrap()
+        );
+    }
+}
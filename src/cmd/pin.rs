@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use serenity::{
+    all::{CommandInteraction, CreateInteractionResponse, CreateInteractionResponseMessage},
+    prelude::*,
+};
+
+use crate::data::FeedStore;
+
+pub async fn execute(
+    ctx: &Context,
+    command: &CommandInteraction,
+    database: &Arc<dyn FeedStore>,
+) -> Result<()> {
+    let url = command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "url")
+        .and_then(|opt| opt.value.as_str())
+        .map(|s| s.to_string())
+        .ok_or_else(|| anyhow::anyhow!("URL is required"))?;
+    let enabled = command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "enabled")
+        .and_then(|opt| opt.value.as_bool())
+        .ok_or_else(|| anyhow::anyhow!("Enabled is required"))?;
+    let guild_id = command.guild_id.unwrap().get();
+
+    let content = if !database.set_pin_latest(guild_id, &url, enabled).await? {
+        "RSS feed not found.".to_string()
+    } else if enabled {
+        format!(
+            "The newest post from {} will stay pinned, replacing the previous pin.",
+            url
+        )
+    } else {
+        format!("Stopped pinning posts from {}", url)
+    };
+
+    let response = CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true),
+    );
+    command.create_response(&ctx.http, response).await?;
+    Ok(())
+}
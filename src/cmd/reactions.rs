@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use serenity::{
+    all::{
+        CommandInteraction, CreateInteractionResponse, CreateInteractionResponseMessage,
+        ReactionType,
+    },
+    prelude::*,
+};
+
+use crate::data::FeedStore;
+
+const MAX_REACTIONS: usize = 20;
+
+pub async fn execute(
+    ctx: &Context,
+    command: &CommandInteraction,
+    database: &Arc<dyn FeedStore>,
+) -> Result<()> {
+    let url = extract_option(command, "url").ok_or_else(|| anyhow::anyhow!("URL is required"))?;
+    let emojis =
+        extract_option(command, "emojis").ok_or_else(|| anyhow::anyhow!("Emojis are required"))?;
+    let guild_id = command.guild_id.unwrap().get();
+
+    let reactions = if emojis.eq_ignore_ascii_case("none") || emojis.eq_ignore_ascii_case("off") {
+        Vec::new()
+    } else {
+        match parse_emojis(&emojis) {
+            Some(reactions) => reactions,
+            None => {
+                return respond(
+                    command,
+                    &ctx.http,
+                    &format!(
+                        "Invalid emoji list. Use up to {} comma-separated emojis like `👀,✅,❌`, \
+                         or `none` to clear.",
+                        MAX_REACTIONS
+                    ),
+                )
+                .await;
+            }
+        }
+    };
+
+    let content = if !database.set_reactions(guild_id, &url, &reactions).await? {
+        "RSS feed not found.".to_string()
+    } else if reactions.is_empty() {
+        format!("Cleared reactions for {}", url)
+    } else {
+        format!(
+            "New posts from {} will get these reactions: {}",
+            url,
+            reactions.join(" ")
+        )
+    };
+
+    respond(command, &ctx.http, &content).await
+}
+
+fn parse_emojis(input: &str) -> Option<Vec<String>> {
+    let mut reactions: Vec<String> = Vec::new();
+    for emoji in input.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        ReactionType::try_from(emoji).ok()?;
+        if !reactions.iter().any(|existing| existing == emoji) {
+            reactions.push(emoji.to_string());
+        }
+    }
+
+    if reactions.is_empty() || reactions.len() > MAX_REACTIONS {
+        return None;
+    }
+    Some(reactions)
+}
+
+fn extract_option(command: &CommandInteraction, name: &str) -> Option<String> {
+    command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == name)
+        .and_then(|opt| opt.value.as_str())
+        .map(|s| s.to_string())
+}
+
+async fn respond(
+    command: &CommandInteraction,
+    http: &serenity::http::Http,
+    content: &str,
+) -> Result<()> {
+    let response = CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true),
+    );
+    command.create_response(http, response).await?;
+    Ok(())
+}
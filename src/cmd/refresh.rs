@@ -0,0 +1,130 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use anyhow::Result;
+use futures::StreamExt;
+use reqwest::Client;
+use serenity::{
+    all::{
+        CommandInteraction, CreateInteractionResponse, CreateInteractionResponseMessage,
+        EditInteractionResponse,
+    },
+    prelude::*,
+};
+use tokio::time::timeout;
+use tracing::{info, warn};
+
+use crate::{
+    data::{FeedStore, models::RequestSpec},
+    util::{fetcher, parser},
+};
+
+pub async fn execute(
+    ctx: &Context,
+    command: &CommandInteraction,
+    database: &Arc<dyn FeedStore>,
+    client: &Client,
+) -> Result<()> {
+    let guild_id = command.guild_id.unwrap().get();
+
+    let response =
+        CreateInteractionResponse::Defer(CreateInteractionResponseMessage::new().ephemeral(true));
+    command.create_response(&ctx.http, response).await?;
+
+    let mut stored: HashMap<String, Option<String>> = HashMap::new();
+    let mut requests: HashMap<String, RequestSpec> = HashMap::new();
+    let mut custom = 0;
+    for feed in database.guild(guild_id).await? {
+        if feed.custom_title {
+            custom += 1;
+            continue;
+        }
+        if let Some(request) = feed.request {
+            requests.entry(feed.url.clone()).or_insert(request);
+        }
+        stored.entry(feed.url).or_insert(feed.title);
+    }
+
+    if stored.is_empty() {
+        let content = if custom > 0 {
+            "Every feed in this server has a title set with /add or /edit, so there is nothing \
+             to refresh."
+        } else {
+            "This server has no feeds to refresh."
+        };
+        let edit_response = EditInteractionResponse::new().content(content);
+        command.edit_response(&ctx.http, edit_response).await?;
+        return Ok(());
+    }
+
+    info!(
+        "Refreshing titles for {} feeds in guild {}",
+        stored.len(),
+        guild_id
+    );
+
+    let targets = stored
+        .keys()
+        .map(|url| (url.clone(), requests.get(url).cloned()))
+        .collect();
+    let titles = fetch_titles(client, targets).await;
+    let failed = stored.len() - titles.len();
+
+    let mut updated = 0;
+    for (url, title) in titles {
+        if stored.get(&url).cloned().flatten().as_deref() == Some(title.as_str()) {
+            continue;
+        }
+
+        match database.refresh_title(guild_id, &url, &title).await {
+            Ok(0) => {}
+            Ok(_) => updated += 1,
+            Err(e) => warn!("Failed to update title for {}: {}", url, e),
+        }
+    }
+
+    let mut content = format!(
+        "Checked {} feeds, updated {} titles.",
+        stored.len(),
+        updated
+    );
+    if failed > 0 {
+        content.push_str(&format!(
+            "\n{} feeds couldn't be fetched or had no title and were left unchanged.",
+            failed
+        ));
+    }
+    if custom > 0 {
+        content.push_str(&format!(
+            "\n{} feeds have a title set with /add or /edit and were skipped.",
+            custom
+        ));
+    }
+
+    command
+        .edit_response(&ctx.http, EditInteractionResponse::new().content(content))
+        .await?;
+    Ok(())
+}
+
+async fn fetch_titles(
+    client: &Client,
+    targets: Vec<(String, Option<RequestSpec>)>,
+) -> Vec<(String, String)> {
+    futures::stream::iter(targets)
+        .map(|(url, request)| async move {
+            let fetched = timeout(
+                Duration::from_secs(10),
+                fetcher::with_request(client, &url, request.as_ref(), false),
+            )
+            .await
+            .ok()?
+            .ok()?;
+            let feed = parser::parse(&fetched.content).ok()?;
+            let title = feed.title?.content.trim().to_string();
+            (!title.is_empty()).then_some((url, title))
+        })
+        .buffer_unordered(8)
+        .filter_map(|result| async move { result })
+        .collect()
+        .await
+}
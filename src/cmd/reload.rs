@@ -0,0 +1,49 @@
+use anyhow::Result;
+use serenity::{
+    all::{
+        Command, CommandInteraction, CreateInteractionResponse, CreateInteractionResponseMessage,
+    },
+    prelude::*,
+};
+use tracing::error;
+
+use crate::cmd::opinionated;
+
+pub async fn execute(ctx: &Context, command: &CommandInteraction) -> Result<()> {
+    let content = match opinionated::reload().await {
+        Ok(0) => opinionated::NO_COLLECTIONS_MESSAGE.to_string(),
+        Ok(count) => {
+            if let Err(e) =
+                Command::create_global_command(&ctx.http, crate::opinionated_command()).await
+            {
+                error!("Failed to register /opinionated: {}", e);
+            }
+            format!("Reloaded {} curated collections", count)
+        }
+        Err(e) => {
+            error!("Failed to reload collections: {}", e);
+            format!("Failed to reload collections: {}", e)
+        }
+    };
+
+    let skipped = opinionated::skipped().await;
+    let content = if skipped.is_empty() {
+        content
+    } else {
+        format!(
+            "{}\nSkipped {} invalid file{}: {}. Check the logs for details.",
+            content,
+            skipped.len(),
+            if skipped.len() == 1 { "" } else { "s" },
+            skipped.join(", ")
+        )
+    };
+
+    let response = CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true),
+    );
+    command.create_response(&ctx.http, response).await?;
+    Ok(())
+}
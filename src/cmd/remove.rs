@@ -1,32 +1,594 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use anyhow::Result;
+use chrono_tz::Tz;
 use serenity::{
-    all::{CommandInteraction, CreateInteractionResponse, CreateInteractionResponseMessage},
+    all::{
+        ButtonStyle, ChannelId, CommandInteraction, ComponentInteraction,
+        ComponentInteractionDataKind, CreateActionRow, CreateButton, CreateEmbed,
+        CreateEmbedFooter, CreateInteractionResponse, CreateInteractionResponseMessage,
+        CreateSelectMenu, CreateSelectMenuKind, CreateSelectMenuOption, GuildChannel, GuildId,
+        UserId,
+    },
     prelude::*,
 };
+use tracing::warn;
 
-use crate::data::Database;
+use crate::{
+    cmd::list::{
+        FIRST_EMOJI, LAST_EMOJI, NEXT_EMOJI, PREV_EMOJI, SELECT_OPTION_LIMIT, button_emoji,
+        extract_domain, guild_timezone, last_item, page_window,
+    },
+    data::{FeedStore, models::Feed, url_variants},
+    util::webhook,
+};
 
 pub async fn execute(
     ctx: &Context,
     command: &CommandInteraction,
-    database: &Arc<Database>,
+    database: &Arc<dyn FeedStore>,
 ) -> Result<()> {
-    let url = extract_url(command)?;
     let guild_id = command.guild_id.unwrap().get();
-    let removed = database.remove(guild_id, &url).await?;
+    let channel = extract_channel(command);
+
+    let Some(url) = extract_url(command) else {
+        if let Some(scope) = extract_scope(command, channel) {
+            return confirm(ctx, command, database, guild_id, scope).await;
+        }
+        return picker(ctx, command, database, guild_id).await;
+    };
+
+    let variants = url_variants(&url);
+    let matches: Vec<Feed> = database
+        .guild(guild_id)
+        .await?
+        .into_iter()
+        .filter(|feed| variants.contains(&feed.url))
+        .filter(|feed| channel.is_none_or(|channel| feed.channel_id as u64 == channel))
+        .collect();
+
+    let user_id = command.user.id;
+    let timezone = guild_timezone(database, guild_id).await;
+    let message = match matches.as_slice() {
+        [] => {
+            let content = match channel {
+                Some(channel) => format!("RSS feed not found in <#{}>.", channel),
+                None => "RSS feed not found.".to_string(),
+            };
+            return respond(command, &ctx.http, &content).await;
+        }
+        [feed] => confirm_feed(feed, timezone, user_id),
+        feeds => {
+            let channel_names = command
+                .guild_id
+                .unwrap()
+                .channels(&ctx.http)
+                .await
+                .unwrap_or_default();
+
+            let options = feeds
+                .iter()
+                .take(25)
+                .map(|feed| {
+                    CreateSelectMenuOption::new(
+                        channel_label(&channel_names, feed.channel_id),
+                        feed.id.to_string(),
+                    )
+                    .description(format!("Last item: {}", last_item(feed, timezone)))
+                })
+                .collect::<Vec<_>>();
+            let option_count = options.len() as u8;
+
+            let embed = CreateEmbed::new()
+                .title("Remove this feed from which channels?")
+                .description(format!(
+                    "`{}` is subscribed in {} channels:\n{}",
+                    url,
+                    feeds.len(),
+                    feeds
+                        .iter()
+                        .map(|feed| format!("• <#{}>", feed.channel_id))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                ))
+                .color(0xf38ba8);
+
+            let select_menu = CreateSelectMenu::new(
+                format!("remove_select_{}", user_id),
+                CreateSelectMenuKind::String { options },
+            )
+            .placeholder("Choose channels to remove it from...")
+            .min_values(1)
+            .max_values(option_count);
+
+            CreateInteractionResponseMessage::new()
+                .embed(embed)
+                .components(vec![
+                    CreateActionRow::SelectMenu(select_menu),
+                    CreateActionRow::Buttons(vec![cancel_button(user_id)]),
+                ])
+        }
+    };
+
+    command
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::Message(message.ephemeral(true)),
+        )
+        .await?;
+    Ok(())
+}
+
+fn confirm_feed(feed: &Feed, timezone: Tz, user_id: UserId) -> CreateInteractionResponseMessage {
+    let embed = CreateEmbed::new()
+        .title("Remove this feed?")
+        .description(format!("`{}`", feed.url))
+        .field("Title", feed.title.as_deref().unwrap_or("Untitled"), false)
+        .field("Channel", format!("<#{}>", feed.channel_id), true)
+        .field("Last item", last_item(feed, timezone), true)
+        .color(0xf38ba8);
+
+    CreateInteractionResponseMessage::new()
+        .embed(embed)
+        .components(vec![confirm_row(
+            &format!("feed-{}", feed.id),
+            "Remove",
+            user_id,
+        )])
+}
+
+async fn picker(
+    ctx: &Context,
+    command: &CommandInteraction,
+    database: &Arc<dyn FeedStore>,
+    guild_id: u64,
+) -> Result<()> {
+    let feeds = picker_feeds(database, guild_id).await?;
+    if feeds.is_empty() {
+        return respond(
+            command,
+            &ctx.http,
+            "No RSS feeds configured for this server.",
+        )
+        .await;
+    }
+
+    let message = picker_page(ctx, GuildId::new(guild_id), &feeds, 0, command.user.id).await;
+    command
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::Message(message.ephemeral(true)),
+        )
+        .await?;
+    Ok(())
+}
+
+async fn picker_feeds(database: &Arc<dyn FeedStore>, guild_id: u64) -> Result<Vec<Feed>> {
+    let mut feeds = database.guild(guild_id).await?;
+    feeds.sort_by_key(|feed| {
+        (
+            feed.title.as_deref().unwrap_or_default().to_lowercase(),
+            feed.channel_id,
+        )
+    });
+    Ok(feeds)
+}
+
+async fn picker_page(
+    ctx: &Context,
+    guild_id: GuildId,
+    feeds: &[Feed],
+    page: usize,
+    user_id: UserId,
+) -> CreateInteractionResponseMessage {
+    let channel_names = guild_id.channels(&ctx.http).await.unwrap_or_default();
+    let total_pages = feeds.len().div_ceil(SELECT_OPTION_LIMIT).max(1);
+    let page = page.min(total_pages - 1);
+    let start = page * SELECT_OPTION_LIMIT;
+    let end = (start + SELECT_OPTION_LIMIT).min(feeds.len());
+
+    let options = feeds[start..end]
+        .iter()
+        .map(|feed| {
+            let title = feed.title.as_deref().unwrap_or("Untitled");
+            let description = format!(
+                "{} • {}",
+                extract_domain(&feed.url),
+                channel_label(&channel_names, feed.channel_id)
+            );
+            CreateSelectMenuOption::new(clip(title, 100), feed.id.to_string())
+                .description(clip(&description, 100))
+        })
+        .collect();
+
+    let embed = CreateEmbed::new()
+        .title("Remove which feed?")
+        .description("Pick a feed below. You'll be asked to confirm before it is removed.")
+        .color(0xf38ba8)
+        .footer(CreateEmbedFooter::new(format!(
+            "Page {} of {} • {} total feeds",
+            page + 1,
+            total_pages,
+            feeds.len()
+        )));
+
+    let select_menu = CreateSelectMenu::new(
+        format!("remove_pick_{}", user_id),
+        CreateSelectMenuKind::String { options },
+    )
+    .placeholder("Choose a feed to remove...");
+
+    let mut buttons = Vec::new();
+    if total_pages > 1 {
+        for (direction, emoji, disabled) in [
+            ("first", FIRST_EMOJI, page == 0),
+            ("prev", PREV_EMOJI, page == 0),
+            ("next", NEXT_EMOJI, page >= total_pages - 1),
+            ("last", LAST_EMOJI, page >= total_pages - 1),
+        ] {
+            buttons.push(
+                CreateButton::new(format!("remove_page_{}_{}_{}", direction, page, user_id))
+                    .emoji(button_emoji(emoji))
+                    .style(ButtonStyle::Secondary)
+                    .disabled(disabled),
+            );
+        }
+    }
+    buttons.push(cancel_button(user_id));
+
+    let mut components = vec![
+        CreateActionRow::SelectMenu(select_menu),
+        CreateActionRow::Buttons(buttons),
+    ];
+
+    if total_pages > 5 {
+        let options = page_window(page, total_pages)
+            .into_iter()
+            .map(|i| {
+                CreateSelectMenuOption::new(format!("Page {}", i + 1), (i + 1).to_string())
+                    .default_selection(i == page)
+            })
+            .collect();
+
+        components.push(CreateActionRow::SelectMenu(
+            CreateSelectMenu::new(
+                format!("remove_page_select_{}", user_id),
+                CreateSelectMenuKind::String { options },
+            )
+            .placeholder(format!("Page {} of {}", page + 1, total_pages)),
+        ));
+    }
+
+    CreateInteractionResponseMessage::new()
+        .embed(embed)
+        .components(components)
+}
+
+fn channel_label(channel_names: &HashMap<ChannelId, GuildChannel>, channel_id: i64) -> String {
+    channel_names
+        .get(&ChannelId::new(channel_id as u64))
+        .map(|channel| format!("#{}", channel.name))
+        .unwrap_or_else(|| format!("Channel {}", channel_id))
+}
+
+fn clip(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let clipped: String = text.chars().take(max_chars - 1).collect();
+    format!("{}…", clipped)
+}
+
+fn selected_values(interaction: &ComponentInteraction) -> &[String] {
+    match &interaction.data.kind {
+        ComponentInteractionDataKind::StringSelect { values } => values,
+        _ => &[],
+    }
+}
+
+fn confirm_row(scope: &str, label: &str, user_id: UserId) -> CreateActionRow {
+    CreateActionRow::Buttons(vec![
+        CreateButton::new(format!("remove_confirm_{}_{}", scope, user_id))
+            .label(label)
+            .style(ButtonStyle::Danger),
+        cancel_button(user_id),
+    ])
+}
+
+fn cancel_button(user_id: UserId) -> CreateButton {
+    CreateButton::new(format!("remove_cancel_{}", user_id))
+        .label("Cancel")
+        .style(ButtonStyle::Secondary)
+}
+
+pub async fn handle_component(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+    database: &Arc<dyn FeedStore>,
+) -> Result<()> {
+    let custom_id = interaction.data.custom_id.as_str();
+    let owner = custom_id.rsplit('_').next().unwrap_or_default();
+
+    if owner != interaction.user.id.to_string() {
+        let response = CreateInteractionResponse::Message(
+            CreateInteractionResponseMessage::new()
+                .content("Only the person who ran this command can use these buttons.")
+                .ephemeral(true),
+        );
+        interaction.create_response(&ctx.http, response).await?;
+        return Ok(());
+    }
+
+    let guild_id = interaction.guild_id.unwrap().get();
+    if custom_id.starts_with("remove_pick_") {
+        let id: Option<i64> = selected_values(interaction)
+            .first()
+            .and_then(|value| value.parse().ok());
+        let feed = database
+            .guild(guild_id)
+            .await?
+            .into_iter()
+            .find(|feed| Some(feed.id) == id);
+        let message = match feed {
+            Some(feed) => {
+                let timezone = guild_timezone(database, guild_id).await;
+                confirm_feed(&feed, timezone, interaction.user.id)
+            }
+            None => CreateInteractionResponseMessage::new()
+                .content("That feed was already removed.")
+                .embeds(vec![])
+                .components(vec![]),
+        };
+        interaction
+            .create_response(&ctx.http, CreateInteractionResponse::UpdateMessage(message))
+            .await?;
+        return Ok(());
+    }
+
+    if let Some(rest) = custom_id.strip_prefix("remove_page_") {
+        let feeds = picker_feeds(database, guild_id).await?;
+        let message = if feeds.is_empty() {
+            CreateInteractionResponseMessage::new()
+                .content("No RSS feeds configured for this server.")
+                .embeds(vec![])
+                .components(vec![])
+        } else {
+            let total_pages = feeds.len().div_ceil(SELECT_OPTION_LIMIT);
+            let mut parts = rest.split('_');
+            let direction = parts.next().unwrap_or_default();
+            let current: usize = parts.next().and_then(|page| page.parse().ok()).unwrap_or(0);
+            let page = match direction {
+                "first" => 0,
+                "prev" => current.saturating_sub(1),
+                "next" => current + 1,
+                "last" => total_pages - 1,
+                "select" => selected_values(interaction)
+                    .first()
+                    .and_then(|page| page.parse::<usize>().ok())
+                    .map_or(0, |page| page.saturating_sub(1)),
+                _ => current,
+            };
+            picker_page(
+                ctx,
+                GuildId::new(guild_id),
+                &feeds,
+                page,
+                interaction.user.id,
+            )
+            .await
+        };
+        interaction
+            .create_response(&ctx.http, CreateInteractionResponse::UpdateMessage(message))
+            .await?;
+        return Ok(());
+    }
+
+    let content = if custom_id.starts_with("remove_select_") {
+        let ids: Vec<i64> = selected_values(interaction)
+            .iter()
+            .filter_map(|value| value.parse().ok())
+            .collect();
+        remove_feeds(ctx, database, guild_id, &ids).await?
+    } else if let Some(rest) = custom_id.strip_prefix("remove_confirm_") {
+        let target = rest.split('_').next().unwrap_or_default();
 
-    let content = if removed {
-        format!("Successfully removed RSS feed: {}", url)
+        if let Some(id) = target.strip_prefix("feed-") {
+            remove_feeds(ctx, database, guild_id, &[id.parse()?]).await?
+        } else {
+            let scope = match target {
+                "all" => Scope::All,
+                id => Scope::Channel(id.parse()?),
+            };
+            remove_scope(ctx, database, guild_id, scope).await?
+        }
     } else {
-        "RSS feed not found.".to_string()
+        "Cancelled. No feeds were removed.".to_string()
     };
 
-    respond(command, &ctx.http, &content).await
+    let response = CreateInteractionResponseMessage::new()
+        .content(content)
+        .embeds(vec![])
+        .components(vec![]);
+    interaction
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::UpdateMessage(response),
+        )
+        .await?;
+    Ok(())
+}
+
+async fn remove_feeds(
+    ctx: &Context,
+    database: &Arc<dyn FeedStore>,
+    guild_id: u64,
+    ids: &[i64],
+) -> Result<String> {
+    let targets: Vec<Feed> = database
+        .guild(guild_id)
+        .await?
+        .into_iter()
+        .filter(|feed| ids.contains(&feed.id))
+        .collect();
+
+    let mut removed = Vec::new();
+    let mut webhook_urls = Vec::new();
+    for feed in targets {
+        if database
+            .remove_from_channel(guild_id, feed.channel_id as u64, &feed.url)
+            .await?
+        {
+            removed.push(feed.channel_id);
+            webhook_urls.extend(feed.webhook_url);
+        }
+    }
+
+    if !webhook_urls.is_empty() {
+        cleanup_webhooks(ctx, database, guild_id, webhook_urls).await?;
+    }
+
+    Ok(if removed.is_empty() {
+        "That feed was already removed.".to_string()
+    } else {
+        format!(
+            "Successfully removed RSS feed from {}",
+            removed
+                .iter()
+                .map(|channel_id| format!("<#{}>", channel_id))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    })
+}
+
+async fn remove_scope(
+    ctx: &Context,
+    database: &Arc<dyn FeedStore>,
+    guild_id: u64,
+    scope: Scope,
+) -> Result<String> {
+    let webhook_urls: Vec<String> = database
+        .guild(guild_id)
+        .await?
+        .into_iter()
+        .filter(|feed| scope.matches(feed.channel_id as u64))
+        .filter_map(|feed| feed.webhook_url)
+        .collect();
+
+    let removed = match scope {
+        Scope::All => database.remove_all(guild_id).await?,
+        Scope::Channel(channel_id) => database.remove_by_channel(guild_id, channel_id).await?,
+    };
+
+    if removed > 0 && !webhook_urls.is_empty() {
+        cleanup_webhooks(ctx, database, guild_id, webhook_urls).await?;
+    }
+
+    Ok(format!(
+        "Removed {} feed(s) from {}.",
+        removed,
+        scope.describe()
+    ))
+}
+
+#[derive(Clone, Copy)]
+enum Scope {
+    All,
+    Channel(u64),
+}
+
+impl Scope {
+    fn matches(self, channel_id: u64) -> bool {
+        match self {
+            Scope::All => true,
+            Scope::Channel(id) => id == channel_id,
+        }
+    }
+
+    fn describe(self) -> String {
+        match self {
+            Scope::All => "this server".to_string(),
+            Scope::Channel(id) => format!("<#{}>", id),
+        }
+    }
+
+    fn id(self) -> String {
+        match self {
+            Scope::All => "all".to_string(),
+            Scope::Channel(id) => id.to_string(),
+        }
+    }
 }
 
-fn extract_url(command: &CommandInteraction) -> Result<String> {
+async fn confirm(
+    ctx: &Context,
+    command: &CommandInteraction,
+    database: &Arc<dyn FeedStore>,
+    guild_id: u64,
+    scope: Scope,
+) -> Result<()> {
+    let count = database
+        .guild(guild_id)
+        .await?
+        .iter()
+        .filter(|feed| scope.matches(feed.channel_id as u64))
+        .count();
+
+    if count == 0 {
+        return respond(
+            command,
+            &ctx.http,
+            &format!("There are no feeds in {}.", scope.describe()),
+        )
+        .await;
+    }
+
+    let response = CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .content(format!(
+                "This will remove **{}** feed(s) from {}. This cannot be undone.",
+                count,
+                scope.describe()
+            ))
+            .components(vec![confirm_row(
+                &scope.id(),
+                &format!("Remove {} feed(s)", count),
+                command.user.id,
+            )])
+            .ephemeral(true),
+    );
+    command.create_response(&ctx.http, response).await?;
+    Ok(())
+}
+
+pub async fn cleanup_webhooks(
+    ctx: &Context,
+    database: &Arc<dyn FeedStore>,
+    guild_id: u64,
+    mut webhook_urls: Vec<String>,
+) -> Result<()> {
+    webhook_urls.sort();
+    webhook_urls.dedup();
+
+    let remaining = database.guild(guild_id).await?;
+
+    for webhook_url in webhook_urls {
+        let shared = remaining
+            .iter()
+            .any(|feed| feed.webhook_url.as_deref() == Some(webhook_url.as_str()));
+        if shared {
+            continue;
+        }
+
+        if let Err(e) = webhook::delete(&ctx.http, &webhook_url).await {
+            warn!("Failed to delete webhook for removed feed: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+fn extract_url(command: &CommandInteraction) -> Option<String> {
     command
         .data
         .options
@@ -34,7 +596,31 @@ fn extract_url(command: &CommandInteraction) -> Result<String> {
         .find(|opt| opt.name == "url")
         .and_then(|opt| opt.value.as_str())
         .map(|s| s.to_string())
-        .ok_or_else(|| anyhow::anyhow!("URL is required"))
+}
+
+fn extract_channel(command: &CommandInteraction) -> Option<u64> {
+    command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "channel")
+        .and_then(|opt| opt.value.as_channel_id())
+        .map(|channel| channel.get())
+}
+
+fn extract_scope(command: &CommandInteraction, channel: Option<u64>) -> Option<Scope> {
+    let all = command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "all")
+        .and_then(|opt| opt.value.as_bool())
+        .unwrap_or(false);
+    if all {
+        return Some(Scope::All);
+    }
+
+    channel.map(Scope::Channel)
 }
 
 async fn respond(
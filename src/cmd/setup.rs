@@ -1,19 +1,28 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use anyhow::Result;
+use reqwest::Client;
 use serenity::{
     all::{
-        ButtonStyle, ChannelType, CommandInteraction, ComponentInteraction,
+        ActionRowComponent, ButtonStyle, ChannelType, CommandInteraction, ComponentInteraction,
         ComponentInteractionDataKind, CreateActionRow, CreateButton, CreateChannel, CreateEmbed,
-        CreateEmbedFooter, CreateInteractionResponse, CreateInteractionResponseMessage,
-        CreateSelectMenu, CreateSelectMenuKind, CreateSelectMenuOption, EditInteractionResponse,
+        CreateEmbedFooter, CreateInputText, CreateInteractionResponse,
+        CreateInteractionResponseMessage, CreateModal, CreateSelectMenu, CreateSelectMenuKind,
+        CreateSelectMenuOption, EditInteractionResponse, InputTextStyle, ModalInteraction,
     },
     prelude::*,
 };
 use tokio::sync::Mutex;
-use tracing::error;
+use tracing::{error, info};
+
+use crate::{cmd::opinionated::subscribe, data::FeedStore, util::blocklist};
 
-use crate::data::Database;
+const STATE_TIMEOUT: Duration = Duration::from_secs(600);
+const MAX_MODAL_INPUTS: usize = 5;
 
 static STATES: std::sync::LazyLock<Mutex<HashMap<String, State>>> =
     std::sync::LazyLock::new(|| Mutex::new(HashMap::new()));
@@ -22,15 +31,26 @@ static STATES: std::sync::LazyLock<Mutex<HashMap<String, State>>> =
 struct State {
     category_id: u64,
     topics: Vec<String>,
-    guild_id: u64,
-    user_id: u64,
+    validate: bool,
+    prefix: String,
+    names: HashMap<String, String>,
+    created_at: Instant,
 }
 
 pub async fn execute(
     ctx: &Context,
     command: &CommandInteraction,
-    _database: &Arc<Database>,
+    _database: &Arc<dyn FeedStore>,
 ) -> Result<()> {
+    if !crate::cmd::opinionated::installed().await {
+        return respond_error(
+            command,
+            &ctx.http,
+            crate::cmd::opinionated::NO_COLLECTIONS_MESSAGE,
+        )
+        .await;
+    }
+
     let guild_id = command.guild_id.unwrap();
     let user_id = command.user.id;
 
@@ -68,12 +88,28 @@ pub async fn execute(
     let state = State {
         category_id: 0,
         topics: Vec::new(),
-        guild_id: guild_id.get(),
-        user_id: user_id.get(),
+        validate: command
+            .data
+            .options
+            .iter()
+            .find(|opt| opt.name == "validate")
+            .and_then(|opt| opt.value.as_bool())
+            .unwrap_or(true),
+        prefix: command
+            .data
+            .options
+            .iter()
+            .find(|opt| opt.name == "prefix")
+            .and_then(|opt| opt.value.as_str())
+            .map(slug)
+            .unwrap_or_default(),
+        names: HashMap::new(),
+        created_at: Instant::now(),
     };
 
     {
         let mut states = STATES.lock().await;
+        states.retain(|_, state| state.created_at.elapsed() < STATE_TIMEOUT);
         states.insert(key(guild_id.get(), user_id.get()), state);
     }
 
@@ -83,13 +119,24 @@ pub async fn execute(
 pub async fn handle_component(
     ctx: &Context,
     interaction: &ComponentInteraction,
-    database: &Arc<Database>,
+    database: &Arc<dyn FeedStore>,
+    client: &Client,
 ) -> Result<()> {
     let custom_id = &interaction.data.custom_id;
     let guild_id = interaction.guild_id.unwrap().get();
     let user_id = interaction.user.id.get();
     let state_key = key(guild_id, user_id);
 
+    let active = {
+        let mut states = STATES.lock().await;
+        states.retain(|_, state| state.created_at.elapsed() < STATE_TIMEOUT);
+        states.contains_key(&state_key)
+    };
+
+    if !active {
+        return expired(ctx, interaction).await;
+    }
+
     match &interaction.data.kind {
         ComponentInteractionDataKind::StringSelect { values } => {
             if custom_id == "setup_category_select" {
@@ -109,7 +156,7 @@ pub async fn handle_component(
                         }
                     }
 
-                    topics(ctx, interaction, database, category_id).await?;
+                    topics(ctx, interaction, database, category_id, &[]).await?;
                 }
             } else if custom_id == "setup_topic_select" {
                 let category_id = {
@@ -117,33 +164,99 @@ pub async fn handle_component(
                     states.get(&state_key).map(|s| s.category_id).unwrap_or(0)
                 };
 
-                {
+                let names = {
                     let mut states = STATES.lock().await;
-                    if let Some(state) = states.get_mut(&state_key) {
-                        state.topics = values.clone();
+                    match states.get_mut(&state_key) {
+                        Some(state) => {
+                            state.topics = values.clone();
+                            channel_names(state)
+                        }
+                        None => Vec::new(),
                     }
-                }
+                };
 
-                confirmation(ctx, interaction, database, category_id, values).await?;
+                confirmation(ctx, interaction, database, category_id, values, &names).await?;
             }
         }
         ComponentInteractionDataKind::Button => {
             if custom_id == "setup_confirm" {
-                let (category_id, topics) = {
+                let (category_id, topics, names, validate) = {
                     let states = STATES.lock().await;
                     if let Some(state) = states.get(&state_key) {
-                        (state.category_id, state.topics.clone())
+                        (
+                            state.category_id,
+                            state.topics.clone(),
+                            channel_names(state),
+                            state.validate,
+                        )
                     } else {
                         return Ok(());
                     }
                 };
 
-                process(ctx, interaction, database, category_id, &topics).await?;
+                let client = validate.then_some(client);
+                process(
+                    ctx,
+                    interaction,
+                    database,
+                    client,
+                    category_id,
+                    &topics,
+                    &names,
+                )
+                .await?;
 
                 {
                     let mut states = STATES.lock().await;
                     states.remove(&state_key);
                 }
+            } else if custom_id == "setup_preview"
+                || custom_id == "setup_back_confirmation"
+                || custom_id == "setup_edit_names"
+            {
+                let (category_id, topics_selected, names) = {
+                    let states = STATES.lock().await;
+                    states
+                        .get(&state_key)
+                        .map(|s| (s.category_id, s.topics.clone(), channel_names(s)))
+                        .unwrap_or_default()
+                };
+
+                if custom_id == "setup_preview" {
+                    preview(
+                        ctx,
+                        interaction,
+                        database,
+                        category_id,
+                        &topics_selected,
+                        &names,
+                    )
+                    .await?;
+                } else if custom_id == "setup_edit_names" {
+                    edit_names(ctx, interaction, &topics_selected, &names).await?;
+                } else {
+                    confirmation(
+                        ctx,
+                        interaction,
+                        database,
+                        category_id,
+                        &topics_selected,
+                        &names,
+                    )
+                    .await?;
+                }
+            } else if custom_id == "setup_back_categories" {
+                back_to_categories(ctx, interaction).await?;
+            } else if custom_id == "setup_back_topics" {
+                let (category_id, topics_selected) = {
+                    let states = STATES.lock().await;
+                    states
+                        .get(&state_key)
+                        .map(|s| (s.category_id, s.topics.clone()))
+                        .unwrap_or_default()
+                };
+
+                topics(ctx, interaction, database, category_id, &topics_selected).await?;
             } else if custom_id == "setup_cancel" {
                 cancel(ctx, interaction).await?;
 
@@ -167,6 +280,45 @@ async fn categories(
         serenity::model::channel::GuildChannel,
     )],
 ) -> Result<()> {
+    let (embed, components) = category_page(categories);
+    let response = EditInteractionResponse::new()
+        .embed(embed)
+        .components(components);
+
+    command.edit_response(&ctx.http, response).await?;
+    Ok(())
+}
+
+async fn back_to_categories(ctx: &Context, interaction: &ComponentInteraction) -> Result<()> {
+    let guild_id = interaction.guild_id.unwrap();
+    let category_channels = guild_id
+        .channels(&ctx.http)
+        .await?
+        .into_iter()
+        .filter(|(_, channel)| channel.kind == ChannelType::Category)
+        .collect::<Vec<_>>();
+
+    let (embed, components) = category_page(&category_channels);
+    let response = CreateInteractionResponseMessage::new()
+        .embed(embed)
+        .components(components);
+
+    interaction
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::UpdateMessage(response),
+        )
+        .await?;
+
+    Ok(())
+}
+
+fn category_page(
+    categories: &[(
+        serenity::model::id::ChannelId,
+        serenity::model::channel::GuildChannel,
+    )],
+) -> (CreateEmbed, Vec<CreateActionRow>) {
     let mut options = vec![
         CreateSelectMenuOption::new("Create New Category", "new_category")
             .description("Create a new category for RSS feeds"),
@@ -175,7 +327,7 @@ async fn categories(
     for (id, channel) in categories {
         options.push(
             CreateSelectMenuOption::new(&channel.name, id.to_string())
-                .description(&format!("Use existing category: {}", channel.name)),
+                .description(format!("Use existing category: {}", channel.name)),
         );
     }
 
@@ -190,23 +342,31 @@ async fn categories(
         .description("Select where to organize your RSS feeds")
         .color(0x89b4fa);
 
-    let components = vec![CreateActionRow::SelectMenu(select_menu)];
-    let response = EditInteractionResponse::new()
-        .embed(embed)
-        .components(components);
+    let components = vec![
+        CreateActionRow::SelectMenu(select_menu),
+        CreateActionRow::Buttons(vec![cancel_button()]),
+    ];
 
-    command.edit_response(&ctx.http, response).await?;
-    Ok(())
+    (embed, components)
 }
 
 async fn topics(
     ctx: &Context,
     interaction: &ComponentInteraction,
-    _database: &Arc<Database>,
+    _database: &Arc<dyn FeedStore>,
     category_id: u64,
+    selected: &[String],
 ) -> Result<()> {
     let topics = match crate::cmd::opinionated::topics().await {
-        Ok(topics) => topics,
+        Ok(topics) if !topics.is_empty() => topics,
+        Ok(_) => {
+            return respond_component_error(
+                interaction,
+                &ctx.http,
+                crate::cmd::opinionated::NO_COLLECTIONS_MESSAGE,
+            )
+            .await;
+        }
         Err(e) => {
             error!("Failed to load topics: {}", e);
             return respond_component_error(
@@ -218,20 +378,12 @@ async fn topics(
         }
     };
 
-    if topics.is_empty() {
-        return respond_component_error(
-            interaction,
-            &ctx.http,
-            "No curated feed collections available",
-        )
-        .await;
-    }
-
     let options: Vec<_> = topics
         .iter()
         .map(|topic| {
             CreateSelectMenuOption::new(topic, topic)
-                .description(&format!("Add {} RSS feeds", topic))
+                .description(format!("Add {} RSS feeds", topic))
+                .default_selection(selected.contains(topic))
         })
         .collect();
 
@@ -262,7 +414,15 @@ async fn topics(
         .field("Available Topics", topics.len().to_string(), true)
         .color(0xb4befe);
 
-    let components = vec![CreateActionRow::SelectMenu(select_menu)];
+    let components = vec![
+        CreateActionRow::SelectMenu(select_menu),
+        CreateActionRow::Buttons(vec![
+            CreateButton::new("setup_back_categories")
+                .label("Back")
+                .style(ButtonStyle::Secondary),
+            cancel_button(),
+        ]),
+    ];
     let response = CreateInteractionResponseMessage::new()
         .embed(embed)
         .components(components);
@@ -280,15 +440,44 @@ async fn topics(
 async fn confirmation(
     ctx: &Context,
     interaction: &ComponentInteraction,
-    _database: &Arc<Database>,
+    _database: &Arc<dyn FeedStore>,
     category_id: u64,
     topics: &[String],
+    names: &[String],
 ) -> Result<()> {
     if topics.is_empty() {
         return respond_component_error(interaction, &ctx.http, "Please select at least one topic")
             .await;
     }
 
+    let guild_id = interaction.guild_id.unwrap();
+    let (embed, components) =
+        match confirmation_page(ctx, guild_id, category_id, topics, names).await {
+            Ok(page) => page,
+            Err(e) => return respond_component_error(interaction, &ctx.http, &e.to_string()).await,
+        };
+
+    let response = CreateInteractionResponseMessage::new()
+        .embed(embed)
+        .components(components);
+
+    interaction
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::UpdateMessage(response),
+        )
+        .await?;
+
+    Ok(())
+}
+
+async fn confirmation_page(
+    ctx: &Context,
+    guild_id: serenity::model::id::GuildId,
+    category_id: u64,
+    topics: &[String],
+    names: &[String],
+) -> Result<(CreateEmbed, Vec<CreateActionRow>)> {
     let mut total_feeds = 0;
     let mut topic_fields = Vec::new();
 
@@ -301,12 +490,7 @@ async fn confirmation(
             }
             Err(e) => {
                 error!("Failed to load collection for topic {}: {}", topic, e);
-                return respond_component_error(
-                    interaction,
-                    &ctx.http,
-                    &format!("Failed to load topic: {}", topic),
-                )
-                .await;
+                return Err(anyhow::anyhow!("Failed to load topic: {}", topic));
             }
         }
     }
@@ -323,9 +507,13 @@ async fn confirmation(
         }
     };
 
-    let channels_list = topics
+    let existing = text_channels(ctx, guild_id).await?;
+    let channels_list = names
         .iter()
-        .map(|topic| topic.to_lowercase().replace(' ', "-"))
+        .map(|name| match find_existing(&existing, name, category_id) {
+            Some(_) => format!("#{} (existing)", name),
+            None => format!("#{}", name),
+        })
         .collect::<Vec<_>>()
         .join("\n");
 
@@ -335,7 +523,7 @@ async fn confirmation(
         .field("Category", category_name, true)
         .field("Selected Topics", topics.len().to_string(), true)
         .field("Total Feeds", total_feeds.to_string(), true)
-        .field("Channels to Create", channels_list, false)
+        .field("Channels", channels_list, false)
         .color(0xf9e2af)
         .footer(CreateEmbedFooter::new(
             "Click Confirm to proceed or Cancel to abort",
@@ -349,16 +537,260 @@ async fn confirmation(
         CreateButton::new("setup_confirm")
             .label("Confirm Setup")
             .style(ButtonStyle::Success),
-        CreateButton::new("setup_cancel")
-            .label("Cancel")
+        CreateButton::new("setup_preview")
+            .label("Preview")
+            .style(ButtonStyle::Primary),
+        CreateButton::new("setup_edit_names")
+            .label("Edit Names")
             .style(ButtonStyle::Secondary),
+        CreateButton::new("setup_back_topics")
+            .label("Back")
+            .style(ButtonStyle::Secondary),
+        cancel_button(),
     ];
 
-    let components = vec![CreateActionRow::Buttons(buttons)];
+    Ok((embed, vec![CreateActionRow::Buttons(buttons)]))
+}
+
+async fn edit_names(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+    topics: &[String],
+    names: &[String],
+) -> Result<()> {
+    let rows = topics
+        .iter()
+        .zip(names)
+        .take(MAX_MODAL_INPUTS)
+        .enumerate()
+        .map(|(index, (topic, name))| {
+            let label: String = topic.chars().take(45).collect();
+            CreateActionRow::InputText(
+                CreateInputText::new(InputTextStyle::Short, label, format!("name_{}", index))
+                    .value(name)
+                    .min_length(1)
+                    .max_length(100)
+                    .required(true),
+            )
+        })
+        .collect();
+
+    let modal = CreateModal::new("setup_names_modal", "Edit Channel Names").components(rows);
+    interaction
+        .create_response(&ctx.http, CreateInteractionResponse::Modal(modal))
+        .await?;
+
+    Ok(())
+}
+
+pub async fn handle_modal(
+    ctx: &Context,
+    modal: &ModalInteraction,
+    _database: &Arc<dyn FeedStore>,
+) -> Result<()> {
+    let guild_id = modal.guild_id.unwrap();
+    let state_key = key(guild_id.get(), modal.user.id.get());
+
+    let edited: Vec<(usize, String)> = modal
+        .data
+        .components
+        .iter()
+        .flat_map(|row| row.components.iter())
+        .filter_map(|component| match component {
+            ActionRowComponent::InputText(input) => {
+                let index = input.custom_id.strip_prefix("name_")?.parse().ok()?;
+                Some((index, slug(input.value.as_deref().unwrap_or_default())))
+            }
+            _ => None,
+        })
+        .collect();
+
+    let state = {
+        let mut states = STATES.lock().await;
+        states.retain(|_, state| state.created_at.elapsed() < STATE_TIMEOUT);
+        states.get_mut(&state_key).map(|state| {
+            for (index, name) in edited {
+                if let Some(topic) = state.topics.get(index) {
+                    if name.is_empty() {
+                        state.names.remove(topic);
+                    } else {
+                        state.names.insert(topic.clone(), name);
+                    }
+                }
+            }
+            (
+                state.category_id,
+                state.topics.clone(),
+                channel_names(state),
+            )
+        })
+    };
+
+    let Some((category_id, topics, names)) = state else {
+        let embed = CreateEmbed::new()
+            .title("Setup Expired")
+            .description("This setup session has expired. Please run `/setup` again.")
+            .color(0xf38ba8);
+        let response = CreateInteractionResponseMessage::new()
+            .embed(embed)
+            .components(vec![]);
+        modal
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::UpdateMessage(response),
+            )
+            .await?;
+        return Ok(());
+    };
+
+    let (embed, components) =
+        confirmation_page(ctx, guild_id, category_id, &topics, &names).await?;
     let response = CreateInteractionResponseMessage::new()
         .embed(embed)
         .components(components);
 
+    modal
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::UpdateMessage(response),
+        )
+        .await?;
+
+    Ok(())
+}
+
+fn channel_names(state: &State) -> Vec<String> {
+    state
+        .topics
+        .iter()
+        .map(|topic| {
+            state
+                .names
+                .get(topic)
+                .cloned()
+                .unwrap_or_else(|| slug(&format!("{}{}", state.prefix, topic)))
+        })
+        .collect()
+}
+
+fn slug(name: &str) -> String {
+    name.trim()
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+async fn text_channels(
+    ctx: &Context,
+    guild_id: serenity::model::id::GuildId,
+) -> Result<Vec<serenity::model::channel::GuildChannel>> {
+    Ok(guild_id
+        .channels(&ctx.http)
+        .await?
+        .into_values()
+        .filter(|channel| channel.kind == ChannelType::Text)
+        .collect())
+}
+
+fn find_existing(
+    channels: &[serenity::model::channel::GuildChannel],
+    name: &str,
+    category_id: u64,
+) -> Option<u64> {
+    let matching = channels.iter().filter(|channel| channel.name == name);
+
+    matching
+        .clone()
+        .find(|channel| channel.parent_id.map(|id| id.get()) == Some(category_id))
+        .or_else(|| matching.clone().next())
+        .map(|channel| channel.id.get())
+}
+
+async fn preview(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+    database: &Arc<dyn FeedStore>,
+    category_id: u64,
+    topics: &[String],
+    names: &[String],
+) -> Result<()> {
+    let guild_id = interaction.guild_id.unwrap();
+    let existing = text_channels(ctx, guild_id).await?;
+    let guild_id = guild_id.get();
+
+    let category = if category_id == 0 {
+        "A new **RSS Feeds** category will be created".to_string()
+    } else {
+        match serenity::model::id::ChannelId::new(category_id)
+            .name(ctx)
+            .await
+        {
+            Ok(name) => format!("Channels go in **{}**", name),
+            Err(_) => "Channels go in the selected category".to_string(),
+        }
+    };
+
+    let mut embed = CreateEmbed::new()
+        .title("Setup Preview")
+        .description(format!("{}\nNothing has been changed yet.", category))
+        .color(0x89dceb)
+        .footer(CreateEmbedFooter::new(
+            "Click Confirm to apply these changes",
+        ));
+
+    for (topic, name) in topics.iter().zip(names).take(24) {
+        let collection = match crate::cmd::opinionated::load_collection(topic).await {
+            Ok(collection) => collection,
+            Err(_) => {
+                embed = embed.field(topic, "Failed to load collection", false);
+                continue;
+            }
+        };
+
+        let mut new_feeds = Vec::new();
+        let mut present = 0;
+        let mut blocked = 0;
+        for feed in &collection.feeds {
+            if database.exists(guild_id, &feed.url).await? {
+                present += 1;
+            } else if blocklist::blocked(&feed.url).is_some() {
+                blocked += 1;
+            } else {
+                new_feeds.push(feed.name.as_str());
+            }
+        }
+
+        let action = match find_existing(&existing, name, category_id) {
+            Some(_) => "Reuse",
+            None => "Create",
+        };
+        let value = format!(
+            "{} `#{}`\n{} to add, {} already present, {} blocked\n{}",
+            action,
+            name,
+            new_feeds.len(),
+            present,
+            blocked,
+            new_feeds.join(", ")
+        );
+        embed = embed.field(topic, crate::util::parser::truncate(&value, 1024), false);
+    }
+
+    let buttons = vec![
+        CreateButton::new("setup_confirm")
+            .label("Confirm Setup")
+            .style(ButtonStyle::Success),
+        CreateButton::new("setup_back_confirmation")
+            .label("Back")
+            .style(ButtonStyle::Secondary),
+        cancel_button(),
+    ];
+
+    let response = CreateInteractionResponseMessage::new()
+        .embed(embed)
+        .components(vec![CreateActionRow::Buttons(buttons)]);
+
     interaction
         .create_response(
             &ctx.http,
@@ -372,9 +804,11 @@ async fn confirmation(
 async fn process(
     ctx: &Context,
     interaction: &ComponentInteraction,
-    database: &Arc<Database>,
+    database: &Arc<dyn FeedStore>,
+    client: Option<&Client>,
     category_id: u64,
     topics: &[String],
+    names: &[String],
 ) -> Result<()> {
     let guild_id = interaction.guild_id.unwrap();
 
@@ -395,7 +829,7 @@ async fn process(
         .await?;
 
     let actual_category_id = if category_id == 0 {
-        match create_category(&ctx, guild_id, "RSS Feeds").await {
+        match create_category(ctx, guild_id, "RSS Feeds").await {
             Ok(id) => id,
             Err(e) => {
                 error!("Failed to create category: {}", e);
@@ -407,12 +841,16 @@ async fn process(
         category_id
     };
 
+    let existing = text_channels(ctx, guild_id).await?;
+    let mut channels_created = 0;
     let mut total_added = 0;
     let mut total_skipped = 0;
+    let mut total_unreachable = 0;
+    let mut total_blocked = 0;
     let mut total_failed = 0;
     let mut channel_fields = Vec::new();
 
-    for topic in topics {
+    for (topic, channel_name) in topics.iter().zip(names) {
         let collection = match crate::cmd::opinionated::load_collection(topic).await {
             Ok(collection) => collection,
             Err(e) => {
@@ -422,55 +860,60 @@ async fn process(
             }
         };
 
-        let channel_name = topic.to_lowercase().replace(' ', "-");
-        let channel_id = match create_channel(&ctx, guild_id, &channel_name, actual_category_id)
-            .await
-        {
-            Ok(id) => id,
-            Err(e) => {
-                error!("Failed to create channel for {}: {}", topic, e);
-                channel_fields.push((topic.clone(), "Failed to create channel".to_string(), false));
-                continue;
-            }
-        };
-
-        let mut added_count = 0;
-        let mut skipped_count = 0;
-        let mut failed_feeds = 0;
-
-        for feed in &collection.feeds {
-            if database.exists(guild_id.get(), &feed.url).await? {
-                skipped_count += 1;
-                continue;
+        let channel_id = match find_existing(&existing, channel_name, actual_category_id) {
+            Some(id) => {
+                info!("Reusing existing channel #{} for {}", channel_name, topic);
+                id
             }
-
-            match database
-                .add(
-                    guild_id.get(),
-                    channel_id,
-                    &feed.url,
-                    Some(&feed.name),
-                    None,
-                )
-                .await
-            {
-                Ok(()) => added_count += 1,
+            None => match create_channel(ctx, guild_id, channel_name, actual_category_id).await {
+                Ok(id) => {
+                    channels_created += 1;
+                    id
+                }
                 Err(e) => {
-                    error!("Failed to add feed {} in {}: {}", feed.name, topic, e);
-                    failed_feeds += 1;
+                    error!("Failed to create channel for {}: {}", topic, e);
+                    channel_fields.push((
+                        topic.clone(),
+                        "Failed to create channel".to_string(),
+                        false,
+                    ));
+                    continue;
                 }
-            }
-        }
+            },
+        };
+
+        let result = subscribe(
+            database.as_ref(),
+            client,
+            &collection.feeds,
+            guild_id.get(),
+            channel_id,
+            interaction.user.id.get(),
+            false,
+        )
+        .await?;
+        let added_count = result.added;
+        let skipped_count = result.skipped;
+        let unreachable_count = result.unreachable.len();
+        let blocked_count = result.blocked.len();
+        let failed_feeds = result.failed.len();
 
         total_added += added_count;
         total_skipped += skipped_count;
+        total_unreachable += unreachable_count;
+        total_blocked += blocked_count;
         total_failed += failed_feeds;
 
         channel_fields.push((
             format!("{} Channel", topic),
             format!(
-                "<#{}>\n{} added, {} skipped, {} failed",
-                channel_id, added_count, skipped_count, failed_feeds
+                "<#{}>\n{} added, {} skipped, {} unreachable, {} blocked, {} failed",
+                channel_id,
+                added_count,
+                skipped_count,
+                unreachable_count,
+                blocked_count,
+                failed_feeds
             ),
             false,
         ));
@@ -479,9 +922,12 @@ async fn process(
     let mut embed = CreateEmbed::new()
         .title("Setup Complete")
         .description("Your RSS feeds have been successfully configured")
-        .field("Channels Created", topics.len().to_string(), true)
+        .field("Channels Created", channels_created.to_string(), true)
         .field("Total Feeds Added", total_added.to_string(), true)
         .field("Total Feeds Skipped", total_skipped.to_string(), true)
+        .field("Unreachable Feeds", total_unreachable.to_string(), true)
+        .field("Blocked Feeds", total_blocked.to_string(), true)
+        .field("Failed Feeds", total_failed.to_string(), true)
         .color(0xa6e3a1)
         .footer(CreateEmbedFooter::new("RSS feeds are now active"));
 
@@ -533,13 +979,41 @@ async fn create_channel(
     Ok(channel.id.get())
 }
 
+fn cancel_button() -> CreateButton {
+    CreateButton::new("setup_cancel")
+        .label("Cancel")
+        .style(ButtonStyle::Secondary)
+}
+
 async fn cancel(ctx: &Context, interaction: &ComponentInteraction) -> Result<()> {
     let embed = CreateEmbed::new()
         .title("Setup Cancelled")
         .description("No changes were made to your server")
         .color(0xf38ba8);
 
-    let response = CreateInteractionResponseMessage::new().embed(embed);
+    let response = CreateInteractionResponseMessage::new()
+        .embed(embed)
+        .components(vec![]);
+
+    interaction
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::UpdateMessage(response),
+        )
+        .await?;
+
+    Ok(())
+}
+
+async fn expired(ctx: &Context, interaction: &ComponentInteraction) -> Result<()> {
+    let embed = CreateEmbed::new()
+        .title("Setup Expired")
+        .description("This setup session has expired. Please run `/setup` again.")
+        .color(0xf38ba8);
+
+    let response = CreateInteractionResponseMessage::new()
+        .embed(embed)
+        .components(vec![]);
 
     interaction
         .create_response(
@@ -617,3 +1091,67 @@ async fn defer(command: &CommandInteraction, http: &serenity::http::Http) -> Res
 fn key(guild_id: u64, user_id: u64) -> String {
     format!("{}:{}", guild_id, user_id)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cmd::opinionated::OpinionatedFeed, data::mock::MockStore};
+
+    fn state(prefix: &str, topics: &[&str]) -> State {
+        State {
+            category_id: 0,
+            topics: topics.iter().map(|topic| topic.to_string()).collect(),
+            validate: false,
+            prefix: prefix.to_string(),
+            names: HashMap::new(),
+            created_at: Instant::now(),
+        }
+    }
+
+    #[test]
+    fn channel_names_use_prefix_and_overrides() {
+        let mut state = state("rss ", &["Tech News", "Science"]);
+        state
+            .names
+            .insert("Science".to_string(), "lab-notes".to_string());
+
+        assert_eq!(channel_names(&state), vec!["rss-tech-news", "lab-notes"]);
+    }
+
+    #[test]
+    fn slug_collapses_whitespace() {
+        assert_eq!(slug("  Open   Source  "), "open-source");
+    }
+
+    #[tokio::test]
+    async fn topics_sharing_a_feed_only_subscribe_it_once() {
+        let store = MockStore::new();
+        let shared = OpinionatedFeed {
+            name: "Shared".to_string(),
+            url: "https://shared.example/rss".to_string(),
+            description: None,
+        };
+        let tech = vec![shared.clone()];
+        let science = vec![
+            shared,
+            OpinionatedFeed {
+                name: "Science".to_string(),
+                url: "https://science.example/rss".to_string(),
+                description: None,
+            },
+        ];
+
+        let first = subscribe(&store, None, &tech, 1, 10, 100, false)
+            .await
+            .unwrap();
+        let second = subscribe(&store, None, &science, 1, 20, 100, false)
+            .await
+            .unwrap();
+
+        assert_eq!((first.added, first.skipped), (1, 0));
+        assert_eq!((second.added, second.skipped), (1, 1));
+        let feeds = store.guild(1).await.unwrap();
+        assert_eq!(feeds.len(), 2);
+        assert_eq!(feeds[1].channel_id, 20);
+    }
+}
@@ -1,38 +1,119 @@
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    sync::{Arc, LazyLock, Mutex},
+    time::{Duration, Instant},
+};
 
 use anyhow::Result;
+use reqwest::Client;
 use serenity::{
     all::{
-        CommandInteraction, CreateInteractionResponse, CreateInteractionResponseMessage,
-        EditInteractionResponse,
+        CommandInteraction, CreateEmbed, CreateInteractionResponse,
+        CreateInteractionResponseMessage, EditInteractionResponse,
     },
     prelude::*,
 };
 
 use crate::{
-    data::Database,
+    data::FeedStore,
     scheduler::tasks::{check, single},
+    util::parser,
 };
 
+type SyncKey = (u64, Option<String>);
+
+static LAST_SYNC: LazyLock<Mutex<HashMap<SyncKey, Instant>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Clone, Copy)]
+pub struct Cooldowns {
+    pub all: Duration,
+    pub single: Duration,
+}
+
+impl Default for Cooldowns {
+    fn default() -> Self {
+        Self {
+            all: Duration::from_secs(300),
+            single: Duration::from_secs(30),
+        }
+    }
+}
+
 pub async fn execute(
     ctx: &Context,
     command: &CommandInteraction,
-    database: &Arc<Database>,
+    database: &Arc<dyn FeedStore>,
+    client: &Client,
+    cooldowns: Cooldowns,
 ) -> Result<()> {
     let url = extract_url(command);
+    let channel = extract_channel(command);
+    let guild_id = command.guild_id.unwrap().get();
+
+    let key = (guild_id, url.as_deref().map(parser::normalize_feed_url));
+    if let Some(remaining) = start_cooldown(key, cooldowns) {
+        let what = if url.is_some() {
+            "This feed was"
+        } else {
+            "This server's feeds were"
+        };
+        let response = CreateInteractionResponse::Message(
+            CreateInteractionResponseMessage::new()
+                .content(format!(
+                    "{} synced recently. Try again in {}.",
+                    what,
+                    format_remaining(remaining)
+                ))
+                .ephemeral(true),
+        );
+        command.create_response(&ctx.http, response).await?;
+        return Ok(());
+    }
+
     defer_response(command, &ctx.http).await?;
 
-    let result = if let Some(feed_url) = url {
-        sync_single(database, ctx, &feed_url).await
+    let edit_response = if let Some(feed_url) = url {
+        EditInteractionResponse::new()
+            .content(sync_single(database, ctx, client, guild_id, &feed_url, channel).await?)
     } else {
-        sync_all(database, ctx).await
+        sync_all(database, ctx, client, guild_id).await?
     };
 
-    let edit_response = EditInteractionResponse::new().content(result);
     command.edit_response(&ctx.http, edit_response).await?;
     Ok(())
 }
 
+fn start_cooldown(key: (u64, Option<String>), cooldowns: Cooldowns) -> Option<Duration> {
+    let cooldown = if key.1.is_some() {
+        cooldowns.single
+    } else {
+        cooldowns.all
+    };
+
+    let mut last_sync = LAST_SYNC.lock().unwrap();
+    last_sync.retain(|_, synced_at| synced_at.elapsed() < cooldowns.all.max(cooldowns.single));
+
+    if let Some(synced_at) = last_sync.get(&key) {
+        let elapsed = synced_at.elapsed();
+        if elapsed < cooldown {
+            return Some(cooldown - elapsed);
+        }
+    }
+
+    last_sync.insert(key, Instant::now());
+    None
+}
+
+fn format_remaining(remaining: Duration) -> String {
+    let seconds = remaining.as_secs().max(1);
+    match (seconds / 60, seconds % 60) {
+        (0, seconds) => format!("{}s", seconds),
+        (minutes, 0) => format!("{}m", minutes),
+        (minutes, seconds) => format!("{}m {}s", minutes, seconds),
+    }
+}
+
 fn extract_url(command: &CommandInteraction) -> Option<String> {
     command
         .data
@@ -43,6 +124,16 @@ fn extract_url(command: &CommandInteraction) -> Option<String> {
         .map(|s| s.to_string())
 }
 
+fn extract_channel(command: &CommandInteraction) -> Option<u64> {
+    command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "channel")
+        .and_then(|opt| opt.value.as_channel_id())
+        .map(|channel| channel.get())
+}
+
 async fn defer_response(command: &CommandInteraction, http: &serenity::http::Http) -> Result<()> {
     let response =
         CreateInteractionResponse::Defer(CreateInteractionResponseMessage::new().ephemeral(true));
@@ -50,22 +141,114 @@ async fn defer_response(command: &CommandInteraction, http: &serenity::http::Htt
     Ok(())
 }
 
-async fn sync_single(database: &Arc<Database>, ctx: &Context, feed_url: &str) -> String {
-    match single(database.clone(), ctx.http.clone(), feed_url).await {
-        Ok(new_items) => {
-            if new_items > 0 {
-                format!("Synced feed and found {} new items", new_items)
-            } else {
-                "Synced feed, no new items found".to_string()
-            }
+async fn sync_single(
+    database: &Arc<dyn FeedStore>,
+    ctx: &Context,
+    client: &Client,
+    guild_id: u64,
+    feed_url: &str,
+    channel: Option<u64>,
+) -> Result<String> {
+    let feeds: Vec<_> = match channel {
+        Some(channel) => database
+            .find_in_guild(guild_id, feed_url, Some(channel))
+            .await?
+            .into_iter()
+            .collect(),
+        None => {
+            let normalized = parser::normalize_feed_url(feed_url);
+            database
+                .guild(guild_id)
+                .await?
+                .into_iter()
+                .filter(|feed| parser::normalize_feed_url(&feed.url) == normalized)
+                .collect()
         }
-        Err(e) => format!("Failed to sync feed: {}", e),
+    };
+
+    if feeds.is_empty() {
+        return Ok("RSS feed not found.".to_string());
+    }
+
+    let mut lines = Vec::new();
+    for feed in &feeds {
+        let line = match single(database.clone(), ctx.http.clone(), client, feed).await {
+            Ok(new_items) => {
+                if new_items > 0 {
+                    format!("Synced feed and found {} new items", new_items)
+                } else {
+                    "Synced feed, no new items found".to_string()
+                }
+            }
+            Err(e) => format!("Failed to sync feed: {}", e),
+        };
+        lines.push(if feeds.len() > 1 {
+            format!("<#{}>: {}", feed.channel_id, line)
+        } else {
+            line
+        });
     }
+
+    Ok(lines.join("\n"))
 }
 
-async fn sync_all(database: &Arc<Database>, ctx: &Context) -> String {
-    match check(database.clone(), ctx.http.clone()).await {
-        Ok(_) => "Successfully synced all feeds".to_string(),
-        Err(e) => format!("Failed to sync feeds: {}", e),
+async fn sync_all(
+    database: &Arc<dyn FeedStore>,
+    ctx: &Context,
+    client: &Client,
+    guild_id: u64,
+) -> Result<EditInteractionResponse> {
+    let summary = match check(database.clone(), ctx.http.clone(), client.clone()).await {
+        Ok(Some(summary)) => summary,
+        Ok(None) => {
+            return Ok(EditInteractionResponse::new()
+                .content("A feed check is already running. Try again in a moment."));
+        }
+        Err(e) => {
+            return Ok(
+                EditInteractionResponse::new().content(format!("Failed to sync feeds: {}", e))
+            );
+        }
+    };
+
+    let guild_urls: Vec<String> = database
+        .guild(guild_id)
+        .await?
+        .into_iter()
+        .map(|feed| feed.url)
+        .collect();
+    let failures: Vec<&(String, String)> = summary
+        .failures
+        .iter()
+        .filter(|(url, _)| guild_urls.contains(url))
+        .collect();
+
+    let mut embed = CreateEmbed::new()
+        .title("Sync Complete")
+        .field("Checked", summary.checked.to_string(), true)
+        .field("Succeeded", summary.succeeded.to_string(), true)
+        .field("Failed", summary.failed.to_string(), true)
+        .field("New Items", summary.new_items.to_string(), true)
+        .color(if failures.is_empty() {
+            0xa6e3a1
+        } else {
+            0xf9e2af
+        });
+
+    if !failures.is_empty() {
+        let mut description = format!("{} feed(s) in this server failed:", failures.len());
+        for (url, reason) in failures.iter().take(10) {
+            description.push_str(&format!(
+                "\n• `{}`\n  {}",
+                parser::truncate(url, 200),
+                parser::truncate(reason, 200)
+            ));
+        }
+        if failures.len() > 10 {
+            description.push_str(&format!("\n... and {} more", failures.len() - 10));
+        }
+        embed = embed.description(description);
     }
+
+    Ok(EditInteractionResponse::new().embed(embed))
 }
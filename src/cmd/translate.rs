@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use serenity::{
+    all::{CommandInteraction, CreateInteractionResponse, CreateInteractionResponseMessage},
+    prelude::*,
+};
+
+use crate::{data::FeedStore, util::translate};
+
+pub async fn execute(
+    ctx: &Context,
+    command: &CommandInteraction,
+    database: &Arc<dyn FeedStore>,
+) -> Result<()> {
+    let url = extract_option(command, "url").ok_or_else(|| anyhow::anyhow!("URL is required"))?;
+    let language = extract_option(command, "language")
+        .ok_or_else(|| anyhow::anyhow!("Language is required"))?;
+    let guild_id = command.guild_id.unwrap().get();
+
+    let language = if language.eq_ignore_ascii_case("off") {
+        None
+    } else if is_language_code(&language) {
+        Some(language.to_lowercase())
+    } else {
+        return respond(
+            command,
+            &ctx.http,
+            "Invalid language. Use a code like `en`, `de` or `pt-br`, or `off` to disable.",
+        )
+        .await;
+    };
+
+    if language.is_some() && !translate::enabled() {
+        return respond(
+            command,
+            &ctx.http,
+            "Translation isn't configured on this bot. Ask the bot owner to set up `[translate]` \
+             in config.toml.",
+        )
+        .await;
+    }
+
+    let content = if !database
+        .set_translate_to(guild_id, &url, language.as_deref())
+        .await?
+    {
+        "RSS feed not found.".to_string()
+    } else if let Some(language) = &language {
+        format!(
+            "New posts from {} will be translated to `{}`",
+            url, language
+        )
+    } else {
+        format!("Stopped translating posts from {}", url)
+    };
+
+    respond(command, &ctx.http, &content).await
+}
+
+fn is_language_code(input: &str) -> bool {
+    let mut parts = input.split('-');
+    let primary = parts.next().unwrap_or_default();
+    let region = parts.next();
+
+    parts.next().is_none()
+        && (2..=3).contains(&primary.len())
+        && primary.chars().all(|c| c.is_ascii_alphabetic())
+        && region.is_none_or(|region| {
+            (2..=4).contains(&region.len()) && region.chars().all(|c| c.is_ascii_alphabetic())
+        })
+}
+
+fn extract_option(command: &CommandInteraction, name: &str) -> Option<String> {
+    command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == name)
+        .and_then(|opt| opt.value.as_str())
+        .map(|s| s.trim().to_string())
+}
+
+async fn respond(
+    command: &CommandInteraction,
+    http: &serenity::http::Http,
+    content: &str,
+) -> Result<()> {
+    let response = CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true),
+    );
+    command.create_response(http, response).await?;
+    Ok(())
+}
@@ -0,0 +1,565 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Mutex,
+};
+
+use anyhow::Result;
+use serenity::async_trait;
+
+use super::{
+    FEED_ERROR_HISTORY, FEED_ERROR_MAX_CHARS, FeedStore,
+    models::{ChannelDefaults, Feed, GuildSettings, ImageMode, NewFeed, PostOrder, QuietHours},
+    url_variants,
+};
+use crate::util::parser::normalize_feed_url;
+
+#[derive(Default)]
+struct State {
+    next_id: i64,
+    feeds: Vec<Feed>,
+    settings: HashMap<u64, GuildSettings>,
+    channel_dedup: HashSet<u64>,
+    channel_defaults: HashMap<u64, ChannelDefaults>,
+    posted_links: HashSet<(u64, String)>,
+    articles: HashMap<(i64, String), (String, Option<i64>)>,
+    errors: HashMap<i64, Vec<(String, String)>>,
+}
+
+/// In-memory `FeedStore` for tests, mirroring the matching rules of the SQL
+/// queries in `Database`.
+#[derive(Default)]
+pub struct MockStore {
+    state: Mutex<State>,
+}
+
+impl MockStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn insert(state: &mut State, feed: &NewFeed<'_>) -> bool {
+        let url = normalize_feed_url(feed.url);
+        let conflict = state.feeds.iter().any(|existing| {
+            existing.guild_id == feed.guild_id as i64
+                && existing.channel_id == feed.channel_id as i64
+                && existing.url == url
+        });
+        if conflict {
+            return false;
+        }
+
+        state.next_id += 1;
+        let now = chrono::Utc::now().to_rfc3339();
+        state.feeds.push(Feed {
+            id: state.next_id,
+            guild_id: feed.guild_id as i64,
+            channel_id: feed.channel_id as i64,
+            url,
+            title: feed.title.map(str::to_string),
+            custom_title: feed.custom_title,
+            webhook_url: feed.webhook_url.map(str::to_string),
+            last_posted: now.clone(),
+            last_checked_at: None,
+            last_item_date: None,
+            color: feed.color,
+            spoiler: false,
+            nsfw: false,
+            reactions: Vec::new(),
+            crosspost: false,
+            pin_latest: false,
+            pinned_message_id: None,
+            translate_to: None,
+            created_at: Some(now),
+            added_by: feed.added_by.map(|id| id as i64),
+            mention_role_id: feed.mention_role_id.map(|id| id as i64),
+            poll_hint_minutes: None,
+            items_posted: 0,
+            repost_on_update: false,
+            post_order: PostOrder::default(),
+            max_per_cycle: None,
+            request: feed.request.cloned(),
+            image_mode: feed.image_mode.unwrap_or_default(),
+        });
+        true
+    }
+
+    fn update_where(
+        &self,
+        matches: impl Fn(&Feed) -> bool,
+        apply: impl Fn(&mut Feed),
+    ) -> Result<bool> {
+        let mut state = self.state.lock().unwrap();
+        let mut updated = false;
+        for feed in state.feeds.iter_mut().filter(|feed| matches(feed)) {
+            apply(feed);
+            updated = true;
+        }
+        Ok(updated)
+    }
+
+    fn update_in_guild(&self, guild_id: u64, url: &str, apply: impl Fn(&mut Feed)) -> Result<bool> {
+        let variants = url_variants(url);
+        self.update_where(
+            |feed| feed.guild_id == guild_id as i64 && variants.contains(&feed.url),
+            apply,
+        )
+    }
+
+    fn remove_where(&self, matches: impl Fn(&Feed) -> bool) -> u64 {
+        let mut state = self.state.lock().unwrap();
+        let before = state.feeds.len();
+        state.feeds.retain(|feed| !matches(feed));
+        let ids: HashSet<i64> = state.feeds.iter().map(|feed| feed.id).collect();
+        state
+            .articles
+            .retain(|(feed_id, _), _| ids.contains(feed_id));
+        state.errors.retain(|feed_id, _| ids.contains(feed_id));
+        (before - state.feeds.len()) as u64
+    }
+
+    fn settings_mut(state: &mut State, guild_id: u64) -> &mut GuildSettings {
+        state
+            .settings
+            .entry(guild_id)
+            .or_insert_with(|| GuildSettings {
+                guild_id: guild_id as i64,
+                rss_channel_id: None,
+                quiet_hours: None,
+                footer: None,
+                timezone: None,
+            })
+    }
+}
+
+#[async_trait]
+impl FeedStore for MockStore {
+    async fn add(&self, feed: NewFeed<'_>) -> Result<()> {
+        if Self::insert(&mut self.state.lock().unwrap(), &feed) {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!(
+                "duplicate key value violates unique constraint"
+            ))
+        }
+    }
+
+    async fn add_many(&self, feeds: &[NewFeed<'_>]) -> Result<u64> {
+        let mut state = self.state.lock().unwrap();
+        Ok(feeds
+            .iter()
+            .filter(|feed| Self::insert(&mut state, feed))
+            .count() as u64)
+    }
+
+    async fn remove(&self, guild_id: u64, url: &str) -> Result<bool> {
+        let variants = url_variants(url);
+        Ok(self
+            .remove_where(|feed| feed.guild_id == guild_id as i64 && variants.contains(&feed.url))
+            > 0)
+    }
+
+    async fn remove_from_channel(&self, guild_id: u64, channel_id: u64, url: &str) -> Result<bool> {
+        let variants = url_variants(url);
+        Ok(self.remove_where(|feed| {
+            feed.guild_id == guild_id as i64
+                && feed.channel_id == channel_id as i64
+                && variants.contains(&feed.url)
+        }) > 0)
+    }
+
+    async fn remove_all(&self, guild_id: u64) -> Result<u64> {
+        Ok(self.remove_where(|feed| feed.guild_id == guild_id as i64))
+    }
+
+    async fn remove_by_channel(&self, guild_id: u64, channel_id: u64) -> Result<u64> {
+        Ok(self.remove_where(|feed| {
+            feed.guild_id == guild_id as i64 && feed.channel_id == channel_id as i64
+        }))
+    }
+
+    async fn move_feed(
+        &self,
+        guild_id: u64,
+        channel_id: u64,
+        url: &str,
+        new_channel_id: u64,
+    ) -> Result<bool> {
+        let variants = url_variants(url);
+        self.update_where(
+            |feed| {
+                feed.guild_id == guild_id as i64
+                    && feed.channel_id == channel_id as i64
+                    && variants.contains(&feed.url)
+            },
+            |feed| feed.channel_id = new_channel_id as i64,
+        )
+    }
+
+    async fn update_feed_meta(
+        &self,
+        guild_id: u64,
+        url: &str,
+        title: Option<&str>,
+    ) -> Result<bool> {
+        self.update_in_guild(guild_id, url, |feed| {
+            feed.title = title.map(str::to_string);
+            feed.custom_title = title.is_some();
+        })
+    }
+
+    async fn refresh_title(&self, guild_id: u64, url: &str, title: &str) -> Result<u64> {
+        let variants = url_variants(url);
+        let mut state = self.state.lock().unwrap();
+        let mut updated = 0;
+        for feed in state.feeds.iter_mut().filter(|feed| {
+            feed.guild_id == guild_id as i64 && variants.contains(&feed.url) && !feed.custom_title
+        }) {
+            feed.title = Some(title.to_string());
+            updated += 1;
+        }
+        Ok(updated)
+    }
+
+    async fn set_color(&self, guild_id: u64, url: &str, color: Option<u32>) -> Result<bool> {
+        self.update_in_guild(guild_id, url, |feed| feed.color = color)
+    }
+
+    async fn set_image_mode(&self, guild_id: u64, url: &str, mode: ImageMode) -> Result<bool> {
+        self.update_in_guild(guild_id, url, |feed| feed.image_mode = mode)
+    }
+
+    async fn set_flags(
+        &self,
+        guild_id: u64,
+        url: &str,
+        spoiler: Option<bool>,
+        nsfw: Option<bool>,
+    ) -> Result<bool> {
+        self.update_in_guild(guild_id, url, |feed| {
+            feed.spoiler = spoiler.unwrap_or(feed.spoiler);
+            feed.nsfw = nsfw.unwrap_or(feed.nsfw);
+        })
+    }
+
+    async fn set_reactions(&self, guild_id: u64, url: &str, reactions: &[String]) -> Result<bool> {
+        self.update_in_guild(guild_id, url, |feed| feed.reactions = reactions.to_vec())
+    }
+
+    async fn set_crosspost(&self, guild_id: u64, url: &str, enabled: bool) -> Result<bool> {
+        self.update_in_guild(guild_id, url, |feed| feed.crosspost = enabled)
+    }
+
+    async fn set_pin_latest(&self, guild_id: u64, url: &str, enabled: bool) -> Result<bool> {
+        self.update_in_guild(guild_id, url, |feed| feed.pin_latest = enabled)
+    }
+
+    async fn set_pinned_message(&self, feed_id: i64, message_id: Option<i64>) -> Result<()> {
+        self.update_where(
+            |feed| feed.id == feed_id,
+            |feed| feed.pinned_message_id = message_id,
+        )?;
+        Ok(())
+    }
+
+    async fn set_translate_to(
+        &self,
+        guild_id: u64,
+        url: &str,
+        language: Option<&str>,
+    ) -> Result<bool> {
+        self.update_in_guild(guild_id, url, |feed| {
+            feed.translate_to = language.map(str::to_string)
+        })
+    }
+
+    async fn increment_posted(&self, id: i64, count: u32) -> Result<()> {
+        self.update_where(
+            |feed| feed.id == id,
+            |feed| feed.items_posted += count as i64,
+        )?;
+        Ok(())
+    }
+
+    async fn channel_defaults(&self, channel_id: u64) -> Result<Option<ChannelDefaults>> {
+        Ok(self
+            .state
+            .lock()
+            .unwrap()
+            .channel_defaults
+            .get(&channel_id)
+            .cloned())
+    }
+
+    async fn set_channel_defaults(
+        &self,
+        _guild_id: u64,
+        channel_id: u64,
+        defaults: Option<&ChannelDefaults>,
+    ) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        match defaults.filter(|defaults| !defaults.is_empty()) {
+            Some(defaults) => {
+                state.channel_defaults.insert(channel_id, defaults.clone());
+            }
+            None => {
+                state.channel_defaults.remove(&channel_id);
+            }
+        }
+        Ok(())
+    }
+
+    async fn set_repost_on_update(&self, guild_id: u64, url: &str, enabled: bool) -> Result<bool> {
+        self.update_in_guild(guild_id, url, |feed| feed.repost_on_update = enabled)
+    }
+
+    async fn record_article(
+        &self,
+        feed_id: i64,
+        article_id: &str,
+        content_hash: &str,
+        message_id: Option<i64>,
+    ) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        let entry = state
+            .articles
+            .entry((feed_id, article_id.to_string()))
+            .or_insert_with(|| (String::new(), None));
+        entry.0 = content_hash.to_string();
+        entry.1 = message_id.or(entry.1);
+        Ok(())
+    }
+
+    async fn posted_article(
+        &self,
+        feed_id: i64,
+        article_id: &str,
+    ) -> Result<Option<(String, Option<i64>)>> {
+        Ok(self
+            .state
+            .lock()
+            .unwrap()
+            .articles
+            .get(&(feed_id, article_id.to_string()))
+            .cloned())
+    }
+
+    async fn set_behavior(
+        &self,
+        guild_id: u64,
+        url: &str,
+        order: Option<PostOrder>,
+        max_per_cycle: Option<i32>,
+    ) -> Result<bool> {
+        self.update_in_guild(guild_id, url, |feed| {
+            feed.post_order = order.unwrap_or(feed.post_order);
+            feed.max_per_cycle = max_per_cycle.or(feed.max_per_cycle);
+        })
+    }
+
+    async fn record_error(&self, feed_id: i64, message: &str) -> Result<()> {
+        let message: String = message.chars().take(FEED_ERROR_MAX_CHARS).collect();
+        let mut state = self.state.lock().unwrap();
+        let errors = state.errors.entry(feed_id).or_default();
+        errors.insert(0, (chrono::Utc::now().to_rfc3339(), message));
+        errors.truncate(FEED_ERROR_HISTORY as usize);
+        Ok(())
+    }
+
+    async fn recent_errors(&self, feed_id: i64) -> Result<Vec<(String, String)>> {
+        Ok(self
+            .state
+            .lock()
+            .unwrap()
+            .errors
+            .get(&feed_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn guild(&self, guild_id: u64) -> Result<Vec<Feed>> {
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .feeds
+            .iter()
+            .filter(|feed| feed.guild_id == guild_id as i64)
+            .cloned()
+            .collect())
+    }
+
+    async fn search(&self, guild_id: u64, query: &str, limit: i64) -> Result<Vec<Feed>> {
+        let query = query.to_lowercase();
+        let mut feeds: Vec<Feed> = self
+            .guild(guild_id)
+            .await?
+            .into_iter()
+            .filter(|feed| {
+                feed.url.to_lowercase().contains(&query)
+                    || feed
+                        .title
+                        .as_ref()
+                        .is_some_and(|title| title.to_lowercase().contains(&query))
+            })
+            .collect();
+        feeds.sort_by(|a, b| (&a.title, &a.url).cmp(&(&b.title, &b.url)));
+        feeds.truncate(limit.max(0) as usize);
+        Ok(feeds)
+    }
+
+    async fn feeds(&self) -> Result<Vec<Feed>> {
+        Ok(self.state.lock().unwrap().feeds.clone())
+    }
+
+    async fn find_in_guild(
+        &self,
+        guild_id: u64,
+        url: &str,
+        channel_id: Option<u64>,
+    ) -> Result<Option<Feed>> {
+        let variants = url_variants(url);
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .feeds
+            .iter()
+            .find(|feed| {
+                feed.guild_id == guild_id as i64
+                    && variants.contains(&feed.url)
+                    && channel_id.is_none_or(|id| feed.channel_id == id as i64)
+            })
+            .cloned())
+    }
+
+    async fn update(&self, id: i64, last_item_date: Option<&str>) -> Result<()> {
+        let last_item_date = last_item_date
+            .and_then(|date| chrono::DateTime::parse_from_rfc3339(date).ok())
+            .map(|date| date.with_timezone(&chrono::Utc).to_rfc3339());
+        self.update_where(
+            |feed| feed.id == id,
+            |feed| {
+                feed.last_posted = chrono::Utc::now().to_rfc3339();
+                feed.last_item_date = last_item_date.clone();
+            },
+        )?;
+        Ok(())
+    }
+
+    async fn touch_checked(&self, id: i64, poll_hint_minutes: Option<i32>) -> Result<()> {
+        self.update_where(
+            |feed| feed.id == id,
+            |feed| {
+                feed.last_checked_at = Some(chrono::Utc::now().to_rfc3339());
+                feed.poll_hint_minutes = poll_hint_minutes;
+            },
+        )?;
+        Ok(())
+    }
+
+    async fn update_url(&self, id: i64, new_url: &str) -> Result<bool> {
+        let new_url = normalize_feed_url(new_url);
+        let mut state = self.state.lock().unwrap();
+        let Some(index) = state.feeds.iter().position(|feed| feed.id == id) else {
+            return Ok(false);
+        };
+        let (guild_id, channel_id) = (state.feeds[index].guild_id, state.feeds[index].channel_id);
+        let taken = state.feeds.iter().any(|feed| {
+            feed.guild_id == guild_id && feed.channel_id == channel_id && feed.url == new_url
+        });
+        if taken {
+            return Ok(false);
+        }
+        state.feeds[index].url = new_url;
+        Ok(true)
+    }
+
+    async fn exists(&self, guild_id: u64, url: &str) -> Result<bool> {
+        let variants = url_variants(url);
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .feeds
+            .iter()
+            .any(|feed| feed.guild_id == guild_id as i64 && variants.contains(&feed.url)))
+    }
+
+    async fn duplicate(&self, guild_id: u64, channel_id: u64, url: &str) -> Result<bool> {
+        let variants = url_variants(url);
+        let state = self.state.lock().unwrap();
+        Ok(state.feeds.iter().any(|feed| {
+            feed.guild_id == guild_id as i64
+                && feed.channel_id == channel_id as i64
+                && variants.contains(&feed.url)
+        }))
+    }
+
+    async fn get_settings(&self, guild_id: u64) -> Result<Option<GuildSettings>> {
+        Ok(self.state.lock().unwrap().settings.get(&guild_id).cloned())
+    }
+
+    async fn set_settings(&self, guild_id: u64, rss_channel_id: u64) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        Self::settings_mut(&mut state, guild_id).rss_channel_id = Some(rss_channel_id as i64);
+        Ok(())
+    }
+
+    async fn set_quiet_hours(&self, guild_id: u64, quiet_hours: Option<QuietHours>) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        Self::settings_mut(&mut state, guild_id).quiet_hours = quiet_hours;
+        Ok(())
+    }
+
+    async fn set_footer(&self, guild_id: u64, footer: Option<&str>) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        Self::settings_mut(&mut state, guild_id).footer = footer.map(str::to_string);
+        Ok(())
+    }
+
+    async fn set_timezone(&self, guild_id: u64, timezone: Option<&str>) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        Self::settings_mut(&mut state, guild_id).timezone = timezone.map(str::to_string);
+        Ok(())
+    }
+
+    async fn channel_dedup(&self, channel_id: u64) -> Result<bool> {
+        Ok(self
+            .state
+            .lock()
+            .unwrap()
+            .channel_dedup
+            .contains(&channel_id))
+    }
+
+    async fn set_channel_dedup(
+        &self,
+        _guild_id: u64,
+        channel_id: u64,
+        enabled: bool,
+    ) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        if enabled {
+            state.channel_dedup.insert(channel_id);
+        } else {
+            state.channel_dedup.remove(&channel_id);
+        }
+        Ok(())
+    }
+
+    async fn recently_posted(&self, channel_id: u64, url: &str) -> Result<bool> {
+        Ok(self
+            .state
+            .lock()
+            .unwrap()
+            .posted_links
+            .contains(&(channel_id, url.to_string())))
+    }
+
+    async fn record_posted(&self, channel_id: u64, url: &str) -> Result<()> {
+        self.state
+            .lock()
+            .unwrap()
+            .posted_links
+            .insert((channel_id, url.to_string()));
+        Ok(())
+    }
+
+    async fn ping(&self) -> Result<()> {
+        Ok(())
+    }
+}
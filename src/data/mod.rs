@@ -1,10 +1,248 @@
+#[cfg(test)]
+pub mod mock;
 pub mod models;
 
-use anyhow::Result;
-use deadpool_postgres::Pool;
-use models::Feed;
-use tokio_postgres::{Config, NoTls};
-use tracing::{error, info};
+use anyhow::{Context, Result};
+use deadpool_postgres::{Client, Pool, PoolError};
+use models::{ChannelDefaults, Feed, GuildSettings, ImageMode, NewFeed, PostOrder, QuietHours};
+use serenity::async_trait;
+use tokio_postgres::{Config, NoTls, Row, types::ToSql};
+use tracing::{error, info, warn};
+
+use crate::util::parser::normalize_feed_url;
+
+const FEED_COLUMNS: &str = "id, guild_id, channel_id, url, title, webhook_url, last_updated, last_item_date, color, spoiler, \
+     nsfw, reactions, crosspost, pin_latest, pinned_message_id, translate_to, created_at, \
+     added_by, last_checked_at, mention_role_id, image_mode, poll_hint_minutes, \
+     items_posted, repost_on_update, post_order, max_per_cycle, request_spec, custom_title";
+// Applied in order at startup and tracked in schema_version; only ever append new steps.
+const MIGRATIONS: &[&str] = &[
+    "CREATE TABLE IF NOT EXISTS feeds (
+    id BIGSERIAL PRIMARY KEY,
+    guild_id BIGINT NOT NULL,
+    channel_id BIGINT NOT NULL,
+    url TEXT NOT NULL,
+    title TEXT,
+    webhook_url TEXT,
+    last_updated TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_item_date TIMESTAMPTZ,
+    UNIQUE(guild_id, channel_id, url)
+)",
+    "ALTER TABLE feeds ADD COLUMN IF NOT EXISTS color INTEGER",
+    "ALTER TABLE feeds ADD COLUMN IF NOT EXISTS spoiler BOOLEAN NOT NULL DEFAULT \
+     FALSE, ADD COLUMN IF NOT EXISTS nsfw BOOLEAN NOT NULL DEFAULT FALSE",
+    "ALTER TABLE feeds ADD COLUMN IF NOT EXISTS reactions TEXT",
+    "ALTER TABLE feeds ADD COLUMN IF NOT EXISTS crosspost BOOLEAN NOT NULL DEFAULT FALSE",
+    "ALTER TABLE feeds ADD COLUMN IF NOT EXISTS pin_latest BOOLEAN NOT NULL DEFAULT \
+     FALSE, ADD COLUMN IF NOT EXISTS pinned_message_id BIGINT",
+    "ALTER TABLE feeds ADD COLUMN IF NOT EXISTS translate_to TEXT",
+    "ALTER TABLE feeds ADD COLUMN IF NOT EXISTS created_at TIMESTAMPTZ, ADD COLUMN IF \
+     NOT EXISTS added_by BIGINT",
+    "ALTER TABLE feeds ALTER COLUMN created_at SET DEFAULT NOW()",
+    "ALTER TABLE feeds ADD COLUMN IF NOT EXISTS last_checked_at TIMESTAMPTZ",
+    "ALTER TABLE feeds ADD COLUMN IF NOT EXISTS mention_role_id BIGINT",
+    "ALTER TABLE feeds ADD COLUMN IF NOT EXISTS image_mode TEXT NOT NULL DEFAULT \
+     'large'",
+    "ALTER TABLE feeds ADD COLUMN IF NOT EXISTS poll_hint_minutes INTEGER",
+    "ALTER TABLE feeds ADD COLUMN IF NOT EXISTS items_posted BIGINT NOT NULL DEFAULT 0",
+    "ALTER TABLE feeds ADD COLUMN IF NOT EXISTS repost_on_update BOOLEAN NOT NULL \
+     DEFAULT FALSE",
+    "ALTER TABLE feeds ADD COLUMN IF NOT EXISTS post_order TEXT NOT NULL DEFAULT \
+     'newest', ADD COLUMN IF NOT EXISTS max_per_cycle INTEGER",
+    "ALTER TABLE feeds ADD COLUMN IF NOT EXISTS request_spec TEXT",
+    "CREATE TABLE IF NOT EXISTS guild_settings (
+    guild_id BIGINT PRIMARY KEY,
+    rss_channel_id BIGINT NOT NULL
+)",
+    "ALTER TABLE guild_settings ALTER COLUMN rss_channel_id DROP NOT NULL",
+    "ALTER TABLE guild_settings ADD COLUMN IF NOT EXISTS quiet_start SMALLINT, ADD \
+     COLUMN IF NOT EXISTS quiet_end SMALLINT, ADD COLUMN IF NOT EXISTS quiet_offset \
+     INTEGER",
+    "ALTER TABLE guild_settings ADD COLUMN IF NOT EXISTS footer TEXT, ADD COLUMN IF NOT \
+     EXISTS timezone TEXT",
+    "CREATE TABLE IF NOT EXISTS channel_settings (
+    channel_id BIGINT PRIMARY KEY,
+    guild_id BIGINT NOT NULL,
+    dedup BOOLEAN NOT NULL DEFAULT FALSE
+)",
+    "CREATE TABLE IF NOT EXISTS channel_defaults (
+    channel_id BIGINT PRIMARY KEY,
+    guild_id BIGINT NOT NULL,
+    color INTEGER,
+    mention_role_id BIGINT,
+    image_mode TEXT
+)",
+    "CREATE TABLE IF NOT EXISTS posted_links (
+    channel_id BIGINT NOT NULL,
+    url TEXT NOT NULL,
+    posted_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY(channel_id, url)
+)",
+    "CREATE TABLE IF NOT EXISTS posted_articles (
+    feed_id BIGINT NOT NULL REFERENCES feeds(id) ON DELETE CASCADE,
+    article_id TEXT NOT NULL,
+    content_hash TEXT NOT NULL,
+    message_id BIGINT,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY(feed_id, article_id)
+)",
+    "CREATE INDEX IF NOT EXISTS idx_feeds_guild_id ON feeds(guild_id)",
+    "CREATE INDEX IF NOT EXISTS idx_feeds_url ON feeds(url)",
+    "CREATE INDEX IF NOT EXISTS idx_feeds_guild_channel ON feeds(guild_id, channel_id)",
+    "CREATE TABLE IF NOT EXISTS feed_errors (
+    id BIGSERIAL PRIMARY KEY,
+    feed_id BIGINT NOT NULL REFERENCES feeds(id) ON DELETE CASCADE,
+    occurred_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    message TEXT NOT NULL
+)",
+    "CREATE INDEX IF NOT EXISTS idx_feed_errors_feed ON feed_errors(feed_id, occurred_at DESC)",
+    "ALTER TABLE feeds ADD COLUMN IF NOT EXISTS custom_title BOOLEAN NOT NULL DEFAULT FALSE",
+];
+const FEED_ERROR_HISTORY: i64 = 10;
+const FEED_ERROR_MAX_CHARS: usize = 1000;
+const READ_BACKOFF: [std::time::Duration; 2] = [
+    std::time::Duration::from_millis(200),
+    std::time::Duration::from_millis(1000),
+];
+const UPDATE_URL: &str = "UPDATE feeds f SET url = $1 WHERE f.id = $2 AND NOT EXISTS (SELECT 1 \
+     FROM feeds o WHERE o.guild_id = f.guild_id AND o.channel_id = f.channel_id AND o.url = $1)";
+
+#[async_trait]
+pub trait FeedStore: Send + Sync {
+    async fn add(&self, feed: NewFeed<'_>) -> Result<()>;
+
+    async fn add_many(&self, feeds: &[NewFeed<'_>]) -> Result<u64>;
+
+    async fn remove(&self, guild_id: u64, url: &str) -> Result<bool>;
+
+    async fn remove_from_channel(&self, guild_id: u64, channel_id: u64, url: &str) -> Result<bool>;
+
+    async fn remove_all(&self, guild_id: u64) -> Result<u64>;
+
+    async fn remove_by_channel(&self, guild_id: u64, channel_id: u64) -> Result<u64>;
+
+    async fn move_feed(
+        &self,
+        guild_id: u64,
+        channel_id: u64,
+        url: &str,
+        new_channel_id: u64,
+    ) -> Result<bool>;
+
+    async fn update_feed_meta(&self, guild_id: u64, url: &str, title: Option<&str>)
+    -> Result<bool>;
+
+    async fn refresh_title(&self, guild_id: u64, url: &str, title: &str) -> Result<u64>;
+
+    async fn set_color(&self, guild_id: u64, url: &str, color: Option<u32>) -> Result<bool>;
+
+    async fn set_flags(
+        &self,
+        guild_id: u64,
+        url: &str,
+        spoiler: Option<bool>,
+        nsfw: Option<bool>,
+    ) -> Result<bool>;
+
+    async fn set_reactions(&self, guild_id: u64, url: &str, reactions: &[String]) -> Result<bool>;
+
+    async fn set_crosspost(&self, guild_id: u64, url: &str, enabled: bool) -> Result<bool>;
+
+    async fn set_pin_latest(&self, guild_id: u64, url: &str, enabled: bool) -> Result<bool>;
+
+    async fn set_repost_on_update(&self, guild_id: u64, url: &str, enabled: bool) -> Result<bool>;
+
+    async fn set_image_mode(&self, guild_id: u64, url: &str, mode: ImageMode) -> Result<bool>;
+
+    async fn set_behavior(
+        &self,
+        guild_id: u64,
+        url: &str,
+        order: Option<PostOrder>,
+        max_per_cycle: Option<i32>,
+    ) -> Result<bool>;
+
+    async fn set_pinned_message(&self, feed_id: i64, message_id: Option<i64>) -> Result<()>;
+
+    async fn set_translate_to(
+        &self,
+        guild_id: u64,
+        url: &str,
+        language: Option<&str>,
+    ) -> Result<bool>;
+
+    async fn guild(&self, guild_id: u64) -> Result<Vec<Feed>>;
+
+    async fn search(&self, guild_id: u64, query: &str, limit: i64) -> Result<Vec<Feed>>;
+
+    async fn feeds(&self) -> Result<Vec<Feed>>;
+
+    async fn find_in_guild(
+        &self,
+        guild_id: u64,
+        url: &str,
+        channel_id: Option<u64>,
+    ) -> Result<Option<Feed>>;
+
+    async fn update(&self, id: i64, last_item_date: Option<&str>) -> Result<()>;
+
+    async fn touch_checked(&self, id: i64, poll_hint_minutes: Option<i32>) -> Result<()>;
+
+    async fn increment_posted(&self, id: i64, count: u32) -> Result<()>;
+
+    async fn update_url(&self, id: i64, new_url: &str) -> Result<bool>;
+
+    async fn exists(&self, guild_id: u64, url: &str) -> Result<bool>;
+
+    async fn duplicate(&self, guild_id: u64, channel_id: u64, url: &str) -> Result<bool>;
+
+    async fn get_settings(&self, guild_id: u64) -> Result<Option<GuildSettings>>;
+
+    async fn set_settings(&self, guild_id: u64, rss_channel_id: u64) -> Result<()>;
+
+    async fn set_quiet_hours(&self, guild_id: u64, quiet_hours: Option<QuietHours>) -> Result<()>;
+
+    async fn set_footer(&self, guild_id: u64, footer: Option<&str>) -> Result<()>;
+
+    async fn set_timezone(&self, guild_id: u64, timezone: Option<&str>) -> Result<()>;
+
+    async fn channel_dedup(&self, channel_id: u64) -> Result<bool>;
+
+    async fn set_channel_dedup(&self, guild_id: u64, channel_id: u64, enabled: bool) -> Result<()>;
+
+    async fn channel_defaults(&self, channel_id: u64) -> Result<Option<ChannelDefaults>>;
+
+    async fn set_channel_defaults(
+        &self,
+        guild_id: u64,
+        channel_id: u64,
+        defaults: Option<&ChannelDefaults>,
+    ) -> Result<()>;
+
+    async fn recently_posted(&self, channel_id: u64, url: &str) -> Result<bool>;
+
+    async fn record_posted(&self, channel_id: u64, url: &str) -> Result<()>;
+
+    async fn posted_article(
+        &self,
+        feed_id: i64,
+        article_id: &str,
+    ) -> Result<Option<(String, Option<i64>)>>;
+
+    async fn record_article(
+        &self,
+        feed_id: i64,
+        article_id: &str,
+        content_hash: &str,
+        message_id: Option<i64>,
+    ) -> Result<()>;
+
+    async fn record_error(&self, feed_id: i64, message: &str) -> Result<()>;
+
+    async fn recent_errors(&self, feed_id: i64) -> Result<Vec<(String, String)>>;
+
+    async fn ping(&self) -> Result<()>;
+}
 
 pub struct Database {
     pool: Pool,
@@ -18,183 +256,495 @@ impl Database {
         };
         let mgr = deadpool_postgres::Manager::from_config(config, NoTls, mgr_config);
         let pool = Pool::builder(mgr).build()?;
-        let client = pool.get().await?;
+        let mut client = pool.get().await?;
+        migrate(&mut client).await?;
 
-        client
-            .execute(
-                "CREATE TABLE IF NOT EXISTS feeds (
-                id BIGSERIAL PRIMARY KEY,
-                guild_id BIGINT NOT NULL,
-                channel_id BIGINT NOT NULL,
-                url TEXT NOT NULL,
-                title TEXT,
-                webhook_url TEXT,
-                last_updated TIMESTAMPTZ NOT NULL DEFAULT NOW(),
-                last_item_date TIMESTAMPTZ,
-                UNIQUE(guild_id, channel_id, url)
+        for row in client.query("SELECT id, url FROM feeds", &[]).await? {
+            let id: i64 = row.get(0);
+            let url: String = row.get(1);
+            let normalized = normalize_feed_url(&url);
+            if normalized != url {
+                client.execute(UPDATE_URL, &[&normalized, &id]).await?;
+            }
+        }
+
+        info!("Database initialized successfully");
+        Ok(Self { pool })
+    }
+
+    async fn read(&self, statement: &str, params: &[&(dyn ToSql + Sync)]) -> Result<Vec<Row>> {
+        let mut attempt = 0;
+        loop {
+            let result = match self.pool.get().await {
+                Ok(client) => client.query(statement, params).await.map_err(Into::into),
+                Err(e) => Err(anyhow::Error::from(e)),
+            };
+
+            match result {
+                Ok(rows) => return Ok(rows),
+                Err(e) if retryable(&e) && attempt < READ_BACKOFF.len() => {
+                    warn!(
+                        "Database read failed (attempt {}), retrying in {}ms: {}",
+                        attempt + 1,
+                        READ_BACKOFF[attempt].as_millis(),
+                        e
+                    );
+                    tokio::time::sleep(READ_BACKOFF[attempt]).await;
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+async fn migrate(client: &mut Client) -> Result<()> {
+    client
+        .execute(
+            "CREATE TABLE IF NOT EXISTS schema_version (
+                version INTEGER PRIMARY KEY,
+                applied_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
             )",
-                &[],
+            &[],
+        )
+        .await?;
+
+    let current: i32 = client
+        .query_one("SELECT COALESCE(MAX(version), 0) FROM schema_version", &[])
+        .await?
+        .get(0);
+    let latest = MIGRATIONS.len() as i32;
+
+    if current > latest {
+        warn!(
+            "Database schema version {} is newer than this build ({}); skipping migrations",
+            current, latest
+        );
+        return Ok(());
+    }
+
+    for (index, statement) in MIGRATIONS.iter().enumerate().skip(current as usize) {
+        let version = index as i32 + 1;
+        let transaction = client.transaction().await?;
+        transaction
+            .batch_execute(statement)
+            .await
+            .with_context(|| format!("Migration {} failed", version))?;
+        transaction
+            .execute(
+                "INSERT INTO schema_version (version) VALUES ($1)",
+                &[&version],
             )
             .await?;
+        transaction.commit().await?;
+    }
+
+    if current < latest {
+        info!(
+            "Applied {} database migrations (schema version {} -> {})",
+            latest - current,
+            current,
+            latest
+        );
+    }
+
+    Ok(())
+}
 
+fn retryable(error: &anyhow::Error) -> bool {
+    if let Some(error) = error.downcast_ref::<PoolError>() {
+        return matches!(error, PoolError::Timeout(_) | PoolError::Backend(_));
+    }
+
+    error
+        .downcast_ref::<tokio_postgres::Error>()
+        .is_some_and(|error| {
+            error.is_closed()
+                || std::error::Error::source(error)
+                    .is_some_and(|source| source.is::<std::io::Error>())
+        })
+}
+
+#[async_trait]
+impl FeedStore for Database {
+    async fn add(&self, feed: NewFeed<'_>) -> Result<()> {
+        let client = self.pool.get().await?;
         client
             .execute(
-                "CREATE INDEX IF NOT EXISTS idx_feeds_guild_id ON feeds(guild_id)",
-                &[],
+                "INSERT INTO feeds (guild_id, channel_id, url, title, webhook_url, added_by, \
+                 color, mention_role_id, image_mode, request_spec, custom_title) VALUES ($1, $2, \
+                 $3, $4, $5, $6, $7, $8, $9, $10, $11)",
+                &[
+                    &(feed.guild_id as i64),
+                    &(feed.channel_id as i64),
+                    &normalize_feed_url(feed.url),
+                    &feed.title,
+                    &feed.webhook_url,
+                    &feed.added_by.map(|id| id as i64),
+                    &feed.color.map(|c| c as i32),
+                    &feed.mention_role_id.map(|id| id as i64),
+                    &feed.image_mode.unwrap_or_default().as_str(),
+                    &feed.request.map(serde_json::to_string).transpose()?,
+                    &feed.custom_title,
+                ],
             )
             .await?;
+        Ok(())
+    }
 
-        client
+    async fn add_many(&self, feeds: &[NewFeed<'_>]) -> Result<u64> {
+        let mut client = self.pool.get().await?;
+        let transaction = client.transaction().await?;
+        let statement = transaction
+            .prepare(
+                "INSERT INTO feeds (guild_id, channel_id, url, title, webhook_url, added_by, \
+                 color, mention_role_id, image_mode, request_spec, custom_title) VALUES ($1, $2, \
+                 $3, $4, $5, $6, $7, $8, $9, $10, $11) \
+                 ON CONFLICT (guild_id, channel_id, url) DO NOTHING",
+            )
+            .await?;
+
+        let mut inserted = 0;
+        for feed in feeds {
+            inserted += transaction
+                .execute(
+                    &statement,
+                    &[
+                        &(feed.guild_id as i64),
+                        &(feed.channel_id as i64),
+                        &normalize_feed_url(feed.url),
+                        &feed.title,
+                        &feed.webhook_url,
+                        &feed.added_by.map(|id| id as i64),
+                        &feed.color.map(|c| c as i32),
+                        &feed.mention_role_id.map(|id| id as i64),
+                        &feed.image_mode.unwrap_or_default().as_str(),
+                        &feed.request.map(serde_json::to_string).transpose()?,
+                        &feed.custom_title,
+                    ],
+                )
+                .await?;
+        }
+
+        transaction.commit().await?;
+        Ok(inserted)
+    }
+
+    async fn remove(&self, guild_id: u64, url: &str) -> Result<bool> {
+        let client = self.pool.get().await?;
+        let result = client
             .execute(
-                "CREATE INDEX IF NOT EXISTS idx_feeds_url ON feeds(url)",
-                &[],
+                "DELETE FROM feeds WHERE guild_id = $1 AND url = ANY($2)",
+                &[&(guild_id as i64), &url_variants(url)],
             )
             .await?;
+        Ok(result > 0)
+    }
 
-        client
+    async fn remove_from_channel(&self, guild_id: u64, channel_id: u64, url: &str) -> Result<bool> {
+        let client = self.pool.get().await?;
+        let result = client
             .execute(
-                "CREATE INDEX IF NOT EXISTS idx_feeds_guild_channel ON feeds(guild_id, channel_id)",
-                &[],
+                "DELETE FROM feeds WHERE guild_id = $1 AND channel_id = $2 AND url = ANY($3)",
+                &[&(guild_id as i64), &(channel_id as i64), &url_variants(url)],
             )
             .await?;
+        Ok(result > 0)
+    }
 
-        info!("Database initialized successfully");
-        Ok(Self { pool })
+    async fn remove_all(&self, guild_id: u64) -> Result<u64> {
+        let client = self.pool.get().await?;
+        let result = client
+            .execute(
+                "DELETE FROM feeds WHERE guild_id = $1",
+                &[&(guild_id as i64)],
+            )
+            .await?;
+        Ok(result)
     }
 
-    pub async fn add(
+    async fn remove_by_channel(&self, guild_id: u64, channel_id: u64) -> Result<u64> {
+        let client = self.pool.get().await?;
+        let result = client
+            .execute(
+                "DELETE FROM feeds WHERE guild_id = $1 AND channel_id = $2",
+                &[&(guild_id as i64), &(channel_id as i64)],
+            )
+            .await?;
+        Ok(result)
+    }
+
+    async fn move_feed(
         &self,
         guild_id: u64,
         channel_id: u64,
         url: &str,
-        title: Option<&str>,
-        webhook_url: Option<&str>,
-    ) -> Result<()> {
+        new_channel_id: u64,
+    ) -> Result<bool> {
         let client = self.pool.get().await?;
-        client
+        let result = client
             .execute(
-                "INSERT INTO feeds (guild_id, channel_id, url, title, webhook_url) VALUES ($1, \
-                 $2, $3, $4, $5)",
+                "UPDATE feeds SET channel_id = $1 WHERE guild_id = $2 AND channel_id = $3 AND url \
+                 = ANY($4)",
                 &[
+                    &(new_channel_id as i64),
                     &(guild_id as i64),
                     &(channel_id as i64),
-                    &url,
+                    &url_variants(url),
+                ],
+            )
+            .await?;
+        Ok(result > 0)
+    }
+
+    async fn update_feed_meta(
+        &self,
+        guild_id: u64,
+        url: &str,
+        title: Option<&str>,
+    ) -> Result<bool> {
+        let client = self.pool.get().await?;
+        let result = client
+            .execute(
+                "UPDATE feeds SET title = $1, custom_title = $2 WHERE guild_id = $3 AND url = \
+                 ANY($4)",
+                &[
                     &title,
-                    &webhook_url,
+                    &title.is_some(),
+                    &(guild_id as i64),
+                    &url_variants(url),
                 ],
             )
             .await?;
-        Ok(())
+        Ok(result > 0)
     }
 
-    pub async fn remove(&self, guild_id: u64, url: &str) -> Result<bool> {
+    async fn refresh_title(&self, guild_id: u64, url: &str, title: &str) -> Result<u64> {
         let client = self.pool.get().await?;
         let result = client
             .execute(
-                "DELETE FROM feeds WHERE guild_id = $1 AND url = $2",
-                &[&(guild_id as i64), &url],
+                "UPDATE feeds SET title = $1 WHERE guild_id = $2 AND url = ANY($3) AND NOT \
+                 custom_title",
+                &[&title, &(guild_id as i64), &url_variants(url)],
+            )
+            .await?;
+        Ok(result)
+    }
+
+    async fn set_color(&self, guild_id: u64, url: &str, color: Option<u32>) -> Result<bool> {
+        let client = self.pool.get().await?;
+        let color = color.map(|c| c as i32);
+        let result = client
+            .execute(
+                "UPDATE feeds SET color = $1 WHERE guild_id = $2 AND url = ANY($3)",
+                &[&color, &(guild_id as i64), &url_variants(url)],
+            )
+            .await?;
+        Ok(result > 0)
+    }
+
+    async fn set_flags(
+        &self,
+        guild_id: u64,
+        url: &str,
+        spoiler: Option<bool>,
+        nsfw: Option<bool>,
+    ) -> Result<bool> {
+        let client = self.pool.get().await?;
+        let result = client
+            .execute(
+                "UPDATE feeds SET spoiler = COALESCE($1, spoiler), nsfw = COALESCE($2, nsfw) WHERE \
+                 guild_id = $3 AND url = ANY($4)",
+                &[&spoiler, &nsfw, &(guild_id as i64), &url_variants(url)],
+            )
+            .await?;
+        Ok(result > 0)
+    }
+
+    async fn set_reactions(&self, guild_id: u64, url: &str, reactions: &[String]) -> Result<bool> {
+        let client = self.pool.get().await?;
+        let reactions = (!reactions.is_empty()).then(|| reactions.join(","));
+        let result = client
+            .execute(
+                "UPDATE feeds SET reactions = $1 WHERE guild_id = $2 AND url = ANY($3)",
+                &[&reactions, &(guild_id as i64), &url_variants(url)],
+            )
+            .await?;
+        Ok(result > 0)
+    }
+
+    async fn set_crosspost(&self, guild_id: u64, url: &str, enabled: bool) -> Result<bool> {
+        let client = self.pool.get().await?;
+        let result = client
+            .execute(
+                "UPDATE feeds SET crosspost = $1 WHERE guild_id = $2 AND url = ANY($3)",
+                &[&enabled, &(guild_id as i64), &url_variants(url)],
             )
             .await?;
         Ok(result > 0)
     }
 
-    pub async fn guild(&self, guild_id: u64) -> Result<Vec<Feed>> {
+    async fn set_image_mode(&self, guild_id: u64, url: &str, mode: ImageMode) -> Result<bool> {
         let client = self.pool.get().await?;
-        let rows = client
-            .query(
-                "SELECT id, guild_id, channel_id, url, title, webhook_url, last_updated, \
-                 last_item_date 
-             FROM feeds WHERE guild_id = $1 ORDER BY id",
+        let result = client
+            .execute(
+                "UPDATE feeds SET image_mode = $1 WHERE guild_id = $2 AND url = ANY($3)",
+                &[&mode.as_str(), &(guild_id as i64), &url_variants(url)],
+            )
+            .await?;
+        Ok(result > 0)
+    }
+
+    async fn set_behavior(
+        &self,
+        guild_id: u64,
+        url: &str,
+        order: Option<PostOrder>,
+        max_per_cycle: Option<i32>,
+    ) -> Result<bool> {
+        let client = self.pool.get().await?;
+        let result = client
+            .execute(
+                "UPDATE feeds SET post_order = COALESCE($1, post_order), max_per_cycle = \
+                 COALESCE($2, max_per_cycle) WHERE guild_id = $3 AND url = ANY($4)",
+                &[
+                    &order.map(PostOrder::as_str),
+                    &max_per_cycle,
+                    &(guild_id as i64),
+                    &url_variants(url),
+                ],
+            )
+            .await?;
+        Ok(result > 0)
+    }
+
+    async fn set_pin_latest(&self, guild_id: u64, url: &str, enabled: bool) -> Result<bool> {
+        let client = self.pool.get().await?;
+        let result = client
+            .execute(
+                "UPDATE feeds SET pin_latest = $1 WHERE guild_id = $2 AND url = ANY($3)",
+                &[&enabled, &(guild_id as i64), &url_variants(url)],
+            )
+            .await?;
+        Ok(result > 0)
+    }
+
+    async fn set_repost_on_update(&self, guild_id: u64, url: &str, enabled: bool) -> Result<bool> {
+        let client = self.pool.get().await?;
+        let result = client
+            .execute(
+                "UPDATE feeds SET repost_on_update = $1 WHERE guild_id = $2 AND url = ANY($3)",
+                &[&enabled, &(guild_id as i64), &url_variants(url)],
+            )
+            .await?;
+        Ok(result > 0)
+    }
+
+    async fn set_pinned_message(&self, feed_id: i64, message_id: Option<i64>) -> Result<()> {
+        let client = self.pool.get().await?;
+        client
+            .execute(
+                "UPDATE feeds SET pinned_message_id = $1 WHERE id = $2",
+                &[&message_id, &feed_id],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn set_translate_to(
+        &self,
+        guild_id: u64,
+        url: &str,
+        language: Option<&str>,
+    ) -> Result<bool> {
+        let client = self.pool.get().await?;
+        let result = client
+            .execute(
+                "UPDATE feeds SET translate_to = $1 WHERE guild_id = $2 AND url = ANY($3)",
+                &[&language, &(guild_id as i64), &url_variants(url)],
+            )
+            .await?;
+        Ok(result > 0)
+    }
+
+    async fn guild(&self, guild_id: u64) -> Result<Vec<Feed>> {
+        let rows = self
+            .read(
+                format!(
+                    "SELECT {} FROM feeds WHERE guild_id = $1 ORDER BY id",
+                    FEED_COLUMNS
+                )
+                .as_str(),
                 &[&(guild_id as i64)],
             )
             .await?;
 
-        let feeds = rows
-            .into_iter()
-            .map(|row| {
-                let last_updated: chrono::DateTime<chrono::Utc> = row.get(6);
-                let last_item_date: Option<chrono::DateTime<chrono::Utc>> = row.get(7);
-
-                Feed {
-                    id: row.get(0),
-                    guild_id: row.get(1),
-                    channel_id: row.get(2),
-                    url: row.get(3),
-                    title: row.get(4),
-                    webhook_url: row.get(5),
-                    last_updated: last_updated.to_rfc3339(),
-                    last_item_date: last_item_date.map(|dt| dt.to_rfc3339()),
-                }
-            })
-            .collect();
+        let feeds = rows.into_iter().map(feed_from_row).collect();
 
         Ok(feeds)
     }
 
-    pub async fn feeds(&self) -> Result<Vec<Feed>> {
-        let client = self.pool.get().await?;
-        let rows = client
-            .query(
-                "SELECT id, guild_id, channel_id, url, title, webhook_url, last_updated, \
-                 last_item_date 
-             FROM feeds ORDER BY id",
+    async fn search(&self, guild_id: u64, query: &str, limit: i64) -> Result<Vec<Feed>> {
+        let pattern = format!(
+            "%{}%",
+            query
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_")
+        );
+        let rows = self
+            .read(
+                format!(
+                    "SELECT {} FROM feeds WHERE guild_id = $1 AND (url ILIKE $2 OR title ILIKE $2) \
+                     ORDER BY title, url LIMIT $3",
+                    FEED_COLUMNS
+                )
+                .as_str(),
+                &[&(guild_id as i64), &pattern, &limit],
+            )
+            .await?;
+
+        Ok(rows.into_iter().map(feed_from_row).collect())
+    }
+
+    async fn feeds(&self) -> Result<Vec<Feed>> {
+        let rows = self
+            .read(
+                format!("SELECT {} FROM feeds ORDER BY id", FEED_COLUMNS).as_str(),
                 &[],
             )
             .await?;
 
-        let feeds = rows
-            .into_iter()
-            .map(|row| {
-                let last_updated: chrono::DateTime<chrono::Utc> = row.get(6);
-                let last_item_date: Option<chrono::DateTime<chrono::Utc>> = row.get(7);
-
-                Feed {
-                    id: row.get(0),
-                    guild_id: row.get(1),
-                    channel_id: row.get(2),
-                    url: row.get(3),
-                    title: row.get(4),
-                    webhook_url: row.get(5),
-                    last_updated: last_updated.to_rfc3339(),
-                    last_item_date: last_item_date.map(|dt| dt.to_rfc3339()),
-                }
-            })
-            .collect();
+        let feeds = rows.into_iter().map(feed_from_row).collect();
 
         Ok(feeds)
     }
 
-    pub async fn find(&self, url: &str) -> Result<Option<Feed>> {
-        let client = self.pool.get().await?;
-        let rows = client
-            .query(
-                "SELECT id, guild_id, channel_id, url, title, webhook_url, last_updated, \
-                 last_item_date 
-             FROM feeds WHERE url = $1 LIMIT 1",
-                &[&url],
+    async fn find_in_guild(
+        &self,
+        guild_id: u64,
+        url: &str,
+        channel_id: Option<u64>,
+    ) -> Result<Option<Feed>> {
+        let rows = self
+            .read(
+                format!(
+                    "SELECT {} FROM feeds WHERE guild_id = $1 AND url = ANY($2) AND ($3::BIGINT IS NULL OR channel_id = $3) ORDER BY id LIMIT 1",
+                    FEED_COLUMNS
+                )
+                .as_str(),
+                &[
+                    &(guild_id as i64),
+                    &url_variants(url),
+                    &channel_id.map(|id| id as i64),
+                ],
             )
             .await?;
 
-        if let Some(row) = rows.first() {
-            let last_updated: chrono::DateTime<chrono::Utc> = row.get(6);
-            let last_item_date: Option<chrono::DateTime<chrono::Utc>> = row.get(7);
-
-            Ok(Some(Feed {
-                id: row.get(0),
-                guild_id: row.get(1),
-                channel_id: row.get(2),
-                url: row.get(3),
-                title: row.get(4),
-                webhook_url: row.get(5),
-                last_updated: last_updated.to_rfc3339(),
-                last_item_date: last_item_date.map(|dt| dt.to_rfc3339()),
-            }))
-        } else {
-            Ok(None)
-        }
+        Ok(rows.into_iter().next().map(feed_from_row))
     }
 
-    pub async fn update(&self, id: i64, last_item_date: Option<&str>) -> Result<()> {
+    async fn update(&self, id: i64, last_item_date: Option<&str>) -> Result<()> {
         let client = self.pool.get().await?;
 
         let last_item_dt = if let Some(date_str) = last_item_date {
@@ -218,12 +768,54 @@ impl Database {
         Ok(())
     }
 
-    pub async fn exists(&self, guild_id: u64, url: &str) -> Result<bool> {
+    async fn touch_checked(&self, id: i64, poll_hint_minutes: Option<i32>) -> Result<()> {
+        let client = self.pool.get().await?;
+        client
+            .execute(
+                "UPDATE feeds SET last_checked_at = NOW(), poll_hint_minutes = $2 WHERE id = $1",
+                &[&id, &poll_hint_minutes],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn increment_posted(&self, id: i64, count: u32) -> Result<()> {
         let client = self.pool.get().await?;
-        let rows = client
-            .query(
-                "SELECT COUNT(*) FROM feeds WHERE guild_id = $1 AND url = $2",
-                &[&(guild_id as i64), &url],
+        client
+            .execute(
+                "UPDATE feeds SET items_posted = items_posted + $1 WHERE id = $2",
+                &[&(count as i64), &id],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn update_url(&self, id: i64, new_url: &str) -> Result<bool> {
+        let client = self.pool.get().await?;
+        let result = client
+            .execute(UPDATE_URL, &[&normalize_feed_url(new_url), &id])
+            .await?;
+        Ok(result > 0)
+    }
+
+    async fn exists(&self, guild_id: u64, url: &str) -> Result<bool> {
+        let rows = self
+            .read(
+                "SELECT COUNT(*) FROM feeds WHERE guild_id = $1 AND url = ANY($2)",
+                &[&(guild_id as i64), &url_variants(url)],
+            )
+            .await?;
+
+        let count: i64 = rows[0].get(0);
+        Ok(count > 0)
+    }
+
+    async fn duplicate(&self, guild_id: u64, channel_id: u64, url: &str) -> Result<bool> {
+        let rows = self
+            .read(
+                "SELECT COUNT(*) FROM feeds WHERE guild_id = $1 AND channel_id = $2 AND url = \
+                 ANY($3)",
+                &[&(guild_id as i64), &(channel_id as i64), &url_variants(url)],
             )
             .await?;
 
@@ -231,16 +823,339 @@ impl Database {
         Ok(count > 0)
     }
 
-    pub async fn duplicate(&self, guild_id: u64, channel_id: u64, url: &str) -> Result<bool> {
+    async fn get_settings(&self, guild_id: u64) -> Result<Option<GuildSettings>> {
+        let rows = self
+            .read(
+                "SELECT guild_id, rss_channel_id, quiet_start, quiet_end, quiet_offset, footer, \
+                 timezone FROM guild_settings WHERE guild_id = $1",
+                &[&(guild_id as i64)],
+            )
+            .await?;
+
+        Ok(rows.first().map(|row| {
+            let quiet_start: Option<i16> = row.get(2);
+            let quiet_end: Option<i16> = row.get(3);
+            let quiet_offset: Option<i32> = row.get(4);
+
+            GuildSettings {
+                guild_id: row.get(0),
+                rss_channel_id: row.get(1),
+                quiet_hours: match (quiet_start, quiet_end) {
+                    (Some(start), Some(end)) => Some(QuietHours {
+                        start_hour: start as u8,
+                        end_hour: end as u8,
+                        utc_offset_minutes: quiet_offset.unwrap_or(0),
+                    }),
+                    _ => None,
+                },
+                footer: row.get(5),
+                timezone: row.get(6),
+            }
+        }))
+    }
+
+    async fn set_settings(&self, guild_id: u64, rss_channel_id: u64) -> Result<()> {
         let client = self.pool.get().await?;
-        let rows = client
-            .query(
-                "SELECT COUNT(*) FROM feeds WHERE guild_id = $1 AND channel_id = $2 AND url = $3",
-                &[&(guild_id as i64), &(channel_id as i64), &url],
+        client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, rss_channel_id) VALUES ($1, $2) ON \
+                 CONFLICT (guild_id) DO UPDATE SET rss_channel_id = EXCLUDED.rss_channel_id",
+                &[&(guild_id as i64), &(rss_channel_id as i64)],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn set_quiet_hours(&self, guild_id: u64, quiet_hours: Option<QuietHours>) -> Result<()> {
+        let client = self.pool.get().await?;
+        let start = quiet_hours.map(|q| q.start_hour as i16);
+        let end = quiet_hours.map(|q| q.end_hour as i16);
+        let offset = quiet_hours.map(|q| q.utc_offset_minutes);
+
+        client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, quiet_start, quiet_end, quiet_offset) VALUES \
+                 ($1, $2, $3, $4) ON CONFLICT (guild_id) DO UPDATE SET quiet_start = \
+                 EXCLUDED.quiet_start, quiet_end = EXCLUDED.quiet_end, quiet_offset = \
+                 EXCLUDED.quiet_offset",
+                &[&(guild_id as i64), &start, &end, &offset],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn set_footer(&self, guild_id: u64, footer: Option<&str>) -> Result<()> {
+        let client = self.pool.get().await?;
+        client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, footer) VALUES ($1, $2) ON CONFLICT \
+                 (guild_id) DO UPDATE SET footer = EXCLUDED.footer",
+                &[&(guild_id as i64), &footer],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn set_timezone(&self, guild_id: u64, timezone: Option<&str>) -> Result<()> {
+        let client = self.pool.get().await?;
+        client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, timezone) VALUES ($1, $2) ON CONFLICT \
+                 (guild_id) DO UPDATE SET timezone = EXCLUDED.timezone",
+                &[&(guild_id as i64), &timezone],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn channel_dedup(&self, channel_id: u64) -> Result<bool> {
+        let rows = self
+            .read(
+                "SELECT dedup FROM channel_settings WHERE channel_id = $1",
+                &[&(channel_id as i64)],
+            )
+            .await?;
+
+        Ok(rows.first().map(|row| row.get(0)).unwrap_or(false))
+    }
+
+    async fn set_channel_dedup(&self, guild_id: u64, channel_id: u64, enabled: bool) -> Result<()> {
+        let client = self.pool.get().await?;
+        client
+            .execute(
+                "INSERT INTO channel_settings (channel_id, guild_id, dedup) VALUES ($1, $2, $3) \
+                 ON CONFLICT (channel_id) DO UPDATE SET dedup = EXCLUDED.dedup",
+                &[&(channel_id as i64), &(guild_id as i64), &enabled],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn channel_defaults(&self, channel_id: u64) -> Result<Option<ChannelDefaults>> {
+        let rows = self
+            .read(
+                "SELECT color, mention_role_id, image_mode FROM channel_defaults WHERE \
+                 channel_id = $1",
+                &[&(channel_id as i64)],
+            )
+            .await?;
+
+        Ok(rows.first().map(|row| {
+            let color: Option<i32> = row.get(0);
+            let image_mode: Option<String> = row.get(2);
+            ChannelDefaults {
+                color: color.map(|c| c as u32),
+                mention_role_id: row.get(1),
+                image_mode: image_mode.as_deref().and_then(ImageMode::parse),
+            }
+        }))
+    }
+
+    async fn set_channel_defaults(
+        &self,
+        guild_id: u64,
+        channel_id: u64,
+        defaults: Option<&ChannelDefaults>,
+    ) -> Result<()> {
+        let client = self.pool.get().await?;
+        match defaults.filter(|defaults| !defaults.is_empty()) {
+            Some(defaults) => {
+                client
+                    .execute(
+                        "INSERT INTO channel_defaults (channel_id, guild_id, color, \
+                         mention_role_id, image_mode) VALUES ($1, $2, $3, $4, $5) ON CONFLICT \
+                         (channel_id) DO UPDATE SET color = EXCLUDED.color, mention_role_id = \
+                         EXCLUDED.mention_role_id, image_mode = EXCLUDED.image_mode",
+                        &[
+                            &(channel_id as i64),
+                            &(guild_id as i64),
+                            &defaults.color.map(|c| c as i32),
+                            &defaults.mention_role_id,
+                            &defaults.image_mode.map(ImageMode::as_str),
+                        ],
+                    )
+                    .await?;
+            }
+            None => {
+                client
+                    .execute(
+                        "DELETE FROM channel_defaults WHERE channel_id = $1",
+                        &[&(channel_id as i64)],
+                    )
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn recently_posted(&self, channel_id: u64, url: &str) -> Result<bool> {
+        let rows = self
+            .read(
+                "SELECT COUNT(*) FROM posted_links WHERE channel_id = $1 AND url = $2 AND \
+                 posted_at > NOW() - INTERVAL '7 days'",
+                &[&(channel_id as i64), &url],
             )
             .await?;
 
         let count: i64 = rows[0].get(0);
         Ok(count > 0)
     }
+
+    async fn record_posted(&self, channel_id: u64, url: &str) -> Result<()> {
+        let client = self.pool.get().await?;
+        client
+            .execute(
+                "INSERT INTO posted_links (channel_id, url) VALUES ($1, $2) ON CONFLICT \
+                 (channel_id, url) DO UPDATE SET posted_at = NOW()",
+                &[&(channel_id as i64), &url],
+            )
+            .await?;
+        client
+            .execute(
+                "DELETE FROM posted_links WHERE channel_id = $1 AND posted_at < NOW() - \
+                 INTERVAL '7 days'",
+                &[&(channel_id as i64)],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn posted_article(
+        &self,
+        feed_id: i64,
+        article_id: &str,
+    ) -> Result<Option<(String, Option<i64>)>> {
+        let rows = self
+            .read(
+                "SELECT content_hash, message_id FROM posted_articles WHERE feed_id = $1 AND \
+                 article_id = $2",
+                &[&feed_id, &article_id],
+            )
+            .await?;
+
+        Ok(rows.first().map(|row| (row.get(0), row.get(1))))
+    }
+
+    async fn record_article(
+        &self,
+        feed_id: i64,
+        article_id: &str,
+        content_hash: &str,
+        message_id: Option<i64>,
+    ) -> Result<()> {
+        let client = self.pool.get().await?;
+        client
+            .execute(
+                "INSERT INTO posted_articles (feed_id, article_id, content_hash, message_id) \
+                 VALUES ($1, $2, $3, $4) ON CONFLICT (feed_id, article_id) DO UPDATE SET \
+                 content_hash = $3, message_id = COALESCE($4, posted_articles.message_id), \
+                 updated_at = NOW()",
+                &[&feed_id, &article_id, &content_hash, &message_id],
+            )
+            .await?;
+        client
+            .execute(
+                "DELETE FROM posted_articles WHERE feed_id = $1 AND updated_at < NOW() - \
+                 INTERVAL '30 days'",
+                &[&feed_id],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn record_error(&self, feed_id: i64, message: &str) -> Result<()> {
+        let message: String = message.chars().take(FEED_ERROR_MAX_CHARS).collect();
+        let client = self.pool.get().await?;
+        client
+            .execute(
+                "INSERT INTO feed_errors (feed_id, message) VALUES ($1, $2)",
+                &[&feed_id, &message],
+            )
+            .await?;
+        client
+            .execute(
+                "DELETE FROM feed_errors WHERE feed_id = $1 AND id NOT IN (SELECT id FROM \
+                 feed_errors WHERE feed_id = $1 ORDER BY occurred_at DESC, id DESC LIMIT $2)",
+                &[&feed_id, &FEED_ERROR_HISTORY],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn recent_errors(&self, feed_id: i64) -> Result<Vec<(String, String)>> {
+        let rows = self
+            .read(
+                "SELECT occurred_at, message FROM feed_errors WHERE feed_id = $1 ORDER BY \
+                 occurred_at DESC, id DESC LIMIT $2",
+                &[&feed_id, &FEED_ERROR_HISTORY],
+            )
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let occurred_at: chrono::DateTime<chrono::Utc> = row.get(0);
+                (occurred_at.to_rfc3339(), row.get(1))
+            })
+            .collect())
+    }
+
+    async fn ping(&self) -> Result<()> {
+        self.read("SELECT 1", &[]).await?;
+        Ok(())
+    }
+}
+
+pub fn url_variants(url: &str) -> Vec<String> {
+    let normalized = normalize_feed_url(url);
+    let mut variants = vec![url.to_string(), normalized.clone()];
+    if let Some(rest) = normalized.strip_prefix("http://") {
+        variants.push(format!("https://{}", rest));
+    } else if let Some(rest) = normalized.strip_prefix("https://") {
+        variants.push(format!("http://{}", rest));
+    }
+    variants
+}
+
+fn feed_from_row(row: tokio_postgres::Row) -> Feed {
+    let last_posted: chrono::DateTime<chrono::Utc> = row.get(6);
+    let last_item_date: Option<chrono::DateTime<chrono::Utc>> = row.get(7);
+    let color: Option<i32> = row.get(8);
+    let reactions: Option<String> = row.get(11);
+    let created_at: Option<chrono::DateTime<chrono::Utc>> = row.get(16);
+    let last_checked_at: Option<chrono::DateTime<chrono::Utc>> = row.get(18);
+    let request_spec: Option<String> = row.get(26);
+
+    Feed {
+        id: row.get(0),
+        guild_id: row.get(1),
+        channel_id: row.get(2),
+        url: row.get(3),
+        title: row.get(4),
+        webhook_url: row.get(5),
+        last_posted: last_posted.to_rfc3339(),
+        last_checked_at: last_checked_at.map(|dt| dt.to_rfc3339()),
+        poll_hint_minutes: row.get(21),
+        items_posted: row.get(22),
+        repost_on_update: row.get(23),
+        post_order: PostOrder::parse(row.get(24)).unwrap_or_default(),
+        max_per_cycle: row.get(25),
+        request: request_spec.and_then(|spec| serde_json::from_str(&spec).ok()),
+        custom_title: row.get(27),
+        last_item_date: last_item_date.map(|dt| dt.to_rfc3339()),
+        color: color.map(|c| c as u32),
+        spoiler: row.get(9),
+        nsfw: row.get(10),
+        reactions: reactions
+            .map(|r| r.split(',').map(|s| s.to_string()).collect())
+            .unwrap_or_default(),
+        crosspost: row.get(12),
+        pin_latest: row.get(13),
+        pinned_message_id: row.get(14),
+        translate_to: row.get(15),
+        created_at: created_at.map(|dt| dt.to_rfc3339()),
+        added_by: row.get(17),
+        mention_role_id: row.get(19),
+        image_mode: ImageMode::parse(row.get(20)).unwrap_or_default(),
+    }
 }
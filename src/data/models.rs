@@ -1,3 +1,5 @@
+use chrono::{DateTime, Duration, Timelike, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -7,13 +9,173 @@ pub struct Feed {
     pub channel_id: i64,
     pub url: String,
     pub title: Option<String>,
+    pub custom_title: bool,
     pub webhook_url: Option<String>,
-    pub last_updated: String,
+    pub last_posted: String,
+    pub last_checked_at: Option<String>,
+    pub poll_hint_minutes: Option<i32>,
+    pub items_posted: i64,
+    pub repost_on_update: bool,
     pub last_item_date: Option<String>,
+    pub color: Option<u32>,
+    pub spoiler: bool,
+    pub nsfw: bool,
+    pub reactions: Vec<String>,
+    pub crosspost: bool,
+    pub pin_latest: bool,
+    pub pinned_message_id: Option<i64>,
+    pub translate_to: Option<String>,
+    pub created_at: Option<String>,
+    pub added_by: Option<i64>,
+    pub mention_role_id: Option<i64>,
+    pub image_mode: ImageMode,
+    pub post_order: PostOrder,
+    pub max_per_cycle: Option<i32>,
+    pub request: Option<RequestSpec>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RequestSpec {
+    #[serde(default = "RequestSpec::default_method")]
+    pub method: String,
+    #[serde(default)]
+    pub body: Option<serde_json::Value>,
+    #[serde(default)]
+    pub headers: std::collections::BTreeMap<String, String>,
+}
+
+impl RequestSpec {
+    fn default_method() -> String {
+        "GET".to_string()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImageMode {
+    #[default]
+    Large,
+    Thumbnail,
+    None,
+}
+
+impl ImageMode {
+    pub fn parse(input: &str) -> Option<Self> {
+        match input.trim().to_lowercase().as_str() {
+            "large" => Some(ImageMode::Large),
+            "thumbnail" => Some(ImageMode::Thumbnail),
+            "none" => Some(ImageMode::None),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ImageMode::Large => "large",
+            ImageMode::Thumbnail => "thumbnail",
+            ImageMode::None => "none",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PostOrder {
+    #[default]
+    Newest,
+    Oldest,
+}
+
+impl PostOrder {
+    pub fn parse(input: &str) -> Option<Self> {
+        match input.trim().to_lowercase().as_str() {
+            "newest" => Some(PostOrder::Newest),
+            "oldest" => Some(PostOrder::Oldest),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PostOrder::Newest => "newest",
+            PostOrder::Oldest => "oldest",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct NewFeed<'a> {
+    pub guild_id: u64,
+    pub channel_id: u64,
+    pub url: &'a str,
+    pub title: Option<&'a str>,
+    pub custom_title: bool,
+    pub webhook_url: Option<&'a str>,
+    pub added_by: Option<u64>,
+    pub color: Option<u32>,
+    pub mention_role_id: Option<u64>,
+    pub image_mode: Option<ImageMode>,
+    pub request: Option<&'a RequestSpec>,
+}
+
+impl NewFeed<'_> {
+    pub fn inherit(mut self, defaults: Option<&ChannelDefaults>) -> Self {
+        if let Some(defaults) = defaults {
+            self.color = self.color.or(defaults.color);
+            self.mention_role_id = self
+                .mention_role_id
+                .or(defaults.mention_role_id.map(|id| id as u64));
+            self.image_mode = self.image_mode.or(defaults.image_mode);
+        }
+        self
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChannelDefaults {
+    pub color: Option<u32>,
+    pub mention_role_id: Option<i64>,
+    pub image_mode: Option<ImageMode>,
+}
+
+impl ChannelDefaults {
+    pub fn is_empty(&self) -> bool {
+        self.color.is_none() && self.mention_role_id.is_none() && self.image_mode.is_none()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GuildSettings {
     pub guild_id: i64,
-    pub rss_channel_id: i64,
+    pub rss_channel_id: Option<i64>,
+    pub quiet_hours: Option<QuietHours>,
+    pub footer: Option<String>,
+    pub timezone: Option<String>,
+}
+
+impl GuildSettings {
+    pub fn tz(&self) -> Tz {
+        self.timezone
+            .as_deref()
+            .and_then(|name| name.parse().ok())
+            .unwrap_or(Tz::UTC)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct QuietHours {
+    pub start_hour: u8,
+    pub end_hour: u8,
+    pub utc_offset_minutes: i32,
+}
+
+impl QuietHours {
+    pub fn contains(&self, now: DateTime<Utc>) -> bool {
+        let local = now + Duration::minutes(self.utc_offset_minutes as i64);
+        let hour = local.hour() as u8;
+
+        if self.start_hour < self.end_hour {
+            hour >= self.start_hour && hour < self.end_hour
+        } else {
+            hour >= self.start_hour || hour < self.end_hour
+        }
+    }
 }
@@ -1,11 +1,12 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
-use anyhow::Result;
+use anyhow::{Context as _, Result};
 use serenity::{
     all::{
-        ActivityData, AutocompleteChoice, Command, CommandOptionType, CreateAutocompleteResponse,
-        CreateCommand, CreateInteractionResponse, CreateInteractionResponseFollowup,
-        CreateInteractionResponseMessage, Interaction, OnlineStatus, Permissions, Ready,
+        ActivityData, AutocompleteChoice, Command, CommandOptionType, ConnectionStage,
+        CreateAutocompleteResponse, CreateCommand, CreateInteractionResponse,
+        CreateInteractionResponseFollowup, CreateInteractionResponseMessage, Interaction,
+        OnlineStatus, Permissions, Ready, ShardStageUpdateEvent,
     },
     async_trait,
     prelude::*,
@@ -13,7 +14,15 @@ use serenity::{
 use tokio_cron_scheduler::{Job, JobScheduler};
 use tracing::{error, info, warn};
 
-use crate::{data::Database, scheduler::tasks::check};
+use crate::{
+    cmd::help::CommandHelp,
+    data::{Database, FeedStore},
+    scheduler::{
+        report,
+        tasks::{self, check},
+    },
+    util::{blocklist, fetcher, footer, health, image, metrics, translate},
+};
 
 mod cmd;
 mod data;
@@ -24,26 +33,704 @@ mod util;
 struct Config {
     token: String,
     check_interval_minutes: u64,
+    cron: Option<String>,
+    feeds_per_page: usize,
+    fetch_limits: fetcher::Limits,
+    sync_cooldowns: cmd::sync::Cooldowns,
+    user_agent: String,
+    extra_content_types: Vec<String>,
+    proxy: Option<(String, reqwest::Proxy)>,
+    allow_private_addresses: bool,
+    post_retries: u32,
+    process_timeout: Duration,
+    footer: Option<String>,
+    og_images: bool,
+    log_channel_id: Option<u64>,
     database_url: String,
+    metrics_port: Option<u16>,
+    health_port: Option<u16>,
+    blocked_domains: Vec<String>,
+    translator: Option<translate::Translator>,
 }
 
 impl Config {
     fn load() -> Result<Self> {
-        let config_str = std::fs::read_to_string("config.toml")?;
-        let config: toml::Value = toml::from_str(&config_str)?;
+        let config_str =
+            std::fs::read_to_string("config.toml").context("failed to read config.toml")?;
+        let config: toml::Value =
+            toml::from_str(&config_str).context("config.toml: invalid TOML")?;
+
+        let token = match optional_str(&config, "bot", "token")? {
+            Some(token) => token,
+            None => std::env::var("RSSBOT_TOKEN")
+                .ok()
+                .filter(|token| !token.is_empty())
+                .ok_or_else(|| {
+                    anyhow::anyhow!("config.toml: missing [bot].token (or set RSSBOT_TOKEN)")
+                })?,
+        };
+
+        let check_interval_minutes =
+            optional_integer(&config, "bot", "check_interval_minutes")?.unwrap_or(15);
+        if check_interval_minutes < 1 {
+            return Err(anyhow::anyhow!(
+                "config.toml: [bot].check_interval_minutes must be at least 1, got {}",
+                check_interval_minutes
+            ));
+        }
+
+        let cron = optional_str(&config, "bot", "cron")?.filter(|cron| !cron.trim().is_empty());
+        if let Some(cron) = &cron {
+            Job::new_async(cron.as_str(), |_uuid, _l| Box::pin(async {})).map_err(|e| {
+                anyhow::anyhow!(
+                    "config.toml: [bot].cron \"{}\" is not a valid cron expression (expected six \
+                     fields: sec min hour day month weekday): {}",
+                    cron,
+                    e
+                )
+            })?;
+        }
+
+        let feeds_per_page = optional_integer(&config, "bot", "feeds_per_page")?
+            .unwrap_or(10)
+            .clamp(1, 25);
+
+        let defaults = fetcher::Limits::default();
+        let fetch_limits = fetcher::Limits {
+            per_host: positive_integer(&config, "bot", "per_host_limit", defaults.per_host)?,
+            max_feed_bytes: positive_integer(
+                &config,
+                "bot",
+                "max_feed_bytes",
+                defaults.max_feed_bytes,
+            )?,
+            max_feed_items: positive_integer(
+                &config,
+                "bot",
+                "max_feed_items",
+                defaults.max_feed_items,
+            )?,
+        };
+
+        let cooldown_defaults = cmd::sync::Cooldowns::default();
+        let sync_cooldowns = cmd::sync::Cooldowns {
+            all: cooldown_seconds(&config, "sync_cooldown_seconds", cooldown_defaults.all)?,
+            single: cooldown_seconds(
+                &config,
+                "sync_feed_cooldown_seconds",
+                cooldown_defaults.single,
+            )?,
+        };
+
+        let user_agent = match optional_str(&config, "bot", "user_agent")? {
+            Some(user_agent) => {
+                let user_agent = user_agent.trim().to_string();
+                if user_agent.is_empty()
+                    || reqwest::header::HeaderValue::from_str(&user_agent).is_err()
+                {
+                    return Err(anyhow::anyhow!(
+                        "config.toml: [bot].user_agent must be a non-empty, single-line string"
+                    ));
+                }
+                user_agent
+            }
+            None => fetcher::DEFAULT_USER_AGENT.to_string(),
+        };
+
+        let extra_content_types = optional_str_list(&config, "bot", "extra_content_types")?;
+
+        let post_retries = match optional_integer(&config, "bot", "post_retries")? {
+            Some(retries) if (0..=tasks::MAX_POST_RETRIES as i64).contains(&retries) => {
+                retries as u32
+            }
+            Some(retries) => {
+                return Err(anyhow::anyhow!(
+                    "config.toml: [bot].post_retries must be between 0 and {}, got {}",
+                    tasks::MAX_POST_RETRIES,
+                    retries
+                ));
+            }
+            None => tasks::DEFAULT_POST_RETRIES,
+        };
+
+        let (min_timeout, max_timeout) = (
+            tasks::MIN_PROCESS_TIMEOUT.as_secs() as i64,
+            tasks::MAX_PROCESS_TIMEOUT.as_secs() as i64,
+        );
+        let process_timeout = match optional_integer(&config, "bot", "feed_timeout_seconds")? {
+            Some(seconds) if (min_timeout..=max_timeout).contains(&seconds) => {
+                Duration::from_secs(seconds as u64)
+            }
+            Some(seconds) => {
+                return Err(anyhow::anyhow!(
+                    "config.toml: [bot].feed_timeout_seconds must be between {} and {}, got {}",
+                    min_timeout,
+                    max_timeout,
+                    seconds
+                ));
+            }
+            None => tasks::DEFAULT_PROCESS_TIMEOUT,
+        };
+
+        let proxy = match optional_str(&config, "proxy", "url")? {
+            Some(url) => {
+                let no_proxy = optional_str_list(&config, "proxy", "no_proxy")?;
+                let proxy = fetcher::proxy(&url, &no_proxy)
+                    .map_err(|e| anyhow::anyhow!("config.toml: invalid [proxy].url: {}", e))?;
+                Some((redact(&url), proxy))
+            }
+            None => None,
+        };
+
+        let allow_private_addresses =
+            optional_bool(&config, "bot", "allow_private_addresses")?.unwrap_or(false);
+
+        let footer = optional_str(&config, "bot", "footer")?;
+        if let Some(template) = &footer {
+            if template.chars().count() > footer::MAX_TEMPLATE_LENGTH {
+                return Err(anyhow::anyhow!(
+                    "config.toml: [bot].footer must be at most {} characters",
+                    footer::MAX_TEMPLATE_LENGTH
+                ));
+            }
+        }
+
+        let og_images = optional_bool(&config, "bot", "og_images")?.unwrap_or(false);
+        let log_channel_id = match optional_integer(&config, "bot", "log_channel_id")? {
+            Some(id) if id > 0 => Some(id as u64),
+            Some(id) => {
+                return Err(anyhow::anyhow!(
+                    "config.toml: [bot].log_channel_id must be a channel ID, got {}",
+                    id
+                ));
+            }
+            None => None,
+        };
+
+        let database_url = optional_str(&config, "database", "url")?
+            .ok_or_else(|| anyhow::anyhow!("config.toml: missing [database].url"))?;
+
+        let metrics_port = optional_server_port(&config, "metrics", 9100)?;
+        let health_port = optional_server_port(&config, "health", 8080)?;
+        let blocked_domains = optional_str_list(&config, "blocklist", "domains")?;
+
+        let translator = match optional_str(&config, "translate", "url")? {
+            Some(url) => {
+                let backend = optional_str(&config, "translate", "backend")?
+                    .unwrap_or_else(|| "libretranslate".to_string());
+                Some(translate::Translator {
+                    backend: translate::Backend::parse(&backend).ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "config.toml: [translate].backend must be \"libretranslate\" or \
+                             \"deepl\", got \"{}\"",
+                            backend
+                        )
+                    })?,
+                    url,
+                    api_key: optional_str(&config, "translate", "api_key")?
+                        .filter(|key| !key.is_empty()),
+                })
+            }
+            None => None,
+        };
 
         Ok(Self {
-            token: config["bot"]["token"].as_str().unwrap().to_string(),
-            check_interval_minutes: config["bot"]["check_interval_minutes"]
-                .as_integer()
-                .unwrap_or(15) as u64,
-            database_url: config["database"]["url"].as_str().unwrap().to_string(),
+            token,
+            check_interval_minutes: check_interval_minutes as u64,
+            cron,
+            feeds_per_page: feeds_per_page as usize,
+            fetch_limits,
+            sync_cooldowns,
+            user_agent,
+            extra_content_types,
+            proxy,
+            allow_private_addresses,
+            post_retries,
+            process_timeout,
+            footer,
+            og_images,
+            log_channel_id,
+            database_url,
+            metrics_port,
+            health_port,
+            blocked_domains,
+            translator,
+        })
+    }
+}
+
+fn optional_str(config: &toml::Value, section: &str, key: &str) -> Result<Option<String>> {
+    match config.get(section).and_then(|s| s.get(key)) {
+        Some(value) => value
+            .as_str()
+            .map(|s| Some(s.to_string()))
+            .ok_or_else(|| anyhow::anyhow!("config.toml: [{}].{} must be a string", section, key)),
+        None => Ok(None),
+    }
+}
+
+fn optional_str_list(config: &toml::Value, section: &str, key: &str) -> Result<Vec<String>> {
+    let Some(value) = config.get(section).and_then(|s| s.get(key)) else {
+        return Ok(Vec::new());
+    };
+    value
+        .as_array()
+        .and_then(|items| {
+            items
+                .iter()
+                .map(|item| item.as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "config.toml: [{}].{} must be an array of strings",
+                section,
+                key
+            )
+        })
+}
+
+fn optional_integer(config: &toml::Value, section: &str, key: &str) -> Result<Option<i64>> {
+    match config.get(section).and_then(|s| s.get(key)) {
+        Some(value) => value.as_integer().map(Some).ok_or_else(|| {
+            anyhow::anyhow!("config.toml: [{}].{} must be an integer", section, key)
+        }),
+        None => Ok(None),
+    }
+}
+
+fn redact(url: &str) -> String {
+    match url::Url::parse(url) {
+        Ok(mut parsed) if parsed.password().is_some() => {
+            let _ = parsed.set_password(Some("***"));
+            parsed.to_string()
+        }
+        _ => url.to_string(),
+    }
+}
+
+fn positive_integer(
+    config: &toml::Value,
+    section: &str,
+    key: &str,
+    default: usize,
+) -> Result<usize> {
+    match optional_integer(config, section, key)? {
+        Some(value) if value >= 1 => Ok(value as usize),
+        Some(value) => Err(anyhow::anyhow!(
+            "config.toml: [{}].{} must be at least 1, got {}",
+            section,
+            key,
+            value
+        )),
+        None => Ok(default),
+    }
+}
+
+fn cooldown_seconds(config: &toml::Value, key: &str, default: Duration) -> Result<Duration> {
+    match optional_integer(config, "bot", key)? {
+        Some(seconds) if seconds >= 0 => Ok(Duration::from_secs(seconds as u64)),
+        Some(seconds) => Err(anyhow::anyhow!(
+            "config.toml: [bot].{} must be 0 or more seconds, got {}",
+            key,
+            seconds
+        )),
+        None => Ok(default),
+    }
+}
+
+fn optional_server_port(config: &toml::Value, section: &str, default: u16) -> Result<Option<u16>> {
+    if !optional_bool(config, section, "enabled")?.unwrap_or(false) {
+        return Ok(None);
+    }
+
+    let port = optional_integer(config, section, "port")?.unwrap_or(default as i64);
+    u16::try_from(port)
+        .ok()
+        .filter(|port| *port > 0)
+        .map(Some)
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "config.toml: [{}].port must be between 1 and 65535, got {}",
+                section,
+                port
+            )
         })
+}
+
+fn optional_bool(config: &toml::Value, section: &str, key: &str) -> Result<Option<bool>> {
+    match config.get(section).and_then(|s| s.get(key)) {
+        Some(value) => value
+            .as_bool()
+            .map(Some)
+            .ok_or_else(|| anyhow::anyhow!("config.toml: [{}].{} must be a boolean", section, key)),
+        None => Ok(None),
     }
 }
 
+const FEED_URL_COMMANDS: &[&str] = &[
+    "remove",
+    "sync",
+    "edit",
+    "color",
+    "flag",
+    "reactions",
+    "crosspost",
+    "image",
+    "behavior",
+    "pin",
+    "repost-updates",
+    "translate",
+    "feedinfo",
+    "move",
+];
+
+const COMMAND_HELP: &[CommandHelp] = &[
+    CommandHelp {
+        name: "add",
+        summary: "Add an RSS feed to a channel",
+        permission: Some("Manage Server"),
+        options: &[
+            ("url", "RSS feed URL", true),
+            (
+                "channel",
+                "Channel to post to (defaults to the server default)",
+                false,
+            ),
+            ("title", "Title shown in the post footer", false),
+            ("color", "Embed color as hex, e.g. #FF0000", false),
+            ("mention", "Role to ping when new items are posted", false),
+            (
+                "request",
+                "JSON request spec for API feeds: method, body, headers",
+                false,
+            ),
+        ],
+    },
+    CommandHelp {
+        name: "remove",
+        summary: "Remove an RSS feed",
+        permission: Some("Manage Server"),
+        options: &[
+            (
+                "url",
+                "RSS feed URL (leave empty to pick from a list)",
+                false,
+            ),
+            (
+                "channel",
+                "Only remove from this channel, or every feed in it",
+                false,
+            ),
+            ("all", "Remove every feed in this server", false),
+        ],
+    },
+    CommandHelp {
+        name: "list",
+        summary: "List all RSS feeds",
+        permission: None,
+        options: &[
+            ("grouped", "Group feeds by channel", false),
+            (
+                "collapse",
+                "Show each feed once with every channel it posts to",
+                false,
+            ),
+        ],
+    },
+    CommandHelp {
+        name: "feedinfo",
+        summary: "Show every detail about one RSS feed",
+        permission: None,
+        options: &[("url", "RSS feed URL", true)],
+    },
+    CommandHelp {
+        name: "sync",
+        summary: "Check feeds for new items right now",
+        permission: None,
+        options: &[
+            ("url", "Only sync this feed", false),
+            ("channel", "Only sync the feed in this channel", false),
+        ],
+    },
+    CommandHelp {
+        name: "edit",
+        summary: "Change an RSS feed's title, color or format",
+        permission: Some("Manage Server"),
+        options: &[
+            ("url", "RSS feed URL", true),
+            ("title", "New title shown in the post footer", false),
+            ("clear_title", "Fall back to the feed's domain", false),
+            ("color", "Embed color as hex, or `default`", false),
+            ("format", "Image layout: large, thumbnail or none", false),
+        ],
+    },
+    CommandHelp {
+        name: "move",
+        summary: "Move an RSS feed to another channel",
+        permission: Some("Manage Server"),
+        options: &[
+            ("url", "RSS feed URL", true),
+            ("channel", "Channel to move the feed to", true),
+            (
+                "from",
+                "Channel the feed posts to now, if it posts to several",
+                false,
+            ),
+        ],
+    },
+    CommandHelp {
+        name: "color",
+        summary: "Set the embed color for an RSS feed",
+        permission: Some("Manage Server"),
+        options: &[
+            ("url", "RSS feed URL", true),
+            ("hex", "Hex color like #FF0000, or \"default\"", true),
+        ],
+    },
+    CommandHelp {
+        name: "flag",
+        summary: "Mark an RSS feed's posts as spoilers or NSFW",
+        permission: Some("Manage Server"),
+        options: &[
+            ("url", "RSS feed URL", true),
+            ("spoiler", "Hide posts behind spoiler tags", false),
+            ("nsfw", "Only post in age-restricted channels", false),
+        ],
+    },
+    CommandHelp {
+        name: "behavior",
+        summary: "Choose how many new items an RSS feed posts per check, and in what order",
+        permission: Some("Manage Server"),
+        options: &[
+            ("url", "RSS feed URL", true),
+            (
+                "order",
+                "Post the newest or the oldest new item first",
+                false,
+            ),
+            ("max_per_cycle", "Most new items posted per check", false),
+        ],
+    },
+    CommandHelp {
+        name: "image",
+        summary: "Choose how an RSS feed's post images are shown",
+        permission: Some("Manage Server"),
+        options: &[
+            ("url", "RSS feed URL", true),
+            ("mode", "large, thumbnail or none", true),
+        ],
+    },
+    CommandHelp {
+        name: "reactions",
+        summary: "Add reactions to every new post from an RSS feed",
+        permission: Some("Manage Server"),
+        options: &[
+            ("url", "RSS feed URL", true),
+            ("emojis", "Comma-separated emojis, or \"none\"", true),
+        ],
+    },
+    CommandHelp {
+        name: "crosspost",
+        summary: "Publish posts to followers of announcement channels",
+        permission: Some("Manage Server"),
+        options: &[
+            ("url", "RSS feed URL", true),
+            ("enabled", "Whether to crosspost new items", true),
+        ],
+    },
+    CommandHelp {
+        name: "pin",
+        summary: "Keep the newest post from an RSS feed pinned",
+        permission: Some("Manage Server"),
+        options: &[
+            ("url", "RSS feed URL", true),
+            ("enabled", "Whether to pin the latest item", true),
+        ],
+    },
+    CommandHelp {
+        name: "repost-updates",
+        summary: "Update posts when their article's content changes",
+        permission: Some("Manage Server"),
+        options: &[
+            ("url", "RSS feed URL", true),
+            ("enabled", "Whether to repost updated articles", true),
+        ],
+    },
+    CommandHelp {
+        name: "translate",
+        summary: "Translate an RSS feed's posts before they are sent",
+        permission: Some("Manage Server"),
+        options: &[
+            ("url", "RSS feed URL", true),
+            ("language", "Language code like en or de, or \"off\"", true),
+        ],
+    },
+    CommandHelp {
+        name: "config",
+        summary: "View or change server settings for RSS feeds",
+        permission: Some("Manage Server"),
+        options: &[
+            (
+                "default-channel",
+                "Channel new feeds post to by default",
+                false,
+            ),
+            (
+                "dedup-channel",
+                "Skip articles another feed already posted here",
+                false,
+            ),
+            (
+                "quiet-hours",
+                "Hold posts during a window, e.g. \"22-7 UTC+2\"",
+                false,
+            ),
+            (
+                "timezone",
+                "Timezone for dates, e.g. \"Europe/Berlin\"",
+                false,
+            ),
+            (
+                "footer",
+                "Post footer template using {feed} and {date}",
+                false,
+            ),
+        ],
+    },
+    CommandHelp {
+        name: "channel-config",
+        summary: "Set defaults that new feeds in a channel inherit",
+        permission: Some("Manage Server"),
+        options: &[
+            ("channel", "Channel to configure", false),
+            ("color", "Default embed color, or \"default\"", false),
+            ("mention", "Default role to ping", false),
+            ("image", "Default image mode", false),
+            ("clear", "Remove every default", false),
+        ],
+    },
+    CommandHelp {
+        name: "cleanup",
+        summary: "Find feeds that no longer respond and remove them",
+        permission: Some("Manage Server"),
+        options: &[],
+    },
+    CommandHelp {
+        name: "refresh-titles",
+        summary: "Re-fetch every feed and update changed titles, except ones set by hand",
+        permission: Some("Manage Server"),
+        options: &[],
+    },
+    CommandHelp {
+        name: "setup",
+        summary: "Interactive setup with categories and channels",
+        permission: Some("Manage Server"),
+        options: &[
+            ("validate", "Check each feed is reachable first", false),
+            ("prefix", "Prefix for created channel names", false),
+        ],
+    },
+    CommandHelp {
+        name: "opinionated",
+        summary: "Add or remove feeds from curated collections",
+        permission: Some("Manage Server"),
+        options: &[
+            ("topic", "Collection to use", true),
+            ("validate", "Check each feed is reachable first", false),
+            ("action", "add or remove", false),
+            ("dry_run", "Show what would change", false),
+            ("channel", "Channel to post to", false),
+        ],
+    },
+    CommandHelp {
+        name: "diagnostics",
+        summary: "Check the bot's avatar, permissions, database and collections",
+        permission: Some("Manage Server"),
+        options: &[],
+    },
+    CommandHelp {
+        name: "reload-collections",
+        summary: "Reload curated feed collections from disk",
+        permission: Some("Administrator"),
+        options: &[],
+    },
+    CommandHelp {
+        name: "help",
+        summary: "Show this list, or details for one command",
+        permission: None,
+        options: &[("command", "Command to explain", false)],
+    },
+];
+
+fn opinionated_command() -> CreateCommand {
+    CreateCommand::new("opinionated")
+        .description("Add or remove curated RSS feeds from community collections")
+        .default_member_permissions(Permissions::MANAGE_GUILD)
+        .add_option(
+            serenity::all::CreateCommandOption::new(
+                CommandOptionType::String,
+                "topic",
+                "Topic collection to add feeds from",
+            )
+            .required(true)
+            .set_autocomplete(true),
+        )
+        .add_option(
+            serenity::all::CreateCommandOption::new(
+                CommandOptionType::Boolean,
+                "validate",
+                "Check each feed is reachable before adding it (default: true)",
+            )
+            .required(false),
+        )
+        .add_option(
+            serenity::all::CreateCommandOption::new(
+                CommandOptionType::String,
+                "action",
+                "Add the collection's feeds or remove them (default: add)",
+            )
+            .required(false)
+            .add_string_choice("add", "add")
+            .add_string_choice("remove", "remove"),
+        )
+        .add_option(
+            serenity::all::CreateCommandOption::new(
+                CommandOptionType::Boolean,
+                "dry_run",
+                "Show what would change without adding or removing anything",
+            )
+            .required(false),
+        )
+        .add_option(
+            serenity::all::CreateCommandOption::new(
+                CommandOptionType::Channel,
+                "channel",
+                "Channel to send RSS feeds to (defaults to the server default or current channel)",
+            )
+            .required(false),
+        )
+}
+
+fn help_command() -> CreateCommand {
+    CreateCommand::new("help")
+        .description("List the bot's commands and their options")
+        .add_option(
+            serenity::all::CreateCommandOption::new(
+                CommandOptionType::String,
+                "command",
+                "Command to explain",
+            )
+            .required(false)
+            .set_autocomplete(true),
+        )
+}
+
 struct Handler {
-    database: Arc<Database>,
+    database: Arc<dyn FeedStore>,
+    http_client: reqwest::Client,
+    feeds_per_page: usize,
+    sync_cooldowns: cmd::sync::Cooldowns,
 }
 
 impl Handler {
@@ -67,7 +754,9 @@ impl EventHandler for Handler {
             Interaction::Command(command) => {
                 let result = match command.data.name.as_str() {
                     "add" => {
-                        let result = cmd::add::execute(&ctx, &command, &self.database).await;
+                        let result =
+                            cmd::add::execute(&ctx, &command, &self.database, &self.http_client)
+                                .await;
                         self.update(&ctx).await;
                         result
                     }
@@ -76,11 +765,64 @@ impl EventHandler for Handler {
                         self.update(&ctx).await;
                         result
                     }
-                    "list" => cmd::list::execute(&ctx, &command, &self.database).await,
-                    "sync" => cmd::sync::execute(&ctx, &command, &self.database).await,
+                    "list" => {
+                        cmd::list::execute(&ctx, &command, &self.database, self.feeds_per_page)
+                            .await
+                    }
+                    "color" => cmd::color::execute(&ctx, &command, &self.database).await,
+                    "flag" => cmd::flag::execute(&ctx, &command, &self.database).await,
+                    "behavior" => cmd::behavior::execute(&ctx, &command, &self.database).await,
+                    "pin" => cmd::pin::execute(&ctx, &command, &self.database).await,
+                    "repost-updates" => cmd::repost::execute(&ctx, &command, &self.database).await,
+                    "feedinfo" => cmd::feedinfo::execute(&ctx, &command, &self.database).await,
+                    "diagnostics" => {
+                        cmd::diagnostics::execute(&ctx, &command, &self.database).await
+                    }
+                    "translate" => cmd::translate::execute(&ctx, &command, &self.database).await,
+                    "crosspost" => cmd::crosspost::execute(&ctx, &command, &self.database).await,
+                    "image" => cmd::image::execute(&ctx, &command, &self.database).await,
+                    "reactions" => cmd::reactions::execute(&ctx, &command, &self.database).await,
+                    "config" => cmd::config::execute(&ctx, &command, &self.database).await,
+                    "channel-config" => {
+                        cmd::channel_config::execute(&ctx, &command, &self.database).await
+                    }
+                    "edit" => cmd::edit::execute(&ctx, &command, &self.database).await,
+                    "reload-collections" => cmd::reload::execute(&ctx, &command).await,
+                    "help" => cmd::help::execute(&ctx, &command).await,
+                    "refresh-titles" => {
+                        cmd::refresh::execute(&ctx, &command, &self.database, &self.http_client)
+                            .await
+                    }
+                    "move" => cmd::r#move::execute(&ctx, &command, &self.database).await,
+                    "sync" => {
+                        cmd::sync::execute(
+                            &ctx,
+                            &command,
+                            &self.database,
+                            &self.http_client,
+                            self.sync_cooldowns,
+                        )
+                        .await
+                    }
                     "opinionated" => {
-                        let result =
-                            cmd::opinionated::execute(&ctx, &command, &self.database).await;
+                        let result = cmd::opinionated::execute(
+                            &ctx,
+                            &command,
+                            &self.database,
+                            &self.http_client,
+                        )
+                        .await;
+                        self.update(&ctx).await;
+                        result
+                    }
+                    "cleanup" => {
+                        let result = cmd::cleanup::execute(
+                            &ctx,
+                            &command,
+                            &self.database,
+                            &self.http_client,
+                        )
+                        .await;
                         self.update(&ctx).await;
                         result
                     }
@@ -103,13 +845,20 @@ impl EventHandler for Handler {
                 }
             }
             Interaction::Component(component) => {
-                if component.data.custom_id.starts_with("prev_")
+                if component.data.custom_id.starts_with("first_")
+                    || component.data.custom_id.starts_with("prev_")
                     || component.data.custom_id.starts_with("next_")
+                    || component.data.custom_id.starts_with("last_")
                     || component.data.custom_id.starts_with("jump_")
-                    || component.data.custom_id == "page_select"
+                    || component.data.custom_id.starts_with("page_select_")
                 {
-                    if let Err(e) =
-                        cmd::list::handle_component(&ctx, &component, &self.database).await
+                    if let Err(e) = cmd::list::handle_component(
+                        &ctx,
+                        &component,
+                        &self.database,
+                        self.feeds_per_page,
+                    )
+                    .await
                     {
                         error!("Component interaction error: {}", e);
                         let _ = component
@@ -124,9 +873,50 @@ impl EventHandler for Handler {
                             )
                             .await;
                     }
-                } else if component.data.custom_id.starts_with("setup_") {
+                } else if component.data.custom_id.starts_with("remove_") {
+                    if let Err(e) =
+                        cmd::remove::handle_component(&ctx, &component, &self.database).await
+                    {
+                        error!("Remove component interaction error: {}", e);
+                        let _ = component
+                            .create_followup(
+                                &ctx.http,
+                                CreateInteractionResponseFollowup::new()
+                                    .content(
+                                        "An error occurred while processing your request. Please \
+                                         try again.",
+                                    )
+                                    .ephemeral(true),
+                            )
+                            .await;
+                    }
+                    self.update(&ctx).await;
+                } else if component.data.custom_id.starts_with("cleanup_") {
                     if let Err(e) =
-                        cmd::setup::handle_component(&ctx, &component, &self.database).await
+                        cmd::cleanup::handle_component(&ctx, &component, &self.database).await
+                    {
+                        error!("Cleanup component interaction error: {}", e);
+                        let _ = component
+                            .create_followup(
+                                &ctx.http,
+                                CreateInteractionResponseFollowup::new()
+                                    .content(
+                                        "An error occurred while processing your request. Please \
+                                         try again.",
+                                    )
+                                    .ephemeral(true),
+                            )
+                            .await;
+                    }
+                    self.update(&ctx).await;
+                } else if component.data.custom_id.starts_with("setup_") {
+                    if let Err(e) = cmd::setup::handle_component(
+                        &ctx,
+                        &component,
+                        &self.database,
+                        &self.http_client,
+                    )
+                    .await
                     {
                         error!("Setup component interaction error: {}", e);
                         let _ = component
@@ -144,10 +934,17 @@ impl EventHandler for Handler {
                 }
             }
             Interaction::Modal(modal) => {
-                if modal.data.custom_id == "page_jump_modal" {
-                    if let Err(e) = cmd::list::handle_modal(&ctx, &modal, &self.database).await {
+                if modal.data.custom_id.starts_with("page_jump_modal") {
+                    if let Err(e) =
+                        cmd::list::handle_modal(&ctx, &modal, &self.database, self.feeds_per_page)
+                            .await
+                    {
                         error!("Modal interaction error: {}", e);
                     }
+                } else if modal.data.custom_id.starts_with("setup_") {
+                    if let Err(e) = cmd::setup::handle_modal(&ctx, &modal, &self.database).await {
+                        error!("Setup modal interaction error: {}", e);
+                    }
                 }
             }
             Interaction::Autocomplete(autocomplete) => {
@@ -180,17 +977,81 @@ impl EventHandler for Handler {
                             error!("Failed to load topics for autocomplete: {}", e);
                         }
                     }
+                } else if autocomplete.data.name == "help" {
+                    let current_value = autocomplete
+                        .data
+                        .options
+                        .iter()
+                        .find(|opt| opt.name == "command")
+                        .and_then(|opt| opt.value.as_str())
+                        .unwrap_or("");
+
+                    let choices = cmd::help::matching(current_value)
+                        .map(|name| AutocompleteChoice::new(name, name))
+                        .collect();
+                    let response = CreateInteractionResponse::Autocomplete(
+                        CreateAutocompleteResponse::new().set_choices(choices),
+                    );
+                    let _ = autocomplete.create_response(&ctx.http, response).await;
+                } else if FEED_URL_COMMANDS.contains(&autocomplete.data.name.as_str()) {
+                    let Some(guild_id) = autocomplete.guild_id else {
+                        return;
+                    };
+                    let current_value = autocomplete
+                        .data
+                        .options
+                        .iter()
+                        .find(|opt| opt.name == "url")
+                        .and_then(|opt| opt.value.as_str())
+                        .unwrap_or("");
+
+                    match self
+                        .database
+                        .search(guild_id.get(), current_value, 50)
+                        .await
+                    {
+                        Ok(feeds) => {
+                            let mut seen = std::collections::HashSet::new();
+                            let choices: Vec<_> = feeds
+                                .into_iter()
+                                .filter(|feed| {
+                                    feed.url.len() <= 100 && seen.insert(feed.url.clone())
+                                })
+                                .take(25)
+                                .map(|feed| {
+                                    let name = feed.title.as_deref().unwrap_or(&feed.url);
+                                    AutocompleteChoice::new(
+                                        util::parser::truncate(name, 100),
+                                        feed.url,
+                                    )
+                                })
+                                .collect();
+
+                            let response = CreateInteractionResponse::Autocomplete(
+                                CreateAutocompleteResponse::new().set_choices(choices),
+                            );
+                            let _ = autocomplete.create_response(&ctx.http, response).await;
+                        }
+                        Err(e) => {
+                            error!("Failed to search feeds for autocomplete: {}", e);
+                        }
+                    }
                 }
             }
             _ => {}
         }
     }
 
+    async fn shard_stage_update(&self, _ctx: Context, event: ShardStageUpdateEvent) {
+        health::set_connected(event.new == ConnectionStage::Connected);
+    }
+
     async fn ready(&self, ctx: Context, ready: Ready) {
         info!("{} is connected!", ready.user.name);
+        health::set_connected(true);
         self.update(&ctx).await;
 
-        let commands = vec![
+        let mut commands = vec![
             CreateCommand::new("add")
                 .description("Add an RSS feed to a channel")
                 .default_member_permissions(Permissions::MANAGE_GUILD)
@@ -206,63 +1067,535 @@ impl EventHandler for Handler {
                     serenity::all::CreateCommandOption::new(
                         CommandOptionType::Channel,
                         "channel",
-                        "Channel to send RSS feeds to (defaults to current channel)",
+                        "Channel to send RSS feeds to (defaults to the server default or current channel)",
+                    )
+                    .required(false),
+                )
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "title",
+                        "Title shown in the post footer (defaults to the feed's own title)",
+                    )
+                    .required(false),
+                )
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "color",
+                        "Embed color as hex, e.g. #FF0000",
+                    )
+                    .required(false),
+                )
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::Role,
+                        "mention",
+                        "Role to ping when new items are posted",
+                    )
+                    .required(false),
+                )
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "request",
+                        "JSON request spec for API feeds, e.g. {\"method\": \"POST\", \"body\": {}}",
                     )
                     .required(false),
                 ),
             CreateCommand::new("remove")
                 .description("Remove an RSS feed")
                 .default_member_permissions(Permissions::MANAGE_GUILD)
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "url",
+                        "RSS feed URL (leave empty to pick from a list)",
+                    )
+                    .required(false)
+                    .set_autocomplete(true),
+                )
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::Channel,
+                        "channel",
+                        "Only remove the feed from this channel, or every feed in it if no URL is given",
+                    )
+                    .required(false),
+                )
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::Boolean,
+                        "all",
+                        "Remove every feed in this server",
+                    )
+                    .required(false),
+                ),
+            CreateCommand::new("diagnostics")
+                .description("Check the bot's deployment: avatar, permissions, database and collections")
+                .default_member_permissions(Permissions::MANAGE_GUILD),
+            CreateCommand::new("feedinfo")
+                .description("Show every detail about one RSS feed")
                 .add_option(
                     serenity::all::CreateCommandOption::new(
                         CommandOptionType::String,
                         "url",
                         "RSS feed URL",
                     )
-                    .required(true),
+                    .required(true)
+                    .set_autocomplete(true),
                 ),
-            CreateCommand::new("list").description("List all RSS feeds"),
-            CreateCommand::new("sync")
-                .description("Manually sync RSS feeds")
+            CreateCommand::new("color")
+                .description("Set the embed color for an RSS feed")
+                .default_member_permissions(Permissions::MANAGE_GUILD)
                 .add_option(
                     serenity::all::CreateCommandOption::new(
                         CommandOptionType::String,
                         "url",
-                        "Specific RSS feed URL to sync (optional)",
+                        "RSS feed URL",
                     )
-                    .required(false),
+                    .required(true)
+                    .set_autocomplete(true),
+                )
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "hex",
+                        "Hex color like #FF0000, or \"default\" to reset",
+                    )
+                    .required(true),
                 ),
-            CreateCommand::new("opinionated")
-                .description("Add curated RSS feeds from community collections")
+            CreateCommand::new("flag")
+                .description("Mark an RSS feed's posts as spoilers or NSFW")
                 .default_member_permissions(Permissions::MANAGE_GUILD)
                 .add_option(
                     serenity::all::CreateCommandOption::new(
                         CommandOptionType::String,
-                        "topic",
-                        "Topic collection to add feeds from",
+                        "url",
+                        "RSS feed URL",
                     )
                     .required(true)
                     .set_autocomplete(true),
                 )
                 .add_option(
                     serenity::all::CreateCommandOption::new(
-                        CommandOptionType::Channel,
-                        "channel",
-                        "Channel to send RSS feeds to (defaults to current channel)",
+                        CommandOptionType::Boolean,
+                        "spoiler",
+                        "Hide post titles and descriptions behind spoiler tags",
+                    )
+                    .required(false),
+                )
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::Boolean,
+                        "nsfw",
+                        "Only post this feed in age-restricted channels",
                     )
                     .required(false),
                 ),
-            CreateCommand::new("setup")
-                .description("Interactive setup for RSS feeds with categories and channels")
-                .default_member_permissions(Permissions::MANAGE_GUILD),
-        ];
-
-        if let Err(e) = Command::set_global_commands(&ctx.http, commands).await {
-            error!("Failed to set commands: {}", e);
-        }
-    }
-}
-
+            CreateCommand::new("behavior")
+                .description(
+                    "Choose how many new items an RSS feed posts per check, and in what order",
+                )
+                .default_member_permissions(Permissions::MANAGE_GUILD)
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "url",
+                        "RSS feed URL",
+                    )
+                    .required(true)
+                    .set_autocomplete(true),
+                )
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "order",
+                        "Post the newest or the oldest new item first",
+                    )
+                    .required(false)
+                    .add_string_choice("newest", "newest")
+                    .add_string_choice("oldest", "oldest"),
+                )
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::Integer,
+                        "max_per_cycle",
+                        "Most new items posted per check",
+                    )
+                    .required(false)
+                    .min_int_value(1)
+                    .max_int_value(cmd::behavior::MAX_PER_CYCLE_LIMIT),
+                ),
+            CreateCommand::new("crosspost")
+                .description("Publish an RSS feed's posts to followers of announcement channels")
+                .default_member_permissions(Permissions::MANAGE_GUILD)
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "url",
+                        "RSS feed URL",
+                    )
+                    .required(true)
+                    .set_autocomplete(true),
+                )
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::Boolean,
+                        "enabled",
+                        "Whether to crosspost new items",
+                    )
+                    .required(true),
+                ),
+            CreateCommand::new("image")
+                .description("Choose how an RSS feed's post images are shown")
+                .default_member_permissions(Permissions::MANAGE_GUILD)
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "url",
+                        "RSS feed URL",
+                    )
+                    .required(true)
+                    .set_autocomplete(true),
+                )
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "mode",
+                        "Full-width image, small thumbnail, or no image",
+                    )
+                    .required(true)
+                    .add_string_choice("large", "large")
+                    .add_string_choice("thumbnail", "thumbnail")
+                    .add_string_choice("none", "none"),
+                ),
+            CreateCommand::new("pin")
+                .description("Keep the newest post from an RSS feed pinned")
+                .default_member_permissions(Permissions::MANAGE_GUILD)
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "url",
+                        "RSS feed URL",
+                    )
+                    .required(true)
+                    .set_autocomplete(true),
+                )
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::Boolean,
+                        "enabled",
+                        "Whether to pin the latest item",
+                    )
+                    .required(true),
+                ),
+            CreateCommand::new("repost-updates")
+                .description("Update posts when their article's content changes")
+                .default_member_permissions(Permissions::MANAGE_GUILD)
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "url",
+                        "RSS feed URL",
+                    )
+                    .required(true)
+                    .set_autocomplete(true),
+                )
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::Boolean,
+                        "enabled",
+                        "Whether to repost updated articles",
+                    )
+                    .required(true),
+                ),
+            CreateCommand::new("translate")
+                .description("Translate an RSS feed's posts before they are sent")
+                .default_member_permissions(Permissions::MANAGE_GUILD)
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "url",
+                        "RSS feed URL",
+                    )
+                    .required(true)
+                    .set_autocomplete(true),
+                )
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "language",
+                        "Target language code like en or de, or \"off\" to disable",
+                    )
+                    .required(true),
+                ),
+            CreateCommand::new("reactions")
+                .description("Add reactions to every new post from an RSS feed")
+                .default_member_permissions(Permissions::MANAGE_GUILD)
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "url",
+                        "RSS feed URL",
+                    )
+                    .required(true)
+                    .set_autocomplete(true),
+                )
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "emojis",
+                        "Comma-separated emojis like 👀,✅,❌, or \"none\" to clear",
+                    )
+                    .required(true),
+                ),
+            CreateCommand::new("channel-config")
+                .description("Set defaults that new feeds in a channel inherit")
+                .default_member_permissions(Permissions::MANAGE_GUILD)
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::Channel,
+                        "channel",
+                        "Channel to configure (defaults to the current channel)",
+                    )
+                    .required(false),
+                )
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "color",
+                        "Default embed color like #FF0000, or \"default\" to unset",
+                    )
+                    .required(false),
+                )
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::Role,
+                        "mention",
+                        "Default role to ping on new posts",
+                    )
+                    .required(false),
+                )
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "image",
+                        "Default image mode",
+                    )
+                    .required(false)
+                    .add_string_choice("large", "large")
+                    .add_string_choice("thumbnail", "thumbnail")
+                    .add_string_choice("none", "none"),
+                )
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::Boolean,
+                        "clear",
+                        "Remove every default for the channel",
+                    )
+                    .required(false),
+                ),
+            CreateCommand::new("config")
+                .description("View or change server settings for RSS feeds")
+                .default_member_permissions(Permissions::MANAGE_GUILD)
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::Channel,
+                        "default-channel",
+                        "Channel new feeds post to when no channel is given",
+                    )
+                    .required(false),
+                )
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::Boolean,
+                        "dedup-channel",
+                        "Skip articles already posted to this channel by another feed",
+                    )
+                    .required(false),
+                )
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "quiet-hours",
+                        "Hold posts during a window, e.g. \"22-7 UTC+2\" (fixed UTC offset, no DST) or \"off\"",
+                    )
+                    .required(false),
+                )
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "timezone",
+                        "Timezone for dates in /list and post footers, e.g. \"Europe/Berlin\", or \"off\" for UTC",
+                    )
+                    .required(false),
+                )
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "footer",
+                        "Post footer template, e.g. \"{feed} • {date} • My Server\", or \"off\" for the default",
+                    )
+                    .required(false),
+                ),
+            CreateCommand::new("edit")
+                .description("Change an RSS feed's title, color or format")
+                .default_member_permissions(Permissions::MANAGE_GUILD)
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "url",
+                        "RSS feed URL",
+                    )
+                    .required(true)
+                    .set_autocomplete(true),
+                )
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "title",
+                        "New title shown in the post footer",
+                    )
+                    .max_length(256)
+                    .required(false),
+                )
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::Boolean,
+                        "clear_title",
+                        "Remove the title and fall back to the feed's domain",
+                    )
+                    .required(false),
+                )
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "color",
+                        "Embed color as hex (e.g. #FF0000), or default to reset",
+                    )
+                    .required(false),
+                )
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "format",
+                        "Full-width image, small thumbnail, or no image",
+                    )
+                    .required(false)
+                    .add_string_choice("large", "large")
+                    .add_string_choice("thumbnail", "thumbnail")
+                    .add_string_choice("none", "none"),
+                ),
+            CreateCommand::new("move")
+                .description("Move an RSS feed to another channel")
+                .default_member_permissions(Permissions::MANAGE_GUILD)
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "url",
+                        "RSS feed URL",
+                    )
+                    .required(true)
+                    .set_autocomplete(true),
+                )
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::Channel,
+                        "channel",
+                        "Channel to move the feed to",
+                    )
+                    .required(true),
+                )
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::Channel,
+                        "from",
+                        "Channel the feed currently posts to (needed if it posts to several)",
+                    )
+                    .required(false),
+                ),
+            CreateCommand::new("list")
+                .description("List all RSS feeds")
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::Boolean,
+                        "grouped",
+                        "Group feeds by channel",
+                    )
+                    .required(false),
+                )
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::Boolean,
+                        "collapse",
+                        "Show each feed once with every channel it posts to",
+                    )
+                    .required(false),
+                ),
+            CreateCommand::new("cleanup")
+                .description("Find feeds that no longer respond and remove them")
+                .default_member_permissions(Permissions::MANAGE_GUILD),
+            CreateCommand::new("sync")
+                .description("Manually sync RSS feeds")
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "url",
+                        "Specific RSS feed URL to sync (optional)",
+                    )
+                    .required(false)
+                    .set_autocomplete(true),
+                )
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::Channel,
+                        "channel",
+                        "Only sync the feed in this channel",
+                    )
+                    .required(false),
+                ),
+            CreateCommand::new("refresh-titles")
+                .description("Re-fetch every feed and update changed titles, except ones set by hand")
+                .default_member_permissions(Permissions::MANAGE_GUILD),
+            CreateCommand::new("reload-collections")
+                .description("Reload curated feed collections from disk")
+                .default_member_permissions(Permissions::ADMINISTRATOR),
+            help_command(),
+            CreateCommand::new("setup")
+                .description("Interactive setup for RSS feeds with categories and channels")
+                .default_member_permissions(Permissions::MANAGE_GUILD)
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::Boolean,
+                        "validate",
+                        "Check each feed is reachable before adding it (default: true)",
+                    )
+                    .required(false),
+                )
+                .add_option(
+                    serenity::all::CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "prefix",
+                        "Prefix for created channel names, e.g. \"rss-\"",
+                    )
+                    .required(false)
+                    .max_length(32),
+                ),
+        ];
+
+        if cmd::opinionated::installed().await {
+            commands.push(opinionated_command());
+        } else {
+            info!("No curated collections installed, skipping /opinionated registration");
+        }
+
+        if let Err(e) = Command::set_global_commands(&ctx.http, commands).await {
+            error!("Failed to set commands: {}", e);
+        }
+    }
+}
+
 async fn retry_database_connection(database_url: &str, max_retries: u32) -> Result<Arc<Database>> {
     for attempt in 1..=max_retries {
         match Database::new(database_url).await {
@@ -289,6 +1622,65 @@ async fn retry_database_connection(database_url: &str, max_retries: u32) -> Resu
     unreachable!()
 }
 
+async fn log_startup_report(
+    database: &Arc<dyn FeedStore>,
+    next_check: Option<chrono::DateTime<chrono::Utc>>,
+) {
+    let feeds = match database.feeds().await {
+        Ok(feeds) => feeds,
+        Err(e) => {
+            warn!("Failed to load feeds for startup report: {}", e);
+            return;
+        }
+    };
+
+    let mut per_guild: std::collections::BTreeMap<i64, usize> = std::collections::BTreeMap::new();
+    for feed in &feeds {
+        *per_guild.entry(feed.guild_id).or_default() += 1;
+    }
+    let never_checked = feeds
+        .iter()
+        .filter(|feed| feed.last_checked_at.is_none())
+        .count();
+    let next_check = next_check
+        .map(|time| time.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    info!(
+        feeds = feeds.len(),
+        guilds = per_guild.len(),
+        never_checked,
+        next_check = %next_check,
+        "Startup report"
+    );
+
+    let mut counts: Vec<_> = per_guild.into_iter().collect();
+    counts.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+    if !counts.is_empty() {
+        let summary = counts
+            .iter()
+            .take(10)
+            .map(|(guild_id, count)| format!("{}={}", guild_id, count))
+            .collect::<Vec<_>>()
+            .join(", ");
+        info!(
+            "Feeds per guild (top {} of {}): {}",
+            counts.len().min(10),
+            counts.len(),
+            summary
+        );
+    }
+
+    for feed in &feeds {
+        tracing::debug!(
+            guild_id = feed.guild_id,
+            channel_id = feed.channel_id,
+            "Registered feed {}",
+            feed.url
+        );
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     tracing_subscriber::fmt()
@@ -302,7 +1694,53 @@ async fn main() -> Result<()> {
     info!("Starting RSS Bot...");
 
     let config = Config::load()?;
-    let database = retry_database_connection(&config.database_url, 10).await?;
+    blocklist::init(config.blocked_domains.clone());
+    match &config.proxy {
+        Some((url, _)) => info!("Fetching feeds through proxy {}", url),
+        None => info!("No proxy configured, fetching feeds directly"),
+    }
+    info!("Fetching as \"{}\"", config.user_agent);
+    fetcher::init(
+        config.fetch_limits,
+        config.user_agent.clone(),
+        config.extra_content_types.clone(),
+        config.proxy.clone().map(|(_, proxy)| proxy),
+        config.allow_private_addresses,
+    );
+    if config.allow_private_addresses {
+        warn!("Private and reserved addresses are allowed as feed destinations");
+    }
+    tasks::init(config.post_retries, config.process_timeout);
+    footer::init(config.footer.clone());
+    image::init(config.og_images);
+    report::init(config.log_channel_id);
+    if let Some(translator) = config.translator.clone() {
+        translate::init(translator);
+    }
+    let database: Arc<dyn FeedStore> = retry_database_connection(&config.database_url, 10).await?;
+
+    if let Err(e) = cmd::opinionated::reload().await {
+        warn!("Failed to load opinionated collections: {}", e);
+    }
+
+    let http_client = fetcher::client()?;
+
+    if let Some(port) = config.metrics_port {
+        tokio::spawn(async move {
+            if let Err(e) = metrics::serve(port).await {
+                error!("Metrics server error: {}", e);
+            }
+        });
+    }
+
+    if let Some(port) = config.health_port {
+        let database = database.clone();
+        tokio::spawn(async move {
+            if let Err(e) = health::serve(port, database).await {
+                error!("Health server error: {}", e);
+            }
+        });
+    }
 
     let mut client = Client::builder(
         &config.token,
@@ -313,35 +1751,40 @@ async fn main() -> Result<()> {
     )
     .event_handler(Handler {
         database: database.clone(),
+        http_client: http_client.clone(),
+        feeds_per_page: config.feeds_per_page,
+        sync_cooldowns: config.sync_cooldowns,
     })
     .await?;
 
     let mut scheduler = JobScheduler::new().await?;
 
-    let interval_minutes = config.check_interval_minutes;
+    let schedule = config
+        .cron
+        .clone()
+        .unwrap_or_else(|| format!("0 */{} * * * *", config.check_interval_minutes));
     let db_for_job = database.clone();
     let http_for_job = client.http.clone();
+    let http_client_for_job = http_client.clone();
 
-    scheduler
-        .add(Job::new_async(
-            &format!("0 */{} * * * *", interval_minutes),
-            move |_uuid, _l| {
-                let db = db_for_job.clone();
-                let http = http_for_job.clone();
-                Box::pin(async move {
-                    if let Err(e) = check(db, http).await {
-                        error!("Feed check error: {}", e);
-                    }
-                })
-            },
-        )?)
+    let check_job = scheduler
+        .add(Job::new_async(schedule.as_str(), move |_uuid, _l| {
+            let db = db_for_job.clone();
+            let http = http_for_job.clone();
+            let http_client = http_client_for_job.clone();
+            Box::pin(async move {
+                if let Err(e) = check(db, http, http_client).await {
+                    error!("Feed check error: {}", e);
+                }
+            })
+        })?)
         .await?;
 
     scheduler.start().await?;
-    info!(
-        "Scheduler started with {} minute intervals",
-        interval_minutes
-    );
+    info!("Scheduler started with schedule \"{}\"", schedule);
+
+    let next_check = scheduler.next_tick_for_job(check_job).await.ok().flatten();
+    log_startup_report(&database, next_check).await;
 
     tokio::select! {
         result = client.start() => {
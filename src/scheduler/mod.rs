@@ -1 +1,2 @@
+pub mod report;
 pub mod tasks;
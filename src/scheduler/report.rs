@@ -0,0 +1,100 @@
+use std::{
+    collections::BTreeSet,
+    sync::{LazyLock, Mutex, OnceLock},
+    time::{Duration, Instant},
+};
+
+use serenity::{
+    all::{CreateAllowedMentions, CreateEmbed, CreateEmbedFooter, CreateMessage, Http},
+    model::id::ChannelId,
+};
+use tracing::warn;
+
+use crate::{scheduler::tasks::CheckSummary, util::parser};
+
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(3600);
+const MAX_LISTED_FAILURES: usize = 10;
+
+type Reported = (Instant, BTreeSet<String>);
+
+static LOG_CHANNEL: OnceLock<ChannelId> = OnceLock::new();
+static LAST_REPORT: LazyLock<Mutex<Option<Reported>>> = LazyLock::new(|| Mutex::new(None));
+
+pub fn init(channel_id: Option<u64>) {
+    if let Some(channel_id) = channel_id {
+        let _ = LOG_CHANNEL.set(ChannelId::new(channel_id));
+    }
+}
+
+pub async fn send(http: &Http, summary: &CheckSummary, duration: Duration) {
+    let Some(channel_id) = LOG_CHANNEL.get() else {
+        return;
+    };
+
+    let failed_urls: BTreeSet<String> = summary
+        .failures
+        .iter()
+        .map(|(url, _)| url.clone())
+        .collect();
+
+    {
+        let mut last_report = LAST_REPORT.lock().unwrap();
+        let due = match last_report.as_ref() {
+            None => true,
+            Some((sent_at, last_failures)) => {
+                summary.new_items > 0
+                    || *last_failures != failed_urls
+                    || sent_at.elapsed() >= HEARTBEAT_INTERVAL
+            }
+        };
+        if !due {
+            return;
+        }
+        *last_report = Some((Instant::now(), failed_urls));
+    }
+
+    let mut embed = CreateEmbed::new()
+        .title("Feed check")
+        .field("Checked", summary.checked.to_string(), true)
+        .field("New items", summary.new_items.to_string(), true)
+        .field("Failed", summary.failed.to_string(), true)
+        .color(if summary.failed == 0 {
+            0xa6e3a1
+        } else {
+            0xf9e2af
+        })
+        .footer(CreateEmbedFooter::new(format!(
+            "Took {:.1}s",
+            duration.as_secs_f64()
+        )));
+
+    if !summary.failures.is_empty() {
+        let mut failures = summary
+            .failures
+            .iter()
+            .take(MAX_LISTED_FAILURES)
+            .map(|(url, reason)| {
+                format!(
+                    "• `{}`: {}",
+                    parser::truncate(url, 150),
+                    parser::truncate(reason, 100)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        if summary.failures.len() > MAX_LISTED_FAILURES {
+            failures.push_str(&format!(
+                "\n... and {} more",
+                summary.failures.len() - MAX_LISTED_FAILURES
+            ));
+        }
+        embed = embed.description(failures);
+    }
+
+    let message = CreateMessage::new()
+        .embed(embed)
+        .allowed_mentions(CreateAllowedMentions::new());
+    if let Err(e) = channel_id.send_message(http, message).await {
+        warn!("Failed to post check report to {}: {}", channel_id, e);
+    }
+}
@@ -1,38 +1,146 @@
-use std::{collections::HashSet, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, LazyLock, OnceLock},
+    time::Instant,
+};
 
 use anyhow::Result;
+use chrono_tz::Tz;
+use reqwest::Client;
 use serenity::{
-    all::{CreateEmbed, CreateMessage, Http},
-    model::id::ChannelId,
+    all::{
+        Channel, ChannelType, CreateAllowedMentions, CreateEmbed, CreateMessage, EditMessage, Http,
+        Message, ReactionType,
+    },
+    model::id::{ChannelId, MessageId},
 };
 use tokio::{
     sync::{Mutex, Semaphore},
     time::{Duration, timeout},
 };
-use tracing::{error, info, warn};
+use tracing::{Instrument, error, info, info_span, warn};
 
 use crate::{
-    data::{Database, models::Feed as DbFeed},
-    util::{fetcher, parser},
+    data::{
+        FeedStore,
+        models::{Feed as DbFeed, ImageMode, PostOrder},
+    },
+    scheduler::report,
+    util::{fetcher, footer, image, metrics, parser, translate},
 };
 
 static FEED_CHECK_LOCK: Mutex<()> = Mutex::const_new(());
-static POSTED_ARTICLES: std::sync::LazyLock<Mutex<HashSet<String>>> =
-    std::sync::LazyLock::new(|| Mutex::new(HashSet::new()));
+static POSTED_ARTICLES: LazyLock<Mutex<HashSet<String>>> =
+    LazyLock::new(|| Mutex::new(HashSet::new()));
+static NSFW_BLOCKED: LazyLock<std::sync::Mutex<HashSet<i64>>> =
+    LazyLock::new(|| std::sync::Mutex::new(HashSet::new()));
+static CROSSPOSTS: LazyLock<std::sync::Mutex<HashMap<u64, Vec<Instant>>>> =
+    LazyLock::new(|| std::sync::Mutex::new(HashMap::new()));
+
+static LAST_POSTED: LazyLock<std::sync::Mutex<HashMap<i64, (String, Instant)>>> =
+    LazyLock::new(|| std::sync::Mutex::new(HashMap::new()));
+static POST_RETRIES: OnceLock<u32> = OnceLock::new();
+static PROCESS_TIMEOUT: OnceLock<Duration> = OnceLock::new();
+// Feed id to the stored URL a permanent redirect couldn't be applied to,
+// either because it clashes with another feed or it changes nothing, so
+// the probe isn't repeated every cycle.
+static SETTLED_MOVES: LazyLock<std::sync::Mutex<HashMap<i64, String>>> =
+    LazyLock::new(|| std::sync::Mutex::new(HashMap::new()));
+static BREAKERS: LazyLock<std::sync::Mutex<HashMap<String, Breaker>>> =
+    LazyLock::new(|| std::sync::Mutex::new(HashMap::new()));
+const BREAKER_THRESHOLD: u32 = 5;
+const BREAKER_COOLDOWN: Duration = Duration::from_secs(600);
+const CROSSPOST_LIMIT: usize = 10;
+const CROSSPOST_WINDOW: Duration = Duration::from_secs(3600);
+const EMBED_DESCRIPTION_LIMIT: usize = 4096;
+const EMBED_TOTAL_LIMIT: usize = 6000;
+const REPUBLISH_WINDOW: Duration = Duration::from_secs(6 * 3600);
+const SEND_BACKOFF_CAP: Duration = Duration::from_secs(30);
+const MAX_FUTURE_SKEW_MINUTES: i64 = 5;
+const MAX_DATE_DRIFT_DAYS: i64 = 20 * 365;
+const MIN_POLL_HINT_MINUTES: u32 = 5;
+const MAX_POLL_HINT_MINUTES: u32 = 6 * 60;
+pub const DEFAULT_MAX_PER_CYCLE: usize = 3;
+pub const DEFAULT_POST_RETRIES: u32 = 2;
+pub const MAX_POST_RETRIES: u32 = 5;
+pub const DEFAULT_PROCESS_TIMEOUT: Duration = Duration::from_secs(45);
+// The fetch alone may take 15s, so anything shorter would time out healthy feeds.
+pub const MIN_PROCESS_TIMEOUT: Duration = Duration::from_secs(20);
+pub const MAX_PROCESS_TIMEOUT: Duration = Duration::from_secs(600);
+
+#[derive(Debug, Default)]
+struct Breaker {
+    failures: u32,
+    opened_at: Option<Instant>,
+    probing: bool,
+}
+
+#[derive(Debug)]
+struct CircuitOpen(String);
+
+impl std::fmt::Display for CircuitOpen {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Circuit open for {}, skipping", self.0)
+    }
+}
+
+impl std::error::Error for CircuitOpen {}
+
+pub fn init(post_retries: u32, process_timeout: Duration) {
+    let _ = POST_RETRIES.set(post_retries);
+    let _ = PROCESS_TIMEOUT.set(process_timeout);
+}
+
+fn post_retries() -> u32 {
+    POST_RETRIES.get().copied().unwrap_or(DEFAULT_POST_RETRIES)
+}
+
+fn process_timeout() -> Duration {
+    PROCESS_TIMEOUT
+        .get()
+        .copied()
+        .unwrap_or(DEFAULT_PROCESS_TIMEOUT)
+}
+
+pub fn is_nsfw_blocked(feed_id: i64) -> bool {
+    NSFW_BLOCKED.lock().unwrap().contains(&feed_id)
+}
 
-pub async fn check(database: Arc<Database>, http: Arc<Http>) -> Result<()> {
+#[derive(Debug, Default)]
+pub struct CheckSummary {
+    pub checked: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub new_items: u32,
+    pub failures: Vec<(String, String)>,
+}
+
+pub async fn check(
+    database: Arc<dyn FeedStore>,
+    http: Arc<Http>,
+    client: Client,
+) -> Result<Option<CheckSummary>> {
     let _lock = FEED_CHECK_LOCK.try_lock();
     if _lock.is_err() {
         warn!("Feed check already in progress, skipping this cycle");
-        return Ok(());
+        return Ok(None);
     }
 
+    let started = std::time::Instant::now();
     let feeds = database.feeds().await?;
-    info!("Checking {} feeds", feeds.len());
+    metrics::set(&metrics::FEED_COUNT, feeds.len() as u64);
+    let total = feeds.len();
+    let now = chrono::Utc::now();
+    let feeds: Vec<_> = feeds.into_iter().filter(|feed| due(feed, now)).collect();
+    info!(
+        "Checking {} feeds ({} deferred by update hints)",
+        feeds.len(),
+        total - feeds.len()
+    );
 
     if feeds.is_empty() {
         info!("No feeds to check");
-        return Ok(());
+        return Ok(Some(CheckSummary::default()));
     }
 
     let semaphore = Arc::new(Semaphore::new(8));
@@ -42,17 +150,26 @@ pub async fn check(database: Arc<Database>, http: Arc<Http>) -> Result<()> {
         .map(|feed| {
             let db = database.clone();
             let http = http.clone();
+            let client = client.clone();
             let sem = semaphore.clone();
 
             tokio::spawn(async move {
                 let _permit = sem.acquire().await.ok()?;
-                let result = timeout(Duration::from_secs(45), process(&feed, &db, &http)).await;
+                let result = timeout(
+                    process_timeout(),
+                    process(&feed, db.as_ref(), &http, &client),
+                )
+                .await;
 
                 match result {
                     Ok(Ok(count)) => Some((feed.url.clone(), Ok(count))),
                     Ok(Err(e)) => Some((feed.url.clone(), Err(e))),
                     Err(_) => {
                         warn!("Feed check timed out: {}", feed.url);
+                        let message = format!("Timed out after {}s", process_timeout().as_secs());
+                        if let Err(e) = db.record_error(feed.id, &message).await {
+                            warn!("Failed to record error for {}: {}", feed.url, e);
+                        }
                         Some((feed.url.clone(), Err(anyhow::anyhow!("Timeout"))))
                     }
                 }
@@ -68,11 +185,20 @@ pub async fn check(database: Arc<Database>, http: Arc<Http>) -> Result<()> {
 
     let success = results.iter().filter(|(_, r)| r.is_ok()).count();
     let failed = results.iter().filter(|(_, r)| r.is_err()).count();
+    let new_items: u32 = results.iter().filter_map(|(_, r)| r.as_ref().ok()).sum();
+    let duration_ms = started.elapsed().as_millis() as u64;
 
     info!(
+        checked = results.len(),
+        succeeded = success,
+        failed,
+        new_items,
+        duration_ms,
         "Feed check complete: {} successful, {} failed",
-        success, failed
+        success,
+        failed
     );
+    metrics::set(&metrics::CHECK_DURATION_MS, duration_ms);
 
     for (url, result) in results.iter().filter(|(_, r)| r.is_err()) {
         if let Err(e) = result {
@@ -82,32 +208,157 @@ pub async fn check(database: Arc<Database>, http: Arc<Http>) -> Result<()> {
         }
     }
 
-    Ok(())
+    let summary = CheckSummary {
+        checked: results.len(),
+        succeeded: success,
+        failed,
+        new_items,
+        failures: results
+            .into_iter()
+            .filter_map(|(url, result)| result.err().map(|e| (url, e.to_string())))
+            .collect(),
+    };
+    report::send(&http, &summary, started.elapsed()).await;
+
+    Ok(Some(summary))
+}
+
+pub async fn single(
+    database: Arc<dyn FeedStore>,
+    http: Arc<Http>,
+    client: &Client,
+    feed: &DbFeed,
+) -> Result<u32> {
+    process(feed, database.as_ref(), &http, client).await
 }
 
-pub async fn single(database: Arc<Database>, http: Arc<Http>, url: &str) -> Result<u32> {
-    match database.find(url).await? {
-        Some(feed) => process(&feed, &database, &http).await,
-        None => Err(anyhow::anyhow!("Feed not found: {}", url)),
+async fn process(
+    feed: &DbFeed,
+    database: &dyn FeedStore,
+    http: &Http,
+    client: &Client,
+) -> Result<u32> {
+    let span = info_span!("feed", url = %feed.url, id = feed.id);
+    let new_items = match check_feed(feed, database, http, client)
+        .instrument(span)
+        .await
+    {
+        Ok(new_items) => new_items,
+        Err(e) => {
+            if e.downcast_ref::<CircuitOpen>().is_none() {
+                if let Err(record) = database.record_error(feed.id, &e.to_string()).await {
+                    warn!("Failed to record error for {}: {}", feed.url, record);
+                }
+            }
+            return Err(e);
+        }
+    };
+
+    if new_items > 0 {
+        if let Err(e) = database.increment_posted(feed.id, new_items).await {
+            warn!("Failed to record posted items for {}: {}", feed.url, e);
+        }
     }
+    Ok(new_items)
 }
 
-async fn process(feed: &DbFeed, database: &Database, http: &Http) -> Result<u32> {
+async fn check_feed(
+    feed: &DbFeed,
+    database: &dyn FeedStore,
+    http: &Http,
+    client: &Client,
+) -> Result<u32> {
     info!("Checking feed: {}", feed.url);
 
-    let content = match timeout(Duration::from_secs(15), fetcher::single(&feed.url)).await {
-        Ok(Ok(content)) => content,
+    let settings = database
+        .get_settings(feed.guild_id as u64)
+        .await
+        .ok()
+        .flatten();
+    let quiet_hours = settings.as_ref().and_then(|settings| settings.quiet_hours);
+    let timezone = settings
+        .as_ref()
+        .map(|settings| settings.tz())
+        .unwrap_or(Tz::UTC);
+    let footer_template = settings.and_then(|settings| settings.footer);
+    if quiet_hours.is_some_and(|quiet_hours| quiet_hours.contains(chrono::Utc::now())) {
+        info!("Quiet hours active, deferring feed: {}", feed.url);
+        return Ok(0);
+    }
+
+    if feed.nsfw && !nsfw_allowed(feed, http).await {
+        return Ok(0);
+    }
+    metrics::increment(&metrics::FEEDS_CHECKED, 1);
+
+    let host = host(&feed.url);
+    if !breaker_allows(&host) {
+        return Err(CircuitOpen(host).into());
+    }
+
+    let probe_moves = SETTLED_MOVES.lock().unwrap().get(&feed.id) != Some(&feed.url);
+    let fetched = match timeout(
+        Duration::from_secs(15),
+        fetcher::with_request(client, &feed.url, feed.request.as_ref(), probe_moves),
+    )
+    .await
+    {
+        Ok(Ok(fetched)) => {
+            metrics::increment(&metrics::FETCH_SUCCESSES, 1);
+            breaker_record(&host, true);
+            fetched
+        }
         Ok(Err(e)) => {
             warn!("Failed to fetch {}: {}", feed.url, e);
+            metrics::increment(&metrics::FETCH_FAILURES, 1);
+            // A 404 or a bad content-type still means the host answered.
+            breaker_record(&host, !fetcher::is_host_failure(&e));
             return Err(e);
         }
         Err(_) => {
             warn!("Timeout fetching feed: {}", feed.url);
+            metrics::increment(&metrics::FETCH_FAILURES, 1);
+            breaker_record(&host, false);
             return Err(anyhow::anyhow!("Timeout fetching feed"));
         }
     };
 
-    let parsed_feed = parser::parse(&content)?;
+    if let Some(new_url) = &fetched.moved_to {
+        let moved = if parser::normalize_feed_url(new_url) == feed.url {
+            false
+        } else {
+            match database.update_url(feed.id, new_url).await {
+                Ok(true) => {
+                    info!("Feed {} moved permanently to {}", feed.url, new_url);
+                    true
+                }
+                Ok(false) => {
+                    warn!(
+                        "Feed {} moved permanently to {}, but that feed already exists in the channel",
+                        feed.url, new_url
+                    );
+                    false
+                }
+                Err(e) => {
+                    error!("Failed to update URL for feed {}: {}", feed.url, e);
+                    false
+                }
+            }
+        };
+        if !moved {
+            SETTLED_MOVES
+                .lock()
+                .unwrap()
+                .insert(feed.id, feed.url.clone());
+        }
+    }
+
+    let parsed_feed = parser::parse(&fetched.content)?;
+    let poll_hint = parser::poll_hint(&fetched.content, &parsed_feed)
+        .map(|minutes| minutes.clamp(MIN_POLL_HINT_MINUTES, MAX_POLL_HINT_MINUTES) as i32);
+    if let Err(e) = database.touch_checked(feed.id, poll_hint).await {
+        warn!("Failed to record check time for {}: {}", feed.url, e);
+    }
     let total_items = parsed_feed.entries.len();
 
     if total_items == 0 {
@@ -118,34 +369,49 @@ async fn process(feed: &DbFeed, database: &Database, http: &Http) -> Result<u32>
     info!("Feed {} has {} total items", feed.url, total_items);
 
     let mut new_items = 0u32;
+    let mut reposted = 0u32;
+    let mut edited = 0u32;
+    let mut skipped_duplicates = 0u32;
     let mut newest_posted_date: Option<String> = None;
+    let mut latest_message: Option<MessageId> = None;
+
+    let channel_dedup = database
+        .channel_dedup(feed.channel_id as u64)
+        .await
+        .unwrap_or_else(|e| {
+            warn!(
+                "Failed to load dedup setting for {}: {}",
+                feed.channel_id, e
+            );
+            false
+        });
 
     let items_to_check = if feed.last_item_date.is_some() {
-        std::cmp::min(3, total_items)
+        let max_per_cycle = feed
+            .max_per_cycle
+            .map_or(DEFAULT_MAX_PER_CYCLE, |max| max.max(1) as usize);
+        std::cmp::min(max_per_cycle, total_items)
     } else {
         1
     };
 
-    let mut sorted_entries = parsed_feed.entries.clone();
-    sorted_entries.sort_by(|a, b| {
-        let date_a = a.published.or(a.updated);
-        let date_b = b.published.or(b.updated);
-        date_b.cmp(&date_a)
-    });
+    let now = chrono::Utc::now();
+    let mut sorted_entries: Vec<_> = parsed_feed
+        .entries
+        .iter()
+        .map(|entry| (entry_date(entry, now, &feed.url), entry))
+        .collect();
+    sorted_entries.sort_by(|(date_a, _), (date_b, _)| date_b.cmp(date_a));
+    sorted_entries.truncate(items_to_check);
+    if feed.post_order == PostOrder::Oldest {
+        sorted_entries.reverse();
+    }
 
-    for entry in sorted_entries.iter().take(items_to_check) {
+    for &(date, entry) in &sorted_entries {
         let entry_id = identifier(entry);
 
-        {
-            let posted_articles = POSTED_ARTICLES.lock().await;
-            if posted_articles.contains(&entry_id) {
-                info!("Skipping already posted article: {}", entry_id);
-                continue;
-            }
-        }
-
         let should_post = if let Some(last_date) = &feed.last_item_date {
-            if let Some(pub_date) = entry.published.or(entry.updated) {
+            if let Some(pub_date) = date {
                 let entry_date = pub_date.to_rfc3339();
                 entry_date > *last_date
             } else {
@@ -156,38 +422,158 @@ async fn process(feed: &DbFeed, database: &Database, http: &Http) -> Result<u32>
         };
 
         if should_post {
-            if let Some(title) = &entry.title {
-                info!("Posting new item: {}", title.content);
+            if !claim(&entry_id).await {
+                info!("Skipping already posted article: {}", entry_id);
+                continue;
             }
 
-            match post(feed, entry, http).await {
-                Ok(_) => {
-                    new_items += 1;
+            // A clamped date is "now" on every check, so future-dated items
+            // would post again once the in-memory claim is gone. Their
+            // posted_articles row marks them as seen across restarts.
+            let future_dated = is_clamped(entry, date);
+            if future_dated && seen_future_dated(feed, entry, database).await {
+                info!("Skipping future-dated item already posted: {}", entry_id);
+                continue;
+            }
+
+            let canonical_link = entry.links.first().map(|l| parser::canonical_url(&l.href));
 
+            if channel_dedup {
+                if let Some(link) = &canonical_link {
+                    if database
+                        .recently_posted(feed.channel_id as u64, link)
+                        .await
+                        .unwrap_or(false)
                     {
-                        let mut posted_articles = POSTED_ARTICLES.lock().await;
-                        posted_articles.insert(entry_id);
+                        info!(
+                            "Skipping article already posted to channel {}: {}",
+                            feed.channel_id, link
+                        );
+                        skipped_duplicates += 1;
+                        track_newest(&mut newest_posted_date, date);
+                        continue;
                     }
+                }
+            }
+
+            let republish_key = republish_key(entry);
+            if is_republish(feed.id, &republish_key) {
+                info!(
+                    "Skipping republished item from {}: {}",
+                    feed.url, republish_key
+                );
+                skipped_duplicates += 1;
+                track_newest(&mut newest_posted_date, date);
+                continue;
+            }
+
+            if let Some(title) = &entry.title {
+                info!("Posting new item: {}", title.content);
+            }
 
-                    if let Some(pub_date) = entry.published.or(entry.updated) {
-                        let date_string = pub_date.to_rfc3339();
-                        if newest_posted_date
-                            .as_ref()
-                            .map_or(true, |existing| date_string > *existing)
-                        {
-                            newest_posted_date = Some(date_string);
+            match post(
+                feed,
+                entry,
+                footer_template.as_deref(),
+                timezone,
+                http,
+                client,
+            )
+            .await
+            {
+                Ok(message_id) => {
+                    new_items += 1;
+                    latest_message.get_or_insert(message_id);
+                    if feed.repost_on_update || future_dated {
+                        record_article(feed, entry, Some(message_id), database).await;
+                    }
+                    LAST_POSTED
+                        .lock()
+                        .unwrap()
+                        .insert(feed.id, (republish_key, Instant::now()));
+                    metrics::increment(&metrics::ITEMS_POSTED, 1);
+
+                    if channel_dedup {
+                        if let Some(link) = &canonical_link {
+                            if let Err(e) =
+                                database.record_posted(feed.channel_id as u64, link).await
+                            {
+                                warn!("Failed to record posted link {}: {}", link, e);
+                            }
                         }
                     }
+
+                    track_newest(&mut newest_posted_date, date);
                 }
                 Err(e) => {
                     error!("Failed to post to channel: {}", e);
+                    release(&entry_id).await;
                     break;
                 }
             }
+        } else if feed.repost_on_update {
+            let key = article_key(entry);
+            let previous = match database.posted_article(feed.id, &key).await {
+                Ok(previous) => previous,
+                Err(e) => {
+                    warn!("Failed to load content hash for {}: {}", key, e);
+                    continue;
+                }
+            };
+
+            match previous {
+                None => record_article(feed, entry, None, database).await,
+                Some((previous, message_id)) if previous != content_hash(entry) => {
+                    let embed =
+                        build_embed(feed, entry, footer_template.as_deref(), timezone, client)
+                            .await;
+
+                    if let Some(message_id) = message_id {
+                        let message_id = MessageId::new(message_id as u64);
+                        match edit_posted(feed, message_id, embed.clone(), http).await {
+                            Ok(()) => {
+                                info!("Content of {} changed, edited message {}", key, message_id);
+                                edited += 1;
+                                record_article(feed, entry, None, database).await;
+                                continue;
+                            }
+                            Err(e) if not_found(&e) => {
+                                info!("Message for {} was deleted, posting it again", key);
+                            }
+                            Err(e) => {
+                                warn!("Failed to edit message for {}: {}", key, e);
+                                continue;
+                            }
+                        }
+                    } else {
+                        info!("Content of {} changed, reposting", key);
+                    }
+
+                    match send(feed, embed, http).await {
+                        Ok(message_id) => {
+                            reposted += 1;
+                            latest_message.get_or_insert(message_id);
+                            metrics::increment(&metrics::ITEMS_POSTED, 1);
+                            record_article(feed, entry, Some(message_id), database).await;
+                        }
+                        Err(e) => {
+                            error!("Failed to repost updated item: {}", e);
+                            break;
+                        }
+                    }
+                }
+                Some(_) => {}
+            }
         }
     }
 
-    if new_items > 0 {
+    if feed.pin_latest {
+        if let Some(message_id) = latest_message {
+            pin_latest(feed, message_id, database, http).await;
+        }
+    }
+
+    if new_items > 0 || skipped_duplicates > 0 {
         info!("Updating last_item_date to: {:?}", newest_posted_date);
         if let Err(e) = database
             .update(feed.id, newest_posted_date.as_deref())
@@ -199,28 +585,240 @@ async fn process(feed: &DbFeed, database: &Database, http: &Http) -> Result<u32>
     } else {
         info!("No new items for feed: {}", feed.url);
     }
+    if reposted > 0 || edited > 0 {
+        info!(
+            "Reposted {} and edited {} updated items for feed: {}",
+            reposted, edited, feed.url
+        );
+    }
 
-    Ok(new_items)
+    Ok(new_items + reposted)
+}
+
+fn host(url: &str) -> String {
+    url::Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(|host| host.to_lowercase()))
+        .unwrap_or_else(|| url.to_string())
+}
+
+fn breaker_allows(host: &str) -> bool {
+    let mut breakers = BREAKERS.lock().unwrap();
+    let Some(breaker) = breakers.get_mut(host) else {
+        return true;
+    };
+    let Some(opened_at) = breaker.opened_at else {
+        return true;
+    };
+
+    if opened_at.elapsed() < BREAKER_COOLDOWN {
+        return false;
+    }
+
+    info!(host, "Circuit half-open, probing host");
+    breaker.probing = true;
+    breaker.opened_at = Some(Instant::now());
+    true
+}
+
+fn breaker_record(host: &str, success: bool) {
+    let mut breakers = BREAKERS.lock().unwrap();
+
+    if success {
+        if let Some(breaker) = breakers.remove(host) {
+            if breaker.opened_at.is_some() {
+                info!(host, "Circuit closed, host recovered");
+            }
+        }
+        return;
+    }
+
+    let breaker = breakers.entry(host.to_string()).or_default();
+    breaker.failures += 1;
+
+    if breaker.probing {
+        breaker.probing = false;
+        breaker.opened_at = Some(Instant::now());
+        warn!(
+            host,
+            failures = breaker.failures,
+            "Circuit re-opened, probe failed"
+        );
+    } else if breaker.opened_at.is_none() && breaker.failures >= BREAKER_THRESHOLD {
+        breaker.opened_at = Some(Instant::now());
+        warn!(
+            host,
+            failures = breaker.failures,
+            cooldown_secs = BREAKER_COOLDOWN.as_secs(),
+            "Circuit opened after consecutive failures"
+        );
+    }
+}
+
+async fn nsfw_allowed(feed: &DbFeed, http: &Http) -> bool {
+    let allowed = match ChannelId::new(feed.channel_id as u64)
+        .to_channel(http)
+        .await
+    {
+        Ok(Channel::Guild(channel)) => channel.nsfw,
+        Ok(_) => false,
+        Err(e) => {
+            warn!(
+                "Failed to look up channel {} for NSFW feed {}: {}",
+                feed.channel_id, feed.url, e
+            );
+            return !is_nsfw_blocked(feed.id);
+        }
+    };
+
+    let mut blocked = NSFW_BLOCKED.lock().unwrap();
+    if allowed {
+        blocked.remove(&feed.id);
+    } else {
+        warn!(
+            "Not posting NSFW feed {} because channel {} is not age-restricted",
+            feed.url, feed.channel_id
+        );
+        blocked.insert(feed.id);
+    }
+    allowed
+}
+
+fn due(feed: &DbFeed, now: chrono::DateTime<chrono::Utc>) -> bool {
+    let (Some(minutes), Some(last_checked)) = (feed.poll_hint_minutes, &feed.last_checked_at)
+    else {
+        return true;
+    };
+    let Ok(last_checked) = chrono::DateTime::parse_from_rfc3339(last_checked) else {
+        return true;
+    };
+
+    now >= last_checked.with_timezone(&chrono::Utc) + chrono::Duration::minutes(minutes as i64)
+}
+
+fn entry_date(
+    entry: &feed_rs::model::Entry,
+    now: chrono::DateTime<chrono::Utc>,
+    feed_url: &str,
+) -> Option<chrono::DateTime<chrono::Utc>> {
+    let date = entry.published.or(entry.updated)?;
+
+    if (date - now).num_days().abs() > MAX_DATE_DRIFT_DAYS {
+        warn!(
+            "Ignoring implausible date {} on item {} from {}",
+            date.to_rfc3339(),
+            identifier(entry),
+            feed_url
+        );
+        return None;
+    }
+
+    if date > now + chrono::Duration::minutes(MAX_FUTURE_SKEW_MINUTES) {
+        info!(
+            "Clamping future date {} on item {} from {} to now",
+            date.to_rfc3339(),
+            identifier(entry),
+            feed_url
+        );
+        return Some(now);
+    }
+
+    Some(date)
+}
+
+fn is_clamped(entry: &feed_rs::model::Entry, date: Option<chrono::DateTime<chrono::Utc>>) -> bool {
+    date.is_some() && entry.published.or(entry.updated) != date
+}
+
+async fn seen_future_dated(
+    feed: &DbFeed,
+    entry: &feed_rs::model::Entry,
+    database: &dyn FeedStore,
+) -> bool {
+    let key = article_key(entry);
+    match database.posted_article(feed.id, &key).await {
+        Ok(Some(_)) => {
+            // Refresh the row so the 30-day prune keeps it while the feed
+            // still serves the item.
+            record_article(feed, entry, None, database).await;
+            true
+        }
+        Ok(None) => false,
+        Err(e) => {
+            warn!("Failed to check whether {} was posted: {}", key, e);
+            false
+        }
+    }
+}
+
+fn track_newest(newest: &mut Option<String>, date: Option<chrono::DateTime<chrono::Utc>>) {
+    if let Some(pub_date) = date {
+        let date_string = pub_date.to_rfc3339();
+        if newest
+            .as_ref()
+            .is_none_or(|existing| date_string > *existing)
+        {
+            *newest = Some(date_string);
+        }
+    }
+}
+
+fn normalize_title(title: &str) -> String {
+    title
+        .trim()
+        .to_lowercase()
+        .replace(
+            [
+                '\n', '\r', '\t', ':', '!', '?', '.', ',', ';', '-', '–', '—',
+            ],
+            " ",
+        )
+        .split_whitespace()
+        .filter(|word| word.len() > 2)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn republish_key(entry: &feed_rs::model::Entry) -> String {
+    let title = entry
+        .title
+        .as_ref()
+        .map(|title| normalize_title(&title.content))
+        .unwrap_or_default();
+    let link = entry
+        .links
+        .first()
+        .map(|link| parser::canonical_url(&link.href))
+        .unwrap_or_default();
+
+    format!("{} {}", title, link)
+}
+
+fn is_republish(feed_id: i64, key: &str) -> bool {
+    if key.trim().is_empty() {
+        return false;
+    }
+
+    LAST_POSTED
+        .lock()
+        .unwrap()
+        .get(&feed_id)
+        .is_some_and(|(last, posted_at)| last == key && posted_at.elapsed() < REPUBLISH_WINDOW)
+}
+
+async fn claim(entry_id: &str) -> bool {
+    POSTED_ARTICLES.lock().await.insert(entry_id.to_string())
+}
+
+async fn release(entry_id: &str) {
+    POSTED_ARTICLES.lock().await.remove(entry_id);
 }
 
 fn identifier(entry: &feed_rs::model::Entry) -> String {
     let mut parts = Vec::new();
 
     if let Some(title) = &entry.title {
-        let normalized_title = title
-            .content
-            .trim()
-            .to_lowercase()
-            .replace(
-                &[
-                    '\n', '\r', '\t', ':', '!', '?', '.', ',', ';', '-', '–', '—',
-                ],
-                " ",
-            )
-            .split_whitespace()
-            .filter(|word| word.len() > 2)
-            .collect::<Vec<_>>()
-            .join(" ");
+        let normalized_title = normalize_title(&title.content);
 
         if !normalized_title.is_empty() {
             parts.push(normalized_title);
@@ -228,7 +826,7 @@ fn identifier(entry: &feed_rs::model::Entry) -> String {
     }
 
     if let Some(link) = entry.links.first() {
-        if let Ok(url) = url::Url::parse(&link.href) {
+        if let Ok(url) = url::Url::parse(&parser::strip_tracking(&link.href)) {
             if let Some(path) = url.path_segments() {
                 let path_parts: Vec<&str> = path.collect();
                 if !path_parts.is_empty() {
@@ -270,14 +868,150 @@ fn identifier(entry: &feed_rs::model::Entry) -> String {
     hash
 }
 
-async fn post(feed: &DbFeed, entry: &feed_rs::model::Entry, http: &Http) -> Result<()> {
-    let channel_id = ChannelId::new(feed.channel_id as u64);
+fn article_key(entry: &feed_rs::model::Entry) -> String {
+    if !entry.id.is_empty() {
+        return entry.id.clone();
+    }
+    entry
+        .links
+        .first()
+        .map(|link| parser::canonical_url(&link.href))
+        .unwrap_or_else(|| identifier(entry))
+}
+
+fn content_hash(entry: &feed_rs::model::Entry) -> String {
+    use std::{
+        collections::hash_map::DefaultHasher,
+        hash::{Hash, Hasher},
+    };
+
+    let mut hasher = DefaultHasher::new();
+    entry.title.as_ref().map(|t| &t.content).hash(&mut hasher);
+    entry.summary.as_ref().map(|s| &s.content).hash(&mut hasher);
+    entry
+        .content
+        .as_ref()
+        .and_then(|c| c.body.as_ref())
+        .hash(&mut hasher);
+    hasher.finish().to_string()
+}
+
+async fn record_article(
+    feed: &DbFeed,
+    entry: &feed_rs::model::Entry,
+    message_id: Option<MessageId>,
+    database: &dyn FeedStore,
+) {
+    let key = article_key(entry);
+    let message_id = message_id.map(|id| id.get() as i64);
+    if let Err(e) = database
+        .record_article(feed.id, &key, &content_hash(entry), message_id)
+        .await
+    {
+        warn!("Failed to record content hash for {}: {}", key, e);
+    }
+}
 
-    let title = parser::truncate(&parser::title(entry), 256);
-    let description = parser::description(entry);
-    let url = entry.links.first().map(|l| l.href.clone());
+async fn post(
+    feed: &DbFeed,
+    entry: &feed_rs::model::Entry,
+    footer_template: Option<&str>,
+    timezone: Tz,
+    http: &Http,
+    client: &Client,
+) -> Result<MessageId> {
+    let embed = build_embed(feed, entry, footer_template, timezone, client).await;
+    send(feed, embed, http).await
+}
+
+async fn build_embed(
+    feed: &DbFeed,
+    entry: &feed_rs::model::Entry,
+    footer_template: Option<&str>,
+    timezone: Tz,
+    client: &Client,
+) -> CreateEmbed {
+    let mut title = parser::title(entry);
+    let mut description = parser::description(entry);
+    let mut translated_from = None;
+
+    if let Some(target) = &feed.translate_to {
+        match translate::translate(&[&title, &description], target).await {
+            Ok(translation) => {
+                let source = translation.source_language;
+                if source
+                    .as_deref()
+                    .is_none_or(|source| !source.eq_ignore_ascii_case(target))
+                {
+                    let mut texts = translation.texts.into_iter();
+                    title = texts.next().unwrap_or(title);
+                    description = parser::truncate(&texts.next().unwrap_or(description), 4000);
+                    translated_from = Some(source.unwrap_or_else(|| "unknown".to_string()));
+                }
+            }
+            Err(e) => warn!(
+                "Failed to translate item from {}, posting original text: {}",
+                feed.url, e
+            ),
+        }
+    }
+
+    title = parser::neutralize_mentions(&title);
+    description = parser::neutralize_mentions(&description);
+
+    let feed_name = if let Some(feed_title) = &feed.title {
+        parser::clean(feed_title)
+    } else if let Ok(parsed_url) = url::Url::parse(&feed.url) {
+        parsed_url.host_str().unwrap_or("RSS Feed").to_string()
+    } else {
+        "RSS Feed".to_string()
+    };
+    let footer_text = footer::render(
+        footer_template,
+        &feed_name,
+        entry.published.or(entry.updated),
+        timezone,
+    );
+
+    let footer_text = match translated_from {
+        Some(source) => format!(
+            "{} • Translated from {}",
+            footer_text,
+            source.to_uppercase()
+        ),
+        None => footer_text,
+    };
+    let footer_text = parser::neutralize_mentions(&footer_text);
+
+    let enclosure = extract_enclosure(entry);
 
-    let embed_color = 0x5865f2;
+    if feed.spoiler {
+        title = format!("||{}||", parser::truncate(&title, 252));
+    } else {
+        title = parser::truncate(&title, 256);
+    }
+
+    let fields: Vec<(&str, &str)> = enclosure
+        .iter()
+        .map(|(name, value)| (name.as_str(), value.as_str()))
+        .collect();
+    let spoiler_markers = if feed.spoiler { 4 } else { 0 };
+    let used = embed_length(&title, "", &footer_text, &fields) + spoiler_markers;
+    let budget = description_budget(used, spoiler_markers);
+    if description.chars().count() > budget {
+        warn!(
+            "Trimming description for {} to fit the {} character embed limit",
+            feed.url, EMBED_TOTAL_LIMIT
+        );
+        description = parser::truncate(&description, budget);
+    }
+
+    if feed.spoiler && !description.is_empty() {
+        description = format!("||{}||", description);
+    }
+    let url = entry.links.first().map(|l| parser::strip_tracking(&l.href));
+
+    let embed_color = feed.color.unwrap_or(0x5865f2);
 
     let mut embed = CreateEmbed::new()
         .title(&title)
@@ -292,87 +1026,812 @@ async fn post(feed: &DbFeed, entry: &feed_rs::model::Entry, http: &Http) -> Resu
         embed = embed.timestamp(pub_date);
     }
 
-    if let Some(image_url) = extract_image(entry) {
-        embed = embed.image(image_url);
+    if !feed.spoiler && feed.image_mode != ImageMode::None {
+        let base = url.as_deref().unwrap_or(&feed.url);
+        let image_url = match image::extract(entry, base) {
+            Some(image_url) => Some(image_url),
+            None => match &url {
+                Some(link) => image::from_page(client, link).await,
+                None => None,
+            },
+        };
+        if let Some(image_url) = image_url {
+            embed = match feed.image_mode {
+                ImageMode::Thumbnail => embed.thumbnail(image_url),
+                _ => embed.image(image_url),
+            };
+        }
     }
 
-    let footer_text = if let Some(feed_title) = &feed.title {
-        parser::clean(feed_title)
-    } else if let Ok(parsed_url) = url::Url::parse(&feed.url) {
-        parsed_url.host_str().unwrap_or("RSS Feed").to_string()
-    } else {
-        "RSS Feed".to_string()
-    };
+    if let Some((name, value)) = enclosure {
+        embed = embed.field(name, value, false);
+    }
 
-    embed = embed.footer(serenity::all::CreateEmbedFooter::new(footer_text));
+    embed.footer(serenity::all::CreateEmbedFooter::new(footer_text))
+}
 
-    let message = CreateMessage::new().embed(embed);
+async fn send(feed: &DbFeed, embed: CreateEmbed, http: &Http) -> Result<MessageId> {
+    let channel_id = ChannelId::new(feed.channel_id as u64);
+    let mut message = CreateMessage::new()
+        .embed(embed)
+        .allowed_mentions(CreateAllowedMentions::new());
+    if let Some(role) = feed.mention_role_id {
+        message = message
+            .content(format!("<@&{}>", role))
+            .allowed_mentions(CreateAllowedMentions::new().roles([role as u64]));
+    }
 
-    for attempt in 0..2 {
+    let retries = post_retries();
+    for attempt in 0..=retries {
         match channel_id.send_message(http, message.clone()).await {
-            Ok(_) => return Ok(()),
+            Ok(sent) => {
+                react(feed, &sent, http).await;
+                if feed.crosspost {
+                    crosspost(feed, &sent, http).await;
+                }
+                return Ok(sent.id);
+            }
+            Err(e) if permanent(&e) => {
+                return Err(anyhow::anyhow!("Failed to send message: {}", e));
+            }
             Err(e) => {
-                if attempt == 1 {
+                if attempt == retries {
                     return Err(anyhow::anyhow!(
-                        "Failed to send message after 2 attempts: {}",
+                        "Failed to send message after {} attempts: {}",
+                        retries + 1,
                         e
                     ));
                 }
+                let delay = send_backoff(attempt);
                 warn!(
-                    "Failed to send message (attempt {}), retrying immediately",
-                    attempt + 1
+                    "Failed to send message (attempt {}), retrying in {}s: {}",
+                    attempt + 1,
+                    delay.as_secs(),
+                    e
                 );
+                tokio::time::sleep(delay).await;
             }
         }
     }
 
-    Ok(())
+    Err(anyhow::anyhow!("Failed to send message"))
 }
 
-fn extract_image(entry: &feed_rs::model::Entry) -> Option<String> {
-    if let Some(content) = &entry.content {
-        if let Some(body) = &content.body {
-            if let Some(img_url) = extract_image_from_html(body) {
-                return Some(img_url);
-            }
-        }
+fn send_backoff(attempt: u32) -> Duration {
+    Duration::from_secs(4u64.saturating_pow(attempt)).min(SEND_BACKOFF_CAP)
+}
+
+async fn edit_posted(
+    feed: &DbFeed,
+    message_id: MessageId,
+    embed: CreateEmbed,
+    http: &Http,
+) -> serenity::Result<()> {
+    let channel_id = ChannelId::new(feed.channel_id as u64);
+    channel_id
+        .edit_message(http, message_id, EditMessage::new().embed(embed))
+        .await
+        .map(|_| ())
+}
+
+fn not_found(error: &serenity::Error) -> bool {
+    match error {
+        serenity::Error::Http(e) => e.status_code().is_some_and(|status| status.as_u16() == 404),
+        _ => false,
+    }
+}
+
+fn permanent(error: &serenity::Error) -> bool {
+    match error {
+        serenity::Error::Http(e) => e
+            .status_code()
+            .is_some_and(|status| matches!(status.as_u16(), 400 | 401 | 403 | 404)),
+        _ => false,
     }
+}
+
+async fn pin_latest(feed: &DbFeed, message_id: MessageId, database: &dyn FeedStore, http: &Http) {
+    let channel_id = ChannelId::new(feed.channel_id as u64);
 
-    if let Some(summary) = &entry.summary {
-        if let Some(img_url) = extract_image_from_html(&summary.content) {
-            return Some(img_url);
+    if let Some(previous) = feed.pinned_message_id {
+        if let Err(e) = channel_id
+            .unpin(http, MessageId::new(previous as u64))
+            .await
+        {
+            warn!(
+                "Failed to unpin previous message {} for feed {}: {}",
+                previous, feed.url, e
+            );
         }
     }
 
-    None
+    if let Err(e) = channel_id.pin(http, message_id).await {
+        warn!("Failed to pin latest message for feed {}: {}", feed.url, e);
+        return;
+    }
+
+    if let Err(e) = database
+        .set_pinned_message(feed.id, Some(message_id.get() as i64))
+        .await
+    {
+        error!(
+            "Failed to record pinned message for feed {}: {}",
+            feed.url, e
+        );
+    }
 }
 
-fn extract_image_from_html(html: &str) -> Option<String> {
-    use std::sync::LazyLock;
+async fn react(feed: &DbFeed, message: &Message, http: &Http) {
+    for emoji in &feed.reactions {
+        let Ok(reaction) = ReactionType::try_from(emoji.as_str()) else {
+            warn!("Skipping invalid reaction {} for feed {}", emoji, feed.url);
+            continue;
+        };
+        if let Err(e) = message.react(http, reaction).await {
+            warn!(
+                "Failed to add reaction {} for feed {}: {}",
+                emoji, feed.url, e
+            );
+        }
+    }
+}
 
-    use regex::Regex;
+async fn crosspost(feed: &DbFeed, message: &Message, http: &Http) {
+    match message.channel_id.to_channel(http).await {
+        Ok(Channel::Guild(channel)) if channel.kind == ChannelType::News => {}
+        Ok(_) => return,
+        Err(e) => {
+            warn!(
+                "Failed to look up channel {} for crosspost: {}",
+                message.channel_id, e
+            );
+            return;
+        }
+    }
 
-    static IMG_REGEX: LazyLock<Regex> =
-        LazyLock::new(|| Regex::new(r#"<img[^>]+src=["']([^"']+)["'][^>]*>"#).unwrap());
+    {
+        let mut crossposts = CROSSPOSTS.lock().unwrap();
+        let recent = crossposts.entry(message.channel_id.get()).or_default();
+        recent.retain(|at| at.elapsed() < CROSSPOST_WINDOW);
+        if recent.len() >= CROSSPOST_LIMIT {
+            warn!(
+                "Crosspost limit reached for channel {}, not publishing item from {}",
+                message.channel_id, feed.url
+            );
+            return;
+        }
+        recent.push(Instant::now());
+    }
+
+    match timeout(
+        Duration::from_secs(5),
+        message.channel_id.crosspost(http, message.id),
+    )
+    .await
+    {
+        Ok(Ok(_)) => info!("Crossposted item from {}", feed.url),
+        Ok(Err(e)) => warn!("Failed to crosspost item from {}: {}", feed.url, e),
+        Err(_) => warn!(
+            "Crosspost for {} is rate limited, skipping this item",
+            feed.url
+        ),
+    }
+}
+
+fn embed_length(title: &str, description: &str, footer: &str, fields: &[(&str, &str)]) -> usize {
+    [title, description, footer]
+        .iter()
+        .chain(fields.iter().flat_map(|(name, value)| [name, value]))
+        .map(|text| text.chars().count())
+        .sum()
+}
 
-    if let Some(captures) = IMG_REGEX.captures(html) {
-        if let Some(url) = captures.get(1) {
-            let image_url = url.as_str();
+fn description_budget(used: usize, spoiler_markers: usize) -> usize {
+    EMBED_TOTAL_LIMIT
+        .saturating_sub(used)
+        .min(EMBED_DESCRIPTION_LIMIT - spoiler_markers)
+}
+
+fn extract_enclosure(entry: &feed_rs::model::Entry) -> Option<(String, String)> {
+    for media in &entry.media {
+        for content in &media.content {
+            let (Some(url), Some(mime)) = (&content.url, &content.content_type) else {
+                continue;
+            };
+
+            let (name, label) = match mime.ty().as_str() {
+                "audio" => ("🎧 Listen", "Listen"),
+                "video" => ("🎬 Watch", "Watch"),
+                _ => continue,
+            };
+
+            let mut value = format!("[{}]({})", label, url);
+
+            if let Some(duration) = content.duration.or(media.duration) {
+                let seconds = duration.as_secs();
+                let formatted = if seconds >= 3600 {
+                    format!(
+                        "{}:{:02}:{:02}",
+                        seconds / 3600,
+                        (seconds % 3600) / 60,
+                        seconds % 60
+                    )
+                } else {
+                    format!("{}:{:02}", seconds / 60, seconds % 60)
+                };
+                value.push_str(&format!(" • {}", formatted));
+            }
 
-            if image_url.starts_with("http") && validate_image_url(image_url) {
-                return Some(image_url.to_string());
+            if let Some(size) = content.size.filter(|size| *size > 0) {
+                value.push_str(&format!(" • {:.1} MB", size as f64 / 1_048_576.0));
             }
+
+            return Some((name.to_string(), value));
         }
     }
 
     None
 }
 
-fn validate_image_url(url: &str) -> bool {
-    let image_extensions = [".jpg", ".jpeg", ".png", ".gif", ".webp", ".bmp", ".svg"];
-    let lower_url = url.to_lowercase();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{mock::MockStore, models::NewFeed};
+
+    fn entry(item: &str) -> feed_rs::model::Entry {
+        let rss = format!(
+            r#"<?xml version="1.0"?>
+<rss version="2.0" xmlns:media="http://search.yahoo.com/mrss/"><channel><title>t</title>
+<item><title>Episode</title><link>https://example.com/1</link>{}</item></channel></rss>"#,
+            item
+        );
+        parser::parse(&rss).unwrap().entries.remove(0)
+    }
+
+    #[test]
+    fn enclosure_for_audio_with_duration_and_size() {
+        let entry = entry(
+            r#"<media:content url="https://cdn.example.com/ep1.mp3" type="audio/mpeg" duration="3725" fileSize="15728640"/>"#,
+        );
+        let (name, value) = extract_enclosure(&entry).unwrap();
+
+        assert_eq!(name, "🎧 Listen");
+        assert_eq!(
+            value,
+            "[Listen](https://cdn.example.com/ep1.mp3) • 1:02:05 • 15.0 MB"
+        );
+    }
+
+    #[test]
+    fn enclosure_for_video_under_an_hour() {
+        let entry = entry(
+            r#"<media:content url="https://cdn.example.com/clip.mp4" type="video/mp4" duration="95"/>"#,
+        );
+        let (name, value) = extract_enclosure(&entry).unwrap();
+
+        assert_eq!(name, "🎬 Watch");
+        assert_eq!(value, "[Watch](https://cdn.example.com/clip.mp4) • 1:35");
+    }
+
+    #[test]
+    fn enclosure_reports_size_in_megabytes() {
+        let entry = entry(
+            r#"<enclosure url="https://cdn.example.com/ep2.mp3" type="audio/mpeg" length="5242880"/>"#,
+        );
+        let (_, value) = extract_enclosure(&entry).unwrap();
+
+        assert_eq!(value, "[Listen](https://cdn.example.com/ep2.mp3) • 5.0 MB");
+    }
+
+    #[test]
+    fn no_enclosure_for_images_or_untyped_media() {
+        let entry = entry(
+            r#"<media:content url="https://cdn.example.com/cover.jpg" type="image/jpeg"/>
+               <media:content url="https://cdn.example.com/unknown"/>"#,
+        );
+        assert!(extract_enclosure(&entry).is_none());
+    }
+
+    #[test]
+    fn embed_length_counts_chars_not_bytes() {
+        assert_eq!(embed_length("日本語", "é", "🎧", &[("名前", "値")]), 8);
+    }
+
+    #[test]
+    fn description_budget_is_capped_at_the_description_limit() {
+        assert_eq!(description_budget(0, 0), EMBED_DESCRIPTION_LIMIT);
+        assert_eq!(description_budget(1904, 0), 4096);
+        assert_eq!(description_budget(1905, 0), 4095);
+        assert_eq!(description_budget(0, 4), 4092);
+        assert_eq!(description_budget(6000, 0), 0);
+        assert_eq!(description_budget(7000, 0), 0);
+    }
+
+    #[test]
+    fn embed_at_exactly_the_total_limit_is_not_trimmed() {
+        let title = "t".repeat(256);
+        let footer = "f".repeat(1648);
+        let description = "é".repeat(4096);
+        let used = embed_length(&title, "", &footer, &[]);
+
+        assert_eq!(embed_length(&title, &description, &footer, &[]), 6000);
+        assert_eq!(description_budget(used, 0), 4096);
+        assert_eq!(
+            parser::truncate(&description, description_budget(used, 0)),
+            description
+        );
+    }
+
+    #[test]
+    fn trimmed_multibyte_description_fits_the_budget() {
+        let title = "t".repeat(256);
+        let footer = "f".repeat(1649);
+        let description = "日本語のテキスト。".repeat(600);
+        let budget = description_budget(embed_length(&title, "", &footer, &[]), 0);
+        let trimmed = parser::truncate(&description, budget);
+
+        assert_eq!(budget, 4095);
+        assert!(trimmed.chars().count() <= budget);
+        assert!(embed_length(&title, &trimmed, &footer, &[]) <= EMBED_TOTAL_LIMIT);
+    }
+
+    fn item(guid: &str, title: &str, link: &str, date: chrono::DateTime<chrono::Utc>) -> String {
+        format!(
+            "<item><guid>{}</guid><title>{}</title><link>{}</link><pubDate>{}</pubDate></item>",
+            guid,
+            title,
+            link,
+            date.to_rfc2822()
+        )
+    }
+
+    fn rss(items: &str) -> String {
+        format!(
+            r#"<?xml version="1.0"?><rss version="2.0"><channel><title>t</title>{}</channel></rss>"#,
+            items
+        )
+    }
+
+    async fn serve(body: String) -> String {
+        fetcher::init(
+            fetcher::Limits::default(),
+            fetcher::DEFAULT_USER_AGENT.to_string(),
+            Vec::new(),
+            None,
+            true,
+        );
+
+        let app = axum::Router::new().route(
+            "/feed.xml",
+            axum::routing::get(move || async move {
+                (
+                    [(axum::http::header::CONTENT_TYPE, "application/rss+xml")],
+                    body,
+                )
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{}/feed.xml", address)
+    }
+
+    #[test]
+    fn republish_key_ignores_guid_date_and_tracking() {
+        let now = chrono::Utc::now();
+        let original = parser::parse(&rss(&item(
+            "guid-1",
+            "Big News: Launch Day!",
+            "https://example.com/news/launch",
+            now - chrono::Duration::hours(3),
+        )))
+        .unwrap()
+        .entries
+        .remove(0);
+        let republished = parser::parse(&rss(&item(
+            "guid-2",
+            "Big news - launch day",
+            "https://example.com/news/launch?utm_source=rss",
+            now,
+        )))
+        .unwrap()
+        .entries
+        .remove(0);
+
+        assert_ne!(identifier(&original), identifier(&republished));
+        assert_eq!(republish_key(&original), republish_key(&republished));
+    }
+
+    #[tokio::test]
+    async fn republished_item_is_skipped_and_advances_the_feed() {
+        let now = chrono::Utc::now();
+        let title = "Republish scenario headline";
+        let original = parser::parse(&rss(&item(
+            "original-guid",
+            title,
+            "https://example.com/republish",
+            now - chrono::Duration::hours(2),
+        )))
+        .unwrap()
+        .entries
+        .remove(0);
+        let republished_at = now - chrono::Duration::minutes(30);
+        let url = serve(rss(&item(
+            "fresh-guid",
+            title,
+            "https://example.com/republish",
+            republished_at,
+        )))
+        .await;
+
+        let store = MockStore::new();
+        store
+            .add(NewFeed {
+                guild_id: 1,
+                channel_id: 10,
+                url: &url,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let previous_date = (now - chrono::Duration::days(1)).to_rfc3339();
+        let feed_id = store.guild(1).await.unwrap()[0].id;
+        store.update(feed_id, Some(&previous_date)).await.unwrap();
+        let feed = store.guild(1).await.unwrap().remove(0);
+        LAST_POSTED
+            .lock()
+            .unwrap()
+            .insert(feed.id, (republish_key(&original), Instant::now()));
+
+        let posted = process(&feed, &store, &Http::new(""), &fetcher::client().unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(posted, 0);
+        let stored = store.guild(1).await.unwrap().remove(0);
+        assert_eq!(stored.items_posted, 0);
+        assert_eq!(
+            stored
+                .last_item_date
+                .as_deref()
+                .and_then(|date| chrono::DateTime::parse_from_rfc3339(date).ok())
+                .map(|date| date.timestamp()),
+            Some(republished_at.timestamp())
+        );
+    }
+
+    // Stands in for the Discord API and counts the messages sent to it.
+    async fn discord() -> (Http, Arc<std::sync::atomic::AtomicUsize>) {
+        let sent = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = sent.clone();
+        let app = axum::Router::new().fallback(move || {
+            let id = counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+            let message = serde_json::json!({
+                "id": id.to_string(),
+                "channel_id": "10",
+                "author": {"id": "1", "username": "rssbot", "discriminator": "0000"},
+                "content": "",
+                "timestamp": "2024-01-01T00:00:00Z",
+                "edited_timestamp": null,
+                "tts": false,
+                "mention_everyone": false,
+                "mentions": [],
+                "mention_roles": [],
+                "attachments": [],
+                "embeds": [],
+                "pinned": false,
+                "type": 0,
+            });
+            async move {
+                (
+                    [(axum::http::header::CONTENT_TYPE, "application/json")],
+                    message.to_string(),
+                )
+            }
+        });
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let http = serenity::http::HttpBuilder::new("token")
+            .proxy(format!("http://{}", address))
+            .ratelimiter_disabled(true)
+            .build();
+        (http, sent)
+    }
+
+    #[tokio::test]
+    async fn future_dated_item_posts_once_across_restarts() {
+        let published = chrono::Utc::now() + chrono::Duration::days(2);
+        let body = rss(&item(
+            "future-guid",
+            "Scheduled announcement",
+            "https://example.com/scheduled",
+            published,
+        ));
+        let entry = parser::parse(&body).unwrap().entries.remove(0);
+        let url = serve(body).await;
+        let (http, sent) = discord().await;
+
+        let store = MockStore::new();
+        store
+            .add(NewFeed {
+                guild_id: 1,
+                channel_id: 10,
+                url: &url,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let client = fetcher::client().unwrap();
+
+        let feed = store.guild(1).await.unwrap().remove(0);
+        assert_eq!(process(&feed, &store, &http, &client).await.unwrap(), 1);
+
+        // A restart forgets the claim and the republish window.
+        release(&identifier(&entry)).await;
+        LAST_POSTED.lock().unwrap().remove(&feed.id);
+        let feed = store.guild(1).await.unwrap().remove(0);
+        assert_eq!(process(&feed, &store, &http, &client).await.unwrap(), 0);
+        assert_eq!(sent.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_claims_have_exactly_one_winner() {
+        let attempts = (0..16).map(|_| tokio::spawn(claim("concurrent-claim-entry")));
+        let results = futures::future::join_all(attempts).await;
+
+        let winners = results
+            .into_iter()
+            .filter(|result| *result.as_ref().unwrap())
+            .count();
+        assert_eq!(winners, 1);
+    }
+
+    #[tokio::test]
+    async fn released_claim_can_be_taken_again() {
+        assert!(claim("released-claim-entry").await);
+        assert!(!claim("released-claim-entry").await);
+
+        release("released-claim-entry").await;
+        assert!(claim("released-claim-entry").await);
+    }
+
+    #[tokio::test]
+    async fn embeds_neutralize_mentions_in_titles_and_descriptions() {
+        let store = MockStore::new();
+        store
+            .add(NewFeed {
+                guild_id: 1,
+                channel_id: 10,
+                url: "https://example.com/mentions.xml",
+                title: Some("Feed @here"),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let feed = store.guild(1).await.unwrap().remove(0);
+        let entry = parser::parse(&rss(
+            "<item><title>@everyone big news for &amp;lt;@123&amp;gt;</title>\
+             <link>https://example.com/a</link><description>Ping @here and \
+             &amp;lt;@&amp;amp;42&amp;gt; and @\u{200d}everyone</description></item>",
+        ))
+        .unwrap()
+        .entries
+        .remove(0);
+
+        let embed = build_embed(&feed, &entry, None, Tz::UTC, &Client::new()).await;
+        let embed = serde_json::to_value(&embed).unwrap();
+
+        assert_eq!(
+            embed["title"],
+            "@\u{200b}everyone big news for <@\u{200b}123>"
+        );
+        assert_eq!(
+            embed["description"],
+            "Ping @\u{200b}here and <@\u{200b}&42> and @\u{200b}everyone"
+        );
+        assert!(
+            embed["footer"]["text"]
+                .as_str()
+                .unwrap()
+                .starts_with("Feed @\u{200b}here")
+        );
+    }
+
+    fn dated_entry(date: Option<&str>) -> feed_rs::model::Entry {
+        let date = date
+            .map(|date| format!("<pubDate>{}</pubDate>", date))
+            .unwrap_or_default();
+        parser::parse(&rss(&format!(
+            "<item><title>Dated</title><link>https://example.com/d</link>{}</item>",
+            date
+        )))
+        .unwrap()
+        .entries
+        .remove(0)
+    }
+
+    #[test]
+    fn future_dates_are_clamped_to_now() {
+        let now = chrono::Utc::now();
+        let tomorrow = (now + chrono::Duration::days(1)).to_rfc2822();
+
+        assert_eq!(
+            entry_date(&dated_entry(Some(&tomorrow)), now, "feed"),
+            Some(now)
+        );
+    }
+
+    #[test]
+    fn small_future_skew_is_kept() {
+        let now = chrono::Utc::now();
+        let soon = now + chrono::Duration::minutes(2);
+        let date = entry_date(&dated_entry(Some(&soon.to_rfc2822())), now, "feed");
+
+        assert_eq!(date.map(|date| date.timestamp()), Some(soon.timestamp()));
+    }
+
+    #[test]
+    fn epoch_dates_are_ignored() {
+        let now = chrono::Utc::now();
+
+        assert_eq!(
+            entry_date(
+                &dated_entry(Some("Thu, 01 Jan 1970 00:00:00 GMT")),
+                now,
+                "feed"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn missing_dates_are_none() {
+        assert_eq!(
+            entry_date(&dated_entry(None), chrono::Utc::now(), "feed"),
+            None
+        );
+    }
+
+    #[test]
+    fn past_dates_are_kept() {
+        let now = chrono::Utc::now();
+        let yesterday = now - chrono::Duration::days(1);
+        let date = entry_date(&dated_entry(Some(&yesterday.to_rfc2822())), now, "feed");
+
+        assert_eq!(
+            date.map(|date| date.timestamp()),
+            Some(yesterday.timestamp())
+        );
+    }
 
-    image_extensions.iter().any(|ext| lower_url.contains(ext))
-        || lower_url.contains("image")
-        || lower_url.contains("img")
+    #[tokio::test]
+    async fn open_circuit_skips_without_recording_an_error() {
+        let store = MockStore::new();
+        store
+            .add(NewFeed {
+                guild_id: 1,
+                channel_id: 10,
+                url: "https://circuit.invalid/feed.xml",
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let feed = store.guild(1).await.unwrap().remove(0);
+        BREAKERS.lock().unwrap().insert(
+            "circuit.invalid".to_string(),
+            Breaker {
+                failures: BREAKER_THRESHOLD,
+                opened_at: Some(Instant::now()),
+                probing: false,
+            },
+        );
+
+        let result = process(&feed, &store, &Http::new(""), &fetcher::client().unwrap()).await;
+
+        assert!(result.unwrap_err().downcast_ref::<CircuitOpen>().is_some());
+        assert!(store.recent_errors(feed.id).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn not_found_does_not_trip_the_breaker() {
+        let url = serve(rss("")).await.replace("/feed.xml", "/missing.xml");
+        let store = MockStore::new();
+        store
+            .add(NewFeed {
+                guild_id: 1,
+                channel_id: 10,
+                url: &url,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let feed = store.guild(1).await.unwrap().remove(0);
+
+        for _ in 0..=BREAKER_THRESHOLD {
+            let client = fetcher::client().unwrap();
+            assert!(
+                process(&feed, &store, &Http::new(""), &client)
+                    .await
+                    .is_err()
+            );
+        }
+
+        assert!(breaker_allows(&host(&url)));
+        assert!(!BREAKERS.lock().unwrap().contains_key(&host(&url)));
+    }
+
+    #[tokio::test]
+    async fn clashing_move_is_probed_only_once() {
+        fetcher::init(
+            fetcher::Limits::default(),
+            fetcher::DEFAULT_USER_AGENT.to_string(),
+            Vec::new(),
+            None,
+            true,
+        );
+        let hits = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = hits.clone();
+        let app = axum::Router::new()
+            .route(
+                "/old.xml",
+                axum::routing::get(move || {
+                    counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                    async { axum::response::Redirect::permanent("/feed.xml") }
+                }),
+            )
+            .route(
+                "/feed.xml",
+                axum::routing::get(|| async {
+                    (
+                        [(axum::http::header::CONTENT_TYPE, "application/rss+xml")],
+                        rss(""),
+                    )
+                }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let store = MockStore::new();
+        for path in ["/old.xml", "/feed.xml"] {
+            store
+                .add(NewFeed {
+                    guild_id: 1,
+                    channel_id: 10,
+                    url: &format!("{}{}", base, path),
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+        }
+        let old_url = format!("{}/old.xml", base);
+        let feed = store
+            .guild(1)
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|feed| feed.url == old_url)
+            .unwrap();
+
+        let client = fetcher::client().unwrap();
+        process(&feed, &store, &Http::new(""), &client)
+            .await
+            .unwrap();
+        assert_eq!(hits.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+        process(&feed, &store, &Http::new(""), &client)
+            .await
+            .unwrap();
+        assert_eq!(hits.load(std::sync::atomic::Ordering::SeqCst), 3);
+        assert!(
+            store
+                .guild(1)
+                .await
+                .unwrap()
+                .iter()
+                .any(|feed| feed.url == old_url)
+        );
+    }
 }
@@ -0,0 +1,41 @@
+use std::sync::OnceLock;
+
+use url::Url;
+
+static DOMAINS: OnceLock<Vec<String>> = OnceLock::new();
+
+pub fn init(domains: Vec<String>) {
+    let domains = domains
+        .into_iter()
+        .map(|domain| domain.trim().trim_end_matches('.').to_lowercase())
+        .filter(|domain| !domain.is_empty())
+        .collect();
+    let _ = DOMAINS.set(domains);
+}
+
+pub fn blocked(url: &str) -> Option<&'static str> {
+    let parsed = Url::parse(url).ok()?;
+    let host = parsed.host_str()?.trim_end_matches('.').to_lowercase();
+
+    DOMAINS
+        .get()?
+        .iter()
+        .map(String::as_str)
+        .find(|pattern| match pattern.strip_prefix("*.") {
+            Some(suffix) => host
+                .strip_suffix(suffix)
+                .is_some_and(|rest| rest.ends_with('.')),
+            None => host == *pattern,
+        })
+}
+
+pub fn message(url: &str, pattern: &str) -> String {
+    format!(
+        "Feeds from `{}` are not allowed on this bot (blocked by `{}`).",
+        Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(|h| h.to_string()))
+            .unwrap_or_else(|| url.to_string()),
+        pattern
+    )
+}
@@ -1,28 +1,853 @@
-use std::time::Duration;
+use std::{
+    collections::HashMap,
+    io::Read,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    sync::{Arc, LazyLock, Mutex, OnceLock},
+    time::Duration,
+};
 
 use anyhow::Result;
-use reqwest::Client;
+use flate2::read::GzDecoder;
+use reqwest::{
+    Client, ClientBuilder, Method, Proxy, RequestBuilder, StatusCode,
+    dns::{Addrs, Name, Resolve, Resolving},
+    header::{ACCEPT_ENCODING, CONTENT_ENCODING, HeaderName, HeaderValue},
+    redirect::Policy,
+};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 
-pub async fn single(url: &str) -> Result<String> {
-    let client = Client::builder()
-        .timeout(Duration::from_secs(30))
-        .user_agent("Mozilla/5.0 RSS Bot")
+use crate::data::models::RequestSpec;
+
+const ACCEPTED_CONTENT_TYPES: [&str; 6] = [
+    "application/rss+xml",
+    "application/atom+xml",
+    "application/xml",
+    "text/xml",
+    "application/feed+json",
+    "text/html",
+];
+
+pub const DEFAULT_USER_AGENT: &str = concat!(
+    "rssbot/",
+    env!("CARGO_PKG_VERSION"),
+    " (+",
+    env!("CARGO_PKG_REPOSITORY"),
+    ")"
+);
+
+static LIMITS: OnceLock<Limits> = OnceLock::new();
+static USER_AGENT: OnceLock<String> = OnceLock::new();
+static ALLOW_PRIVATE: OnceLock<bool> = OnceLock::new();
+static EXTRA_CONTENT_TYPES: OnceLock<Vec<String>> = OnceLock::new();
+static PROXY: OnceLock<Proxy> = OnceLock::new();
+static HOST_PERMITS: LazyLock<Mutex<HashMap<String, Arc<Semaphore>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+static PROBE_CLIENT: LazyLock<Client> = LazyLock::new(|| {
+    builder()
+        .redirect(Policy::none())
+        .build()
+        .expect("failed to build redirect probe client")
+});
+
+pub struct Fetched {
+    pub content: String,
+    pub moved_to: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Limits {
+    pub per_host: usize,
+    pub max_feed_bytes: usize,
+    pub max_feed_items: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            per_host: 4,
+            max_feed_bytes: 5_000_000,
+            max_feed_items: 500,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct UnsupportedContentType(pub String);
+
+impl std::fmt::Display for UnsupportedContentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "not a feed content-type ({})", self.0)
+    }
+}
+
+impl std::error::Error for UnsupportedContentType {}
+
+#[derive(Debug)]
+pub struct PrivateAddress(pub IpAddr);
+
+impl std::fmt::Display for PrivateAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "resolves to a private or reserved address ({})", self.0)
+    }
+}
+
+impl std::error::Error for PrivateAddress {}
+
+#[derive(Debug)]
+pub struct HttpStatus(pub StatusCode);
+
+impl std::fmt::Display for HttpStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "HTTP {}", self.0)
+    }
+}
+
+impl std::error::Error for HttpStatus {}
+
+/// Whether a fetch error means the host itself is down or struggling: it
+/// couldn't be reached, it timed out or it answered with a 5xx.
+pub fn is_host_failure(error: &anyhow::Error) -> bool {
+    if let Some(status) = error.downcast_ref::<HttpStatus>() {
+        return status.0.is_server_error();
+    }
+    error
+        .downcast_ref::<reqwest::Error>()
+        .is_some_and(|e| e.is_connect() || e.is_timeout())
+}
+
+// Filters every lookup the HTTP clients make, so redirects, probes and a
+// host that re-resolves after check_destination can't reach private ranges.
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addresses: Vec<SocketAddr> =
+                tokio::net::lookup_host((name.as_str(), 0)).await?.collect();
+            let addresses = if ALLOW_PRIVATE.get().copied().unwrap_or(false) {
+                addresses
+            } else {
+                public_addresses(addresses)?
+            };
+            Ok(Box::new(addresses.into_iter()) as Addrs)
+        })
+    }
+}
+
+fn public_addresses(
+    addresses: Vec<SocketAddr>,
+) -> std::result::Result<Vec<SocketAddr>, PrivateAddress> {
+    match addresses.iter().find(|address| is_private(address.ip())) {
+        Some(address) => Err(PrivateAddress(address.ip())),
+        None => Ok(addresses),
+    }
+}
+
+pub fn init(
+    limits: Limits,
+    user_agent: String,
+    extra_content_types: Vec<String>,
+    proxy: Option<Proxy>,
+    allow_private: bool,
+) {
+    let _ = LIMITS.set(limits);
+    let _ = ALLOW_PRIVATE.set(allow_private);
+    let _ = USER_AGENT.set(user_agent);
+    if let Some(proxy) = proxy {
+        let _ = PROXY.set(proxy);
+    }
+    let _ = EXTRA_CONTENT_TYPES.set(
+        extra_content_types
+            .into_iter()
+            .map(|content_type| content_type.trim().to_lowercase())
+            .collect(),
+    );
+}
+
+/// `api` also accepts plain `application/json`, which JSON Feed endpoints
+/// behind a custom request spec commonly answer with.
+pub fn check_content_type(
+    headers: &reqwest::header::HeaderMap,
+    api: bool,
+) -> std::result::Result<(), UnsupportedContentType> {
+    let Some(value) = headers
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return Ok(());
+    };
+
+    let essence = value
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase();
+    let extra = EXTRA_CONTENT_TYPES
+        .get()
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+
+    if essence.is_empty()
+        || ACCEPTED_CONTENT_TYPES.contains(&essence.as_str())
+        || (api && essence == "application/json")
+        || extra.contains(&essence)
+    {
+        Ok(())
+    } else {
+        Err(UnsupportedContentType(essence))
+    }
+}
+
+pub async fn check_destination(url: &str) -> Result<()> {
+    if ALLOW_PRIVATE.get().copied().unwrap_or(false) {
+        return Ok(());
+    }
+
+    let parsed = reqwest::Url::parse(url)?;
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| anyhow::anyhow!("URL has no host"))?
+        .trim_start_matches('[')
+        .trim_end_matches(']');
+    if let Ok(ip) = host.parse::<IpAddr>() {
+        return public(ip);
+    }
+
+    // Behind a proxy the proxy does its own lookup, so this is a best effort:
+    // names that don't resolve here are left to the proxy, and a host that
+    // re-resolves between the two lookups isn't caught. Restrict what the
+    // proxy itself may reach if that matters.
+    let port = parsed.port_or_known_default().unwrap_or(80);
+    let addresses = match tokio::net::lookup_host((host, port)).await {
+        Ok(addresses) => addresses,
+        Err(_) if PROXY.get().is_some() => return Ok(()),
+        Err(e) => return Err(e.into()),
+    };
+    for address in addresses {
+        public(address.ip())?;
+    }
+    Ok(())
+}
+
+fn public(ip: IpAddr) -> Result<()> {
+    if is_private(ip) {
+        Err(PrivateAddress(ip).into())
+    } else {
+        Ok(())
+    }
+}
+
+fn is_private(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, c, _] = ip.octets();
+            ip.is_private()
+                || ip.is_loopback()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_multicast()
+                || ip.is_documentation()
+                || a == 0
+                || a >= 240
+                || (a == 100 && (64..128).contains(&b))
+                || (a == 192 && b == 0 && c == 0)
+                || (a == 198 && (b == 18 || b == 19))
+        }
+        IpAddr::V6(ip) => {
+            let segments = ip.segments();
+            let first = segments[0];
+            ip.is_loopback()
+                || ip.is_unspecified()
+                || ip.is_multicast()
+                || (first & 0xfe00) == 0xfc00
+                || (first & 0xffc0) == 0xfe80
+                || ip
+                    .to_ipv4_mapped()
+                    .is_some_and(|ip| is_private(IpAddr::V4(ip)))
+                || (segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0]
+                    && is_private(IpAddr::V4(Ipv4Addr::from(
+                        (u32::from(segments[6]) << 16) | u32::from(segments[7]),
+                    ))))
+        }
+    }
+}
+
+pub fn limits() -> Limits {
+    LIMITS.get().copied().unwrap_or_default()
+}
+
+pub fn proxy(url: &str, no_proxy: &[String]) -> Result<Proxy> {
+    let parsed = reqwest::Url::parse(url)?;
+    if !matches!(parsed.scheme(), "http" | "https" | "socks5" | "socks5h") {
+        return Err(anyhow::anyhow!(
+            "unsupported proxy scheme \"{}\", expected http, https, socks5 or socks5h",
+            parsed.scheme()
+        ));
+    }
+
+    let proxy = Proxy::all(parsed)?;
+    Ok(if no_proxy.is_empty() {
+        proxy
+    } else {
+        proxy.no_proxy(reqwest::NoProxy::from_string(&no_proxy.join(",")))
+    })
+}
+
+pub fn request_spec(json: &str) -> Result<RequestSpec> {
+    let mut spec: RequestSpec = serde_json::from_str(json)?;
+    spec.method = spec.method.trim().to_uppercase();
+    if !matches!(spec.method.as_str(), "GET" | "POST") {
+        return Err(anyhow::anyhow!(
+            "method must be GET or POST, got \"{}\"",
+            spec.method
+        ));
+    }
+    if spec.method == "GET" && spec.body.is_some() {
+        return Err(anyhow::anyhow!("a GET request can't have a body"));
+    }
+    for (name, value) in &spec.headers {
+        HeaderName::from_bytes(name.as_bytes())
+            .map_err(|_| anyhow::anyhow!("invalid header name \"{}\"", name))?;
+        HeaderValue::from_str(value)
+            .map_err(|_| anyhow::anyhow!("invalid value for header \"{}\"", name))?;
+    }
+    Ok(spec)
+}
+
+pub fn request(client: &Client, url: &str, spec: Option<&RequestSpec>) -> RequestBuilder {
+    let Some(spec) = spec else {
+        return client.get(url);
+    };
+
+    let method = Method::from_bytes(spec.method.as_bytes()).unwrap_or(Method::GET);
+    let mut request = client.request(method, url);
+    for (name, value) in &spec.headers {
+        request = request.header(name.as_str(), value.as_str());
+    }
+    match &spec.body {
+        Some(serde_json::Value::String(body)) => request.body(body.clone()),
+        Some(body) => request.json(body),
+        None => request,
+    }
+}
+
+pub fn client() -> Result<Client> {
+    let client = builder()
+        .pool_max_idle_per_host(limits().per_host)
         .build()?;
+    Ok(client)
+}
+
+fn builder() -> ClientBuilder {
+    let user_agent = USER_AGENT.get().map_or(DEFAULT_USER_AGENT, String::as_str);
+    let builder = Client::builder()
+        .user_agent(user_agent)
+        .redirect(Policy::custom(|attempt| {
+            let private = !ALLOW_PRIVATE.get().copied().unwrap_or(false)
+                && attempt
+                    .url()
+                    .host_str()
+                    .map(|host| host.trim_start_matches('[').trim_end_matches(']'))
+                    .and_then(|host| host.parse::<IpAddr>().ok())
+                    .is_some_and(is_private);
+            if private {
+                attempt.error("redirect to a private or reserved address")
+            } else if attempt.previous().len() >= 10 {
+                attempt.error("too many redirects")
+            } else {
+                attempt.follow()
+            }
+        }));
+    match PROXY.get() {
+        Some(proxy) => builder.proxy(proxy.clone()),
+        None => builder.dns_resolver(Arc::new(PublicResolver)),
+    }
+}
+
+async fn host_permit(url: &str) -> Option<OwnedSemaphorePermit> {
+    let host = reqwest::Url::parse(url)
+        .ok()?
+        .host_str()?
+        .trim_end_matches('.')
+        .to_lowercase();
+
+    let semaphore = HOST_PERMITS
+        .lock()
+        .unwrap()
+        .entry(host)
+        .or_insert_with(|| Arc::new(Semaphore::new(limits().per_host)))
+        .clone();
 
-    fetch(&client, url).await
+    semaphore.acquire_owned().await.ok()
 }
 
-async fn fetch(client: &Client, url: &str) -> Result<String> {
-    let response = client.get(url).send().await?;
+pub async fn single(client: &Client, url: &str) -> Result<Fetched> {
+    fetch(client, url, None, true).await
+}
+
+/// Fetches with an optional request spec. `probe_moves` re-checks a
+/// redirected URL for a permanent move; callers that won't act on
+/// `moved_to` should pass `false` to save the extra requests.
+pub async fn with_request(
+    client: &Client,
+    url: &str,
+    spec: Option<&RequestSpec>,
+    probe_moves: bool,
+) -> Result<Fetched> {
+    fetch(client, url, spec, probe_moves).await
+}
+
+async fn fetch(
+    client: &Client,
+    url: &str,
+    spec: Option<&RequestSpec>,
+    probe_moves: bool,
+) -> Result<Fetched> {
+    check_destination(url).await?;
+    let permit = host_permit(url).await;
+
+    let custom_encoding = spec.is_some_and(|spec| {
+        spec.headers
+            .keys()
+            .any(|name| name.eq_ignore_ascii_case(ACCEPT_ENCODING.as_str()))
+    });
+    let mut request = request(client, url, spec).timeout(Duration::from_secs(30));
+    if !custom_encoding {
+        request = request.header(ACCEPT_ENCODING, "gzip");
+    }
+    let response = request.send().await?;
 
     if !response.status().is_success() {
-        return Err(anyhow::anyhow!("HTTP {}", response.status()));
+        return Err(HttpStatus(response.status()).into());
     }
+    check_content_type(response.headers(), spec.is_some())?;
+
+    let redirected = reqwest::Url::parse(url).ok().as_ref() != Some(response.url());
+    let gzipped = response
+        .headers()
+        .get(CONTENT_ENCODING)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.trim().eq_ignore_ascii_case("gzip"));
 
     let bytes = response.bytes().await?;
-    if bytes.len() > 5_000_000 {
+    drop(permit);
+    if bytes.len() > limits().max_feed_bytes {
         return Err(anyhow::anyhow!("Feed too large: {} bytes", bytes.len()));
     }
+    let bytes = if gzipped {
+        gunzip(&bytes, limits().max_feed_bytes)?
+    } else {
+        bytes.to_vec()
+    };
+
+    let moved_to = if redirected && spec.is_none() && probe_moves {
+        permanent_target(url).await
+    } else {
+        None
+    };
 
-    Ok(String::from_utf8_lossy(&bytes).into_owned())
+    Ok(Fetched {
+        content: String::from_utf8_lossy(&bytes).into_owned(),
+        moved_to,
+    })
+}
+
+async fn permanent_target(url: &str) -> Option<String> {
+    let mut current = reqwest::Url::parse(url).ok()?;
+    let mut target = None;
+
+    for _ in 0..10 {
+        check_destination(current.as_str()).await.ok()?;
+        let response = PROBE_CLIENT
+            .get(current.clone())
+            .timeout(Duration::from_secs(10))
+            .send()
+            .await
+            .ok()?;
+
+        if !matches!(
+            response.status(),
+            StatusCode::MOVED_PERMANENTLY | StatusCode::PERMANENT_REDIRECT
+        ) {
+            break;
+        }
+
+        let location = response.headers().get(reqwest::header::LOCATION)?;
+        current = current.join(location.to_str().ok()?).ok()?;
+        target = Some(current.to_string());
+    }
+
+    target.filter(|target| target != url)
+}
+
+fn gunzip(bytes: &[u8], limit: usize) -> Result<Vec<u8>> {
+    let mut decoded = Vec::new();
+    GzDecoder::new(bytes)
+        .take(limit as u64 + 1)
+        .read_to_end(&mut decoded)?;
+    if decoded.len() > limit {
+        return Err(anyhow::anyhow!(
+            "Feed too large: over {} bytes uncompressed",
+            limit
+        ));
+    }
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use axum::{
+        Router,
+        http::{HeaderMap, StatusCode, header},
+        response::{IntoResponse, Redirect},
+        routing::get,
+    };
+    use flate2::{Compression, write::GzEncoder};
+
+    use super::*;
+
+    const RSS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0"><channel><title>Test Feed</title><link>https://example.com/</link>
+<item><title>First post</title><link>https://example.com/1</link><guid>1</guid></item>
+</channel></rss>"#;
+    const JSON_FEED: &str = r#"{"version": "https://jsonfeed.org/version/1.1", "title": "Test Feed",
+"items": [{"id": "1", "title": "First post", "url": "https://example.com/1"}]}"#;
+
+    async fn rss() -> impl IntoResponse {
+        ([(header::CONTENT_TYPE, "application/rss+xml")], RSS)
+    }
+
+    static COUNTED_HITS: AtomicUsize = AtomicUsize::new(0);
+
+    async fn counted() -> impl IntoResponse {
+        COUNTED_HITS.fetch_add(1, Ordering::SeqCst);
+        rss().await
+    }
+
+    async fn api(headers: HeaderMap, body: String) -> axum::response::Response {
+        let authorized = headers.get("x-api-key").is_some_and(|value| value == "abc");
+        let json = headers
+            .get(header::CONTENT_TYPE)
+            .is_some_and(|value| value == "application/json");
+        if authorized && json && body == r#"{"page":1}"# {
+            rss().await.into_response()
+        } else {
+            StatusCode::BAD_REQUEST.into_response()
+        }
+    }
+
+    async fn json_feed() -> impl IntoResponse {
+        ([(header::CONTENT_TYPE, "application/json")], JSON_FEED)
+    }
+
+    async fn gzipped(headers: HeaderMap) -> impl IntoResponse {
+        let accepts_gzip = headers
+            .get(header::ACCEPT_ENCODING)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.contains("gzip"));
+        if !accepts_gzip {
+            return (StatusCode::NOT_ACCEPTABLE, HeaderMap::new(), Vec::new());
+        }
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(RSS.as_bytes()).unwrap();
+        let mut response_headers = HeaderMap::new();
+        response_headers.insert(header::CONTENT_TYPE, "application/rss+xml".parse().unwrap());
+        response_headers.insert(header::CONTENT_ENCODING, "gzip".parse().unwrap());
+        (StatusCode::OK, response_headers, encoder.finish().unwrap())
+    }
+
+    async fn serve() -> String {
+        init(
+            Limits::default(),
+            DEFAULT_USER_AGENT.to_string(),
+            Vec::new(),
+            None,
+            true,
+        );
+
+        let app = Router::new()
+            .route("/feed.xml", get(rss))
+            .route("/gzip.xml", get(gzipped))
+            .route("/counted.xml", get(counted))
+            .route(
+                "/old.xml",
+                get(|| async { Redirect::permanent("/feed.xml") }),
+            )
+            .route(
+                "/temporary.xml",
+                get(|| async { Redirect::temporary("/feed.xml") }),
+            )
+            .route("/not-modified", get(|| async { StatusCode::NOT_MODIFIED }))
+            .route("/missing", get(|| async { StatusCode::NOT_FOUND }))
+            .route(
+                "/unavailable",
+                get(|| async { StatusCode::SERVICE_UNAVAILABLE }),
+            )
+            .route("/api", axum::routing::post(api))
+            .route("/json", axum::routing::post(json_feed).get(json_feed));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{}", address)
+    }
+
+    #[tokio::test]
+    async fn fetches_an_rss_body() {
+        let base = serve().await;
+        let fetched = single(&client().unwrap(), &format!("{}/feed.xml", base))
+            .await
+            .unwrap();
+
+        assert_eq!(fetched.content, RSS);
+        assert!(fetched.moved_to.is_none());
+    }
+
+    #[tokio::test]
+    async fn reports_a_permanent_redirect() {
+        let base = serve().await;
+        let fetched = single(&client().unwrap(), &format!("{}/old.xml", base))
+            .await
+            .unwrap();
+
+        assert_eq!(fetched.content, RSS);
+        assert_eq!(fetched.moved_to, Some(format!("{}/feed.xml", base)));
+    }
+
+    #[tokio::test]
+    async fn ignores_a_temporary_redirect() {
+        let base = serve().await;
+        let fetched = single(&client().unwrap(), &format!("{}/temporary.xml", base))
+            .await
+            .unwrap();
+
+        assert!(fetched.moved_to.is_none());
+    }
+
+    #[tokio::test]
+    async fn does_not_probe_when_only_the_spelling_differs() {
+        let base = serve().await;
+        let url = format!("{}/counted.xml", base.replacen("http", "HTTP", 1));
+        let fetched = single(&client().unwrap(), &url).await.unwrap();
+
+        assert!(fetched.moved_to.is_none());
+        assert_eq!(COUNTED_HITS.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn decodes_a_gzip_body() {
+        let base = serve().await;
+        let fetched = single(&client().unwrap(), &format!("{}/gzip.xml", base))
+            .await
+            .unwrap();
+
+        assert_eq!(fetched.content, RSS);
+    }
+
+    #[tokio::test]
+    async fn not_modified_is_not_treated_as_content() {
+        let base = serve().await;
+        let error = single(&client().unwrap(), &format!("{}/not-modified", base))
+            .await
+            .err()
+            .unwrap();
+
+        assert!(error.to_string().contains("304"), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn not_found_is_an_error() {
+        let base = serve().await;
+        let error = single(&client().unwrap(), &format!("{}/missing", base))
+            .await
+            .err()
+            .unwrap();
+
+        assert!(error.to_string().contains("404"), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn only_outages_count_as_host_failures() {
+        let base = serve().await;
+        let client = client().unwrap();
+
+        let missing = single(&client, &format!("{}/missing", base))
+            .await
+            .err()
+            .unwrap();
+        assert!(!is_host_failure(&missing), "{}", missing);
+
+        let unavailable = single(&client, &format!("{}/unavailable", base))
+            .await
+            .err()
+            .unwrap();
+        assert!(is_host_failure(&unavailable), "{}", unavailable);
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let closed = format!("http://{}/feed.xml", listener.local_addr().unwrap());
+        drop(listener);
+        let refused = single(&client, &closed).await.err().unwrap();
+        assert!(is_host_failure(&refused), "{}", refused);
+    }
+
+    #[test]
+    fn gunzip_enforces_the_size_limit() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&[b'a'; 1024]).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        assert_eq!(gunzip(&compressed, 1024).unwrap().len(), 1024);
+        assert!(gunzip(&compressed, 1023).is_err());
+    }
+
+    #[test]
+    fn request_spec_rejects_a_get_with_a_body() {
+        let error = request_spec(r#"{"method": "GET", "body": {"q": 1}}"#).unwrap_err();
+        assert!(error.to_string().contains("can't have a body"), "{}", error);
+    }
+
+    #[test]
+    fn request_spec_rejects_a_bad_header_name() {
+        let error =
+            request_spec(r#"{"method": "POST", "headers": {"Bad Header": "x"}}"#).unwrap_err();
+        assert!(
+            error.to_string().contains("invalid header name"),
+            "{}",
+            error
+        );
+
+        let error = request_spec(r#"{"headers": {"X-Token": "line\nbreak"}}"#).unwrap_err();
+        assert!(error.to_string().contains("invalid value"), "{}", error);
+    }
+
+    #[test]
+    fn request_spec_rejects_other_methods() {
+        let error = request_spec(r#"{"method": "PUT"}"#).unwrap_err();
+        assert!(error.to_string().contains("PUT"), "{}", error);
+    }
+
+    #[test]
+    fn request_spec_normalizes_the_method() {
+        let spec = request_spec(
+            r#"{"method": " post ", "body": {"page": 1}, "headers": {"X-Api-Key": "abc"}}"#,
+        )
+        .unwrap();
+        assert_eq!(spec.method, "POST");
+        assert_eq!(spec.body, Some(serde_json::json!({"page": 1})));
+        assert_eq!(
+            spec.headers.get("X-Api-Key").map(String::as_str),
+            Some("abc")
+        );
+
+        assert_eq!(request_spec("{}").unwrap().method, "GET");
+    }
+
+    #[tokio::test]
+    async fn with_request_sends_the_method_body_and_headers() {
+        let base = serve().await;
+        let spec = request_spec(
+            r#"{"method": "post", "body": {"page": 1}, "headers": {"X-Api-Key": "abc"}}"#,
+        )
+        .unwrap();
+        let fetched = with_request(
+            &client().unwrap(),
+            &format!("{}/api", base),
+            Some(&spec),
+            true,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(fetched.content, RSS);
+    }
+
+    #[tokio::test]
+    async fn request_spec_accepts_a_json_feed_served_as_plain_json() {
+        let base = serve().await;
+        let url = format!("{}/json", base);
+        let spec = request_spec(r#"{"method": "POST", "body": {"page": 1}}"#).unwrap();
+        let fetched = with_request(&client().unwrap(), &url, Some(&spec), true)
+            .await
+            .unwrap();
+
+        let feed = crate::util::parser::parse(&fetched.content).unwrap();
+        assert_eq!(feed.entries.len(), 1);
+        assert!(single(&client().unwrap(), &url).await.is_err());
+    }
+
+    fn private(address: &str) -> bool {
+        is_private(address.parse().unwrap())
+    }
+
+    #[test]
+    fn private_ranges_are_detected() {
+        let cases = [
+            "10.0.0.1",
+            "10.255.255.255",
+            "100.64.0.1",
+            "100.127.255.254",
+            "127.0.0.1",
+            "169.254.169.254",
+            "172.16.0.1",
+            "192.168.1.1",
+            "192.0.0.8",
+            "198.18.0.1",
+            "198.19.255.255",
+            "240.0.0.1",
+            "255.255.255.254",
+            "0.0.0.0",
+            "::1",
+            "::ffff:127.0.0.1",
+            "::ffff:10.1.2.3",
+            "fc00::1",
+            "fd12:3456::1",
+            "fe80::1",
+            "febf::1",
+            "64:ff9b::7f00:1",
+            "64:ff9b::a00:1",
+        ];
+
+        for address in cases {
+            assert!(private(address), "{} should be private", address);
+        }
+    }
+
+    #[test]
+    fn public_addresses_are_allowed() {
+        let cases = [
+            "1.1.1.1",
+            "8.8.8.8",
+            "100.63.255.255",
+            "100.128.0.1",
+            "172.32.0.1",
+            "192.0.1.1",
+            "198.17.255.255",
+            "198.20.0.1",
+            "2606:4700:4700::1111",
+            "64:ff9b::808:808",
+            "::ffff:8.8.8.8",
+            "fec0::1",
+        ];
+
+        for address in cases {
+            assert!(!private(address), "{} should be public", address);
+        }
+    }
+
+    #[test]
+    fn resolver_rejects_hosts_with_any_private_address() {
+        let public: SocketAddr = "93.184.216.34:0".parse().unwrap();
+        let private: SocketAddr = "10.0.0.5:0".parse().unwrap();
+
+        assert_eq!(public_addresses(vec![public]).unwrap(), vec![public]);
+        let error = public_addresses(vec![public, private]).unwrap_err();
+        assert_eq!(error.0, private.ip());
+    }
 }
@@ -0,0 +1,48 @@
+use std::sync::OnceLock;
+
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+
+pub const MAX_TEMPLATE_LENGTH: usize = 200;
+
+static DEFAULT_TEMPLATE: OnceLock<String> = OnceLock::new();
+
+pub fn init(template: Option<String>) {
+    if let Some(template) = template.filter(|template| !template.trim().is_empty()) {
+        let _ = DEFAULT_TEMPLATE.set(template);
+    }
+}
+
+pub fn default_template() -> &'static str {
+    DEFAULT_TEMPLATE
+        .get()
+        .map(String::as_str)
+        .unwrap_or("{feed}")
+}
+
+pub fn render(
+    template: Option<&str>,
+    feed: &str,
+    date: Option<DateTime<Utc>>,
+    timezone: Tz,
+) -> String {
+    let date = date
+        .map(|date| {
+            date.with_timezone(&timezone)
+                .format("%b %d, %Y")
+                .to_string()
+        })
+        .unwrap_or_default();
+
+    let rendered = template
+        .unwrap_or(default_template())
+        .replace("{feed}", feed)
+        .replace("{date}", &date);
+
+    let rendered = rendered.trim().trim_matches('•').trim();
+    if rendered.is_empty() {
+        feed.to_string()
+    } else {
+        rendered.to_string()
+    }
+}
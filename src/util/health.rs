@@ -0,0 +1,43 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
+use anyhow::Result;
+use axum::{Router, extract::State, http::StatusCode, routing::get};
+use tokio::time::{Duration, timeout};
+use tracing::{info, warn};
+
+use crate::data::FeedStore;
+
+static CONNECTED: AtomicBool = AtomicBool::new(false);
+
+pub fn set_connected(connected: bool) {
+    CONNECTED.store(connected, Ordering::Relaxed);
+}
+
+pub async fn serve(port: u16, database: Arc<dyn FeedStore>) -> Result<()> {
+    let app = Router::new()
+        .route("/healthz", get(healthz))
+        .with_state(database);
+    let listener = tokio::net::TcpListener::bind(("0.0.0.0", port)).await?;
+
+    info!("Health endpoint listening on port {}", port);
+    axum::serve(listener, app).await?;
+    Ok(())
+}
+
+async fn healthz(State(database): State<Arc<dyn FeedStore>>) -> (StatusCode, &'static str) {
+    if !CONNECTED.load(Ordering::Relaxed) {
+        return (StatusCode::SERVICE_UNAVAILABLE, "discord disconnected");
+    }
+
+    match timeout(Duration::from_secs(5), database.ping()).await {
+        Ok(Ok(())) => (StatusCode::OK, "ok"),
+        Ok(Err(e)) => {
+            warn!("Health check database ping failed: {}", e);
+            (StatusCode::SERVICE_UNAVAILABLE, "database unavailable")
+        }
+        Err(_) => (StatusCode::SERVICE_UNAVAILABLE, "database timed out"),
+    }
+}
@@ -0,0 +1,381 @@
+use std::{
+    collections::HashMap,
+    sync::{LazyLock, Mutex, OnceLock},
+    time::{Duration, Instant},
+};
+
+use regex::Regex;
+use reqwest::Client;
+use tokio::time::timeout;
+use url::Url;
+
+use crate::util::fetcher;
+
+static IMG_TAG_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)<img\b[^>]*>").unwrap());
+static SRC_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?i)\bsrc\s*=\s*["']([^"']+)["']"#).unwrap());
+static WIDTH_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?i)\bwidth\s*=\s*["']?(\d+)"#).unwrap());
+static HEIGHT_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?i)\bheight\s*=\s*["']?(\d+)"#).unwrap());
+static OG_IMAGE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)<meta[^>]+property=["']og:image["'][^>]+content=["']([^"']+)["']"#).unwrap()
+});
+static OG_IMAGE_REVERSED_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)<meta[^>]+content=["']([^"']+)["'][^>]+property=["']og:image["']"#).unwrap()
+});
+
+type PageImage = (Option<String>, Instant);
+
+static PAGE_IMAGES: LazyLock<Mutex<HashMap<String, PageImage>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+static FETCH_PAGES: OnceLock<bool> = OnceLock::new();
+
+const PAGE_CACHE_TTL: Duration = Duration::from_secs(3600);
+const PAGE_FETCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+const TRACKER_PATTERNS: [&str; 10] = [
+    "feeds.feedburner.com/~r",
+    "feeds.feedburner.com/~ff",
+    "pixel.wp.com",
+    "stats.wordpress.com",
+    "feedblitz.com",
+    "doubleclick.net",
+    "google-analytics.com",
+    "gravatar.com/avatar",
+    "/pixel.",
+    "/tracking",
+];
+
+const UNKNOWN_AREA: u64 = 300 * 200;
+
+struct Candidate {
+    url: String,
+    width: Option<u32>,
+    height: Option<u32>,
+    preferred: bool,
+}
+
+impl Candidate {
+    fn area(&self) -> u64 {
+        match (self.width, self.height) {
+            (Some(width), Some(height)) => width as u64 * height as u64,
+            (Some(side), None) | (None, Some(side)) => side as u64 * side as u64,
+            (None, None) => UNKNOWN_AREA,
+        }
+    }
+
+    fn is_tracker(&self) -> bool {
+        let lower_url = self.url.to_lowercase();
+
+        lower_url.starts_with("data:")
+            || !lower_url.starts_with("http")
+            || self.width.is_some_and(|w| w <= 2)
+            || self.height.is_some_and(|h| h <= 2)
+            || TRACKER_PATTERNS.iter().any(|p| lower_url.contains(p))
+    }
+}
+
+pub fn extract(entry: &feed_rs::model::Entry, base: &str) -> Option<String> {
+    let base = Url::parse(base).ok();
+    let mut candidates = Vec::new();
+
+    for media in &entry.media {
+        for content in &media.content {
+            let Some(url) = &content.url else {
+                continue;
+            };
+
+            let is_image = match &content.content_type {
+                Some(mime) => mime.ty().as_str() == "image",
+                None => validate(url.as_str()),
+            };
+
+            if is_image {
+                candidates.push(Candidate {
+                    url: url.to_string(),
+                    width: content.width,
+                    height: content.height,
+                    preferred: true,
+                });
+            }
+        }
+
+        for thumbnail in &media.thumbnails {
+            let Some(url) = resolve(&thumbnail.image.uri, base.as_ref()) else {
+                continue;
+            };
+            candidates.push(Candidate {
+                url,
+                width: thumbnail.image.width,
+                height: thumbnail.image.height,
+                preferred: true,
+            });
+        }
+    }
+
+    if let Some(body) = entry.content.as_ref().and_then(|c| c.body.as_ref()) {
+        collect_from_html(body, base.as_ref(), &mut candidates);
+    }
+
+    if let Some(summary) = &entry.summary {
+        collect_from_html(&summary.content, base.as_ref(), &mut candidates);
+    }
+
+    let mut best: Option<Candidate> = None;
+    for candidate in candidates.into_iter().filter(|c| !c.is_tracker()) {
+        let better = best.as_ref().is_none_or(|current| {
+            (candidate.preferred, candidate.area()) > (current.preferred, current.area())
+        });
+        if better {
+            best = Some(candidate);
+        }
+    }
+
+    best.map(|candidate| candidate.url)
+}
+
+pub fn init(fetch_pages: bool) {
+    let _ = FETCH_PAGES.set(fetch_pages);
+}
+
+pub async fn from_page(client: &Client, url: &str) -> Option<String> {
+    if !FETCH_PAGES.get().copied().unwrap_or(false) {
+        return None;
+    }
+
+    {
+        let mut cache = PAGE_IMAGES.lock().unwrap();
+        cache.retain(|_, (_, fetched_at)| fetched_at.elapsed() < PAGE_CACHE_TTL);
+        if let Some((image, _)) = cache.get(url) {
+            return image.clone();
+        }
+    }
+
+    let image = match timeout(PAGE_FETCH_TIMEOUT, fetcher::single(client, url)).await {
+        Ok(Ok(page)) => og_image(&page.content, Url::parse(url).ok().as_ref()).filter(|image| {
+            !Candidate {
+                url: image.clone(),
+                width: None,
+                height: None,
+                preferred: true,
+            }
+            .is_tracker()
+        }),
+        _ => None,
+    };
+
+    PAGE_IMAGES
+        .lock()
+        .unwrap()
+        .insert(url.to_string(), (image.clone(), Instant::now()));
+    image
+}
+
+fn og_image(html: &str, base: Option<&Url>) -> Option<String> {
+    OG_IMAGE_REGEX
+        .captures(html)
+        .or_else(|| OG_IMAGE_REVERSED_REGEX.captures(html))
+        .and_then(|c| c.get(1))
+        .and_then(|url| resolve(url.as_str(), base))
+}
+
+fn resolve(url: &str, base: Option<&Url>) -> Option<String> {
+    let url = html_escape::decode_html_entities(url.trim());
+    if url.is_empty() || url.starts_with("data:") {
+        return Some(url.into_owned());
+    }
+
+    match Url::parse(&url) {
+        Ok(absolute) => Some(absolute.to_string()),
+        Err(url::ParseError::RelativeUrlWithoutBase) => {
+            base?.join(&url).ok().map(|resolved| resolved.to_string())
+        }
+        Err(_) => None,
+    }
+}
+
+fn collect_from_html(html: &str, base: Option<&Url>, candidates: &mut Vec<Candidate>) {
+    if let Some(url) = og_image(html, base) {
+        candidates.push(Candidate {
+            url,
+            width: None,
+            height: None,
+            preferred: true,
+        });
+    }
+
+    for tag in IMG_TAG_REGEX.find_iter(html) {
+        let tag = tag.as_str();
+
+        let Some(url) = SRC_REGEX
+            .captures(tag)
+            .and_then(|c| c.get(1))
+            .and_then(|url| resolve(url.as_str(), base))
+        else {
+            continue;
+        };
+
+        if !validate(&url) {
+            continue;
+        }
+
+        candidates.push(Candidate {
+            url,
+            width: dimension(&WIDTH_REGEX, tag),
+            height: dimension(&HEIGHT_REGEX, tag),
+            preferred: false,
+        });
+    }
+}
+
+fn dimension(regex: &Regex, tag: &str) -> Option<u32> {
+    regex
+        .captures(tag)
+        .and_then(|c| c.get(1))
+        .and_then(|m| m.as_str().parse().ok())
+}
+
+fn validate(url: &str) -> bool {
+    let image_extensions = [".jpg", ".jpeg", ".png", ".gif", ".webp", ".bmp", ".svg"];
+    let lower_url = url.to_lowercase();
+
+    image_extensions.iter().any(|ext| lower_url.contains(ext))
+        || lower_url.contains("image")
+        || lower_url.contains("img")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(media: &str, description: &str) -> feed_rs::model::Entry {
+        let rss = format!(
+            r#"<?xml version="1.0"?>
+<rss version="2.0" xmlns:media="http://search.yahoo.com/mrss/"><channel><title>t</title>
+<item><title>Post</title><link>https://example.com/post</link>{}
+<description><![CDATA[{}]]></description></item></channel></rss>"#,
+            media, description
+        );
+        crate::util::parser::parse(&rss).unwrap().entries.remove(0)
+    }
+
+    #[test]
+    fn skips_tracker_pixels() {
+        let entry = entry(
+            "",
+            r#"<img src="https://pixel.wp.com/g.gif?blog=1" width="1" height="1">
+               <img src="https://feeds.feedburner.com/~r/example/~4/abc.png">
+               <img src="https://example.com/photo.jpg">"#,
+        );
+        assert_eq!(
+            extract(&entry, "https://example.com/post").as_deref(),
+            Some("https://example.com/photo.jpg")
+        );
+    }
+
+    #[test]
+    fn skips_data_uris() {
+        let entry = entry(
+            "",
+            r#"<img src="data:image/png;base64,iVBORw0KGgo=" width="800" height="600">"#,
+        );
+        assert_eq!(extract(&entry, "https://example.com/post"), None);
+    }
+
+    #[test]
+    fn filters_tiny_images_and_prefers_the_largest() {
+        let entry = entry(
+            "",
+            r#"<img src="https://example.com/spacer.png" width="2" height="40">
+               <img src="https://example.com/icon.png" width="32" height="32">
+               <img src="https://example.com/hero.jpg" width="1200" height="630">"#,
+        );
+        assert_eq!(
+            extract(&entry, "https://example.com/post").as_deref(),
+            Some("https://example.com/hero.jpg")
+        );
+    }
+
+    #[test]
+    fn prefers_media_content_over_inline_images() {
+        let entry = entry(
+            r#"<media:content url="https://cdn.example.com/cover.jpg" type="image/jpeg" width="320" height="200"/>"#,
+            r#"<img src="https://example.com/inline.jpg" width="1600" height="900">"#,
+        );
+        assert_eq!(
+            extract(&entry, "https://example.com/post").as_deref(),
+            Some("https://cdn.example.com/cover.jpg")
+        );
+    }
+
+    #[test]
+    fn ignores_non_image_media_content() {
+        let entry = entry(
+            r#"<media:content url="https://cdn.example.com/episode.mp3" type="audio/mpeg"/>"#,
+            r#"<img src="https://example.com/inline.jpg">"#,
+        );
+        assert_eq!(
+            extract(&entry, "https://example.com/post").as_deref(),
+            Some("https://example.com/inline.jpg")
+        );
+    }
+
+    #[test]
+    fn resolves_image_urls_against_the_article_link() {
+        let base = Url::parse("https://blog.example.com/2024/05/post.html").unwrap();
+        let cases = [
+            ("/images/a.png", "https://blog.example.com/images/a.png"),
+            (
+                "images/b.png",
+                "https://blog.example.com/2024/05/images/b.png",
+            ),
+            ("../c.png", "https://blog.example.com/2024/c.png"),
+            ("//cdn.example.net/d.png", "https://cdn.example.net/d.png"),
+            (
+                "https://other.example.org/e.png",
+                "https://other.example.org/e.png",
+            ),
+            (
+                " /f.png?w=800&amp;h=600 ",
+                "https://blog.example.com/f.png?w=800&h=600",
+            ),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(
+                resolve(input, Some(&base)).as_deref(),
+                Some(expected),
+                "resolving {}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn relative_urls_need_a_base() {
+        assert_eq!(resolve("/images/a.png", None), None);
+        assert_eq!(
+            resolve("https://example.com/a.png", None).as_deref(),
+            Some("https://example.com/a.png")
+        );
+    }
+
+    #[test]
+    fn extract_resolves_relative_inline_images() {
+        let relative = entry(
+            "",
+            r#"<img src="/uploads/hero.jpg" width="800" height="400">"#,
+        );
+        assert_eq!(
+            extract(&relative, "https://example.com/blog/post").as_deref(),
+            Some("https://example.com/uploads/hero.jpg")
+        );
+
+        let protocol_relative = entry("", r#"<img src="//cdn.example.com/hero.jpg">"#);
+        assert_eq!(
+            extract(&protocol_relative, "http://example.com/blog/post").as_deref(),
+            Some("http://cdn.example.com/hero.jpg")
+        );
+    }
+}
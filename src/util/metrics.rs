@@ -0,0 +1,90 @@
+use std::{
+    fmt::Write,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use anyhow::Result;
+use axum::{Router, http::header, response::IntoResponse, routing::get};
+use tracing::info;
+
+pub static FEEDS_CHECKED: AtomicU64 = AtomicU64::new(0);
+pub static FETCH_SUCCESSES: AtomicU64 = AtomicU64::new(0);
+pub static FETCH_FAILURES: AtomicU64 = AtomicU64::new(0);
+pub static ITEMS_POSTED: AtomicU64 = AtomicU64::new(0);
+pub static CHECK_DURATION_MS: AtomicU64 = AtomicU64::new(0);
+pub static FEED_COUNT: AtomicU64 = AtomicU64::new(0);
+
+pub fn increment(counter: &AtomicU64, value: u64) {
+    counter.fetch_add(value, Ordering::Relaxed);
+}
+
+pub fn set(gauge: &AtomicU64, value: u64) {
+    gauge.store(value, Ordering::Relaxed);
+}
+
+pub async fn serve(port: u16) -> Result<()> {
+    let app = Router::new().route("/metrics", get(metrics));
+    let listener = tokio::net::TcpListener::bind(("0.0.0.0", port)).await?;
+
+    info!("Metrics endpoint listening on port {}", port);
+    axum::serve(listener, app).await?;
+    Ok(())
+}
+
+async fn metrics() -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        render(),
+    )
+}
+
+fn render() -> String {
+    let mut output = String::new();
+
+    let metrics = [
+        (
+            "rssbot_feeds_checked_total",
+            "counter",
+            "Feeds checked since startup",
+            FEEDS_CHECKED.load(Ordering::Relaxed) as f64,
+        ),
+        (
+            "rssbot_fetch_successes_total",
+            "counter",
+            "Successful feed fetches",
+            FETCH_SUCCESSES.load(Ordering::Relaxed) as f64,
+        ),
+        (
+            "rssbot_fetch_failures_total",
+            "counter",
+            "Failed or timed out feed fetches",
+            FETCH_FAILURES.load(Ordering::Relaxed) as f64,
+        ),
+        (
+            "rssbot_items_posted_total",
+            "counter",
+            "Articles posted to Discord",
+            ITEMS_POSTED.load(Ordering::Relaxed) as f64,
+        ),
+        (
+            "rssbot_check_duration_seconds",
+            "gauge",
+            "Duration of the last feed check cycle",
+            CHECK_DURATION_MS.load(Ordering::Relaxed) as f64 / 1000.0,
+        ),
+        (
+            "rssbot_feeds",
+            "gauge",
+            "Feeds currently configured",
+            FEED_COUNT.load(Ordering::Relaxed) as f64,
+        ),
+    ];
+
+    for (name, kind, help, value) in metrics {
+        let _ = writeln!(output, "# HELP {} {}", name, help);
+        let _ = writeln!(output, "# TYPE {} {}", name, kind);
+        let _ = writeln!(output, "{} {}", name, value);
+    }
+
+    output
+}
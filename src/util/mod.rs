@@ -1,2 +1,9 @@
+pub mod blocklist;
 pub mod fetcher;
+pub mod footer;
+pub mod health;
+pub mod image;
+pub mod metrics;
 pub mod parser;
+pub mod translate;
+pub mod webhook;
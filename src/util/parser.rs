@@ -22,12 +22,203 @@ static ASIDE_BLOCK_REGEX: LazyLock<Regex> =
 static OBJECT_REFERENCE_REGEX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"<[^>]*object at 0x[a-fA-F0-9]+>").unwrap());
 static ENCODED_ENTITIES_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"&#\d+;").unwrap());
+static UPDATE_PERIOD_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)<(?:\w+:)?updatePeriod>\s*(\w+)\s*</(?:\w+:)?updatePeriod>").unwrap()
+});
+static UPDATE_FREQUENCY_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)<(?:\w+:)?updateFrequency>\s*(\d+)\s*</(?:\w+:)?updateFrequency>").unwrap()
+});
+static MENTION_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(concat!(
+        r"@[\u{200b}-\u{200d}\u{2060}\u{feff}]*(?:everyone|here)",
+        r"|<@[\u{200b}-\u{200d}\u{2060}\u{feff}]*[&!]?\d+>"
+    ))
+    .unwrap()
+});
+const ZERO_WIDTH: [char; 5] = ['\u{200b}', '\u{200c}', '\u{200d}', '\u{2060}', '\u{feff}'];
+static TRACKING_PARAMS: &[&str] = &[
+    "fbclid", "gclid", "dclid", "gbraid", "wbraid", "msclkid", "yclid", "igshid", "mc_cid",
+    "mc_eid", "_hsenc", "_hsmi", "mkt_tok", "ref_src", "ref_url",
+];
+static FORMAT_PATTERNS: LazyLock<Vec<(Regex, &str)>> = LazyLock::new(|| {
+    [
+        (r"\[\u{2026}\]", ""),
+        (r"\[\.\.\.\]", ""),
+        (
+            r"(?i)(^|[.!?\u{2026}]\s+)(read more|continue reading|click here|more info)\b[^.!?]{0,80}[.\u{2026}]*\s*$",
+            "$1",
+        ),
+        (r"\s*\.\.\.\s*$", ""),
+        (r"^\s*-\s*", ""),
+        (r"^\s*\*\s*", ""),
+        (r"\{'[^']*'[^}]*\}", ""),
+        (r"\([^)]*'[^']*'[^)]*\)", ""),
+        (r"an\.\.\.$", ""),
+        (r"<[^>]*Value[^>]*>", ""),
+        (r"object at 0x[a-fA-F0-9]+", ""),
+    ]
+    .into_iter()
+    .map(|(pattern, replacement)| (Regex::new(pattern).unwrap(), replacement))
+    .collect()
+});
 
 pub fn parse(content: &str) -> Result<feed_rs::model::Feed> {
     let feed = feed_rs::parser::parse(content.as_bytes())?;
     Ok(feed)
 }
 
+pub fn issues(feed: &feed_rs::model::Feed) -> Vec<String> {
+    let total = feed.entries.len();
+    if total == 0 {
+        return vec!["It has no items yet, so there's nothing to check it against.".to_string()];
+    }
+
+    let count = |missing: fn(&feed_rs::model::Entry) -> bool| {
+        feed.entries.iter().filter(|entry| missing(entry)).count()
+    };
+    let describe = |missing: usize| {
+        if missing == total {
+            "None of its items have".to_string()
+        } else {
+            format!("{} of its {} items don't have", missing, total)
+        }
+    };
+
+    let mut issues = Vec::new();
+
+    let undated = count(|entry| entry.published.or(entry.updated).is_none());
+    if undated > 0 {
+        issues.push(format!(
+            "{} a date, so new items may be missed or posted late.",
+            describe(undated)
+        ));
+    }
+
+    let untitled = count(|entry| {
+        entry
+            .title
+            .as_ref()
+            .is_none_or(|title| title.content.trim().is_empty())
+    });
+    if untitled > 0 {
+        issues.push(format!(
+            "{} a title, so posts will show as \"Untitled\".",
+            describe(untitled)
+        ));
+    }
+
+    let unlinked = count(|entry| entry.links.is_empty());
+    if unlinked > 0 {
+        issues.push(format!(
+            "{} a link, so posts can't point to the article.",
+            describe(unlinked)
+        ));
+    }
+
+    let mut ids: Vec<&str> = feed.entries.iter().map(|entry| entry.id.as_str()).collect();
+    ids.sort_unstable();
+    ids.dedup();
+    if ids.len() < total {
+        issues.push(
+            "Some items share the same ID, so updates and duplicates may be detected wrongly."
+                .to_string(),
+        );
+    }
+
+    issues
+}
+
+pub fn poll_hint(content: &str, feed: &feed_rs::model::Feed) -> Option<u32> {
+    if let Some(ttl) = feed.ttl.filter(|ttl| *ttl > 0) {
+        return Some(ttl);
+    }
+
+    let period = UPDATE_PERIOD_REGEX.captures(content)?;
+    let period_minutes = match period[1].to_lowercase().as_str() {
+        "hourly" => 60,
+        "daily" => 60 * 24,
+        "weekly" => 60 * 24 * 7,
+        "monthly" => 60 * 24 * 30,
+        "yearly" => 60 * 24 * 365,
+        _ => return None,
+    };
+    let frequency = UPDATE_FREQUENCY_REGEX
+        .captures(content)
+        .and_then(|frequency| frequency[1].parse::<u32>().ok())
+        .filter(|frequency| *frequency > 0)
+        .unwrap_or(1);
+
+    Some(period_minutes / frequency)
+}
+
+pub fn canonical_url(input: &str) -> String {
+    let Ok(mut url) = url::Url::parse(&strip_tracking(input)) else {
+        return input.trim().to_string();
+    };
+
+    url.set_fragment(None);
+
+    let canonical = url.to_string();
+    canonical
+        .strip_suffix('/')
+        .map(|s| s.to_string())
+        .unwrap_or(canonical)
+}
+
+pub fn normalize_feed_url(input: &str) -> String {
+    let Ok(mut url) = url::Url::parse(input.trim()) else {
+        return input.trim().to_string();
+    };
+
+    url.set_fragment(None);
+
+    if let Some(query) = url.query().map(|q| q.to_string()) {
+        let mut pairs: Vec<&str> = query.split('&').filter(|pair| !pair.is_empty()).collect();
+        pairs.sort_unstable();
+        let sorted = pairs.join("&");
+        url.set_query((!sorted.is_empty()).then_some(sorted.as_str()));
+    }
+
+    if url.path().len() > 1 && url.path().ends_with('/') {
+        let path = url.path().trim_end_matches('/').to_string();
+        url.set_path(&path);
+    }
+
+    let normalized = url.to_string();
+    normalized
+        .strip_suffix('/')
+        .map(|s| s.to_string())
+        .unwrap_or(normalized)
+}
+
+pub fn strip_tracking(input: &str) -> String {
+    let Ok(mut url) = url::Url::parse(input.trim()) else {
+        return input.trim().to_string();
+    };
+
+    let Some(query) = url.query() else {
+        return url.to_string();
+    };
+
+    let kept: Vec<&str> = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .filter(|pair| {
+            let key = pair.split('=').next().unwrap_or_default().to_lowercase();
+            !key.starts_with("utm_") && !TRACKING_PARAMS.contains(&key.as_str())
+        })
+        .collect();
+
+    if kept.is_empty() {
+        url.set_query(None);
+    } else {
+        let query = kept.join("&");
+        url.set_query(Some(&query));
+    }
+
+    url.to_string()
+}
+
 pub fn clean(input: &str) -> String {
     if input.is_empty() {
         return String::new();
@@ -60,34 +251,7 @@ fn artifacts(input: &str) -> String {
 }
 
 fn decode(input: &str) -> String {
-    input
-        .replace("&amp;", "&")
-        .replace("&lt;", "<")
-        .replace("&gt;", ">")
-        .replace("&quot;", "\"")
-        .replace("&apos;", "'")
-        .replace("&nbsp;", " ")
-        .replace("&#39;", "'")
-        .replace("&#x27;", "'")
-        .replace("&#x2F;", "/")
-        .replace("&#8220;", "\u{201C}")
-        .replace("&#8221;", "\u{201D}")
-        .replace("&#8217;", "\u{2019}")
-        .replace("&#8211;", "\u{2013}")
-        .replace("&#8212;", "\u{2014}")
-        .replace("&#8230;", "\u{2026}")
-        .replace("&mdash;", "\u{2014}")
-        .replace("&ndash;", "\u{2013}")
-        .replace("&ldquo;", "\u{201C}")
-        .replace("&rdquo;", "\u{201D}")
-        .replace("&lsquo;", "\u{2018}")
-        .replace("&rsquo;", "\u{2019}")
-        .replace("&hellip;", "\u{2026}")
-        .replace("&#160;", " ")
-        .replace("&#8594;", "→")
-        .replace("&#8592;", "←")
-        .replace("&#8593;", "↑")
-        .replace("&#8595;", "↓")
+    html_escape::decode_html_entities(input).replace('\u{a0}', " ")
 }
 
 fn normalize(input: &str) -> String {
@@ -95,28 +259,9 @@ fn normalize(input: &str) -> String {
 }
 
 fn format(input: &str) -> String {
-    let patterns = [
-        (r"\[\u{2026}\]", ""),
-        (r"\[\.\.\.\]", ""),
-        (r"Read More\.\.\..*$", ""),
-        (r"Continue reading.*$", ""),
-        (r"Click here.*$", ""),
-        (r"More info.*$", ""),
-        (r"\s*\.\.\.\s*$", ""),
-        (r"^\s*-\s*", ""),
-        (r"^\s*\*\s*", ""),
-        (r"\{'[^']*'[^}]*\}", ""),
-        (r"\([^)]*'[^']*'[^)]*\)", ""),
-        (r"an\.\.\.$", ""),
-        (r"<[^>]*Value[^>]*>", ""),
-        (r"object at 0x[a-fA-F0-9]+", ""),
-    ];
-
     let mut result = input.to_string();
-    for (pattern, replacement) in patterns {
-        if let Ok(regex) = Regex::new(pattern) {
-            result = regex.replace_all(&result, replacement).to_string();
-        }
+    for (regex, replacement) in FORMAT_PATTERNS.iter() {
+        result = regex.replace_all(&result, *replacement).to_string();
     }
 
     result
@@ -143,48 +288,342 @@ pub fn description(entry: &feed_rs::model::Entry) -> String {
         })
         .unwrap_or_else(|| "No description available.".to_string());
 
-    if description.len() > 1800 {
-        let truncated = &description[..1800];
-        if let Some(last_sentence) = truncated.rfind('.') {
-            if last_sentence > 1400 {
-                return format!("{}.", &truncated[..last_sentence]);
-            }
-        }
-        if let Some(last_space) = truncated.rfind(' ') {
-            if last_space > 1400 {
-                return format!("{}…", &truncated[..last_space]);
-            }
-        }
-        format!("{}…", &description[..1797])
-    } else {
-        description
-    }
+    truncate(&description, 1800)
 }
 
+pub fn neutralize_mentions(text: &str) -> String {
+    MENTION_REGEX
+        .replace_all(text, |caps: &regex::Captures| {
+            caps[0]
+                .replace(ZERO_WIDTH, "")
+                .replacen('@', "@\u{200b}", 1)
+        })
+        .into_owned()
+}
+
+// Lengths are in chars, the unit Discord uses for embed limits.
 pub fn truncate(text: &str, max_length: usize) -> String {
-    if text.len() <= max_length {
+    let boundary = |chars: usize| {
+        text.char_indices()
+            .nth(chars)
+            .map_or(text.len(), |(index, _)| index)
+    };
+    if boundary(max_length) == text.len() {
         return text.to_string();
     }
 
-    let truncated = &text[..max_length];
+    let truncated = &text[..boundary(max_length)];
+    let with_ellipsis = &text[..boundary(max_length.saturating_sub(1))];
+    let threshold = boundary(max_length * 3 / 4);
 
     if let Some(last_sentence) = truncated.rfind('.') {
-        if last_sentence > max_length * 3 / 4 {
+        if last_sentence > threshold {
             return format!("{}.", &truncated[..last_sentence]);
         }
     }
 
-    if let Some(last_space) = truncated.rfind(' ') {
-        if last_space > max_length * 3 / 4 {
-            return format!("{}…", &truncated[..last_space]);
+    if let Some(last_space) = with_ellipsis.rfind(' ') {
+        if last_space > threshold {
+            return format!("{}…", &with_ellipsis[..last_space]);
+        }
+    }
+
+    if let Some(last_punct) = with_ellipsis.rfind(['.', '!', '?', ',', ';']) {
+        if last_punct > threshold {
+            return format!("{}…", &with_ellipsis[..=last_punct]);
+        }
+    }
+
+    format!("{}…", with_ellipsis)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clean_unwraps_cdata() {
+        assert_eq!(clean("<![CDATA[Hello <b>world</b>]]>"), "Hello world");
+    }
+
+    #[test]
+    fn clean_drops_scripts_and_styles() {
+        let input = "<p>Visible</p>\n<script type=\"text/javascript\">\nalert('x');\n</script>\
+                     <STYLE>p { color: red; }</STYLE><p>text</p>";
+        assert_eq!(clean(input), "Visible text");
+    }
+
+    #[test]
+    fn clean_decodes_entities() {
+        assert_eq!(
+            clean("Fish &amp; Chips&nbsp;&mdash; &quot;best&quot; in town"),
+            "Fish & Chips — \"best\" in town"
+        );
+    }
+
+    #[test]
+    fn clean_removes_wagtail_noise() {
+        let input = "Intro &lt;wagtail.rich_text.RichText object at 0x7f3a2b1c&gt; \
+                     StructValue({'heading': 'x'}) aside_block &lt;StreamValue [1]&gt; continues \
+                     here";
+        assert_eq!(clean(input), "Intro continues here");
+    }
+
+    #[test]
+    fn clean_strips_trailing_boilerplate() {
+        assert_eq!(
+            clean("The launch went well. Continue reading at Example News..."),
+            "The launch went well."
+        );
+        assert_eq!(clean("Short summary [&#8230;]"), "Short summary");
+    }
+
+    #[test]
+    fn clean_keeps_boilerplate_words_mid_text() {
+        let input = "Read more about it below. Then the story continues with details.";
+        assert_eq!(clean(input), input);
+    }
+
+    #[test]
+    fn decode_handles_every_entity_from_the_old_table() {
+        let cases = [
+            ("&amp;", "&"),
+            ("&lt;", "<"),
+            ("&gt;", ">"),
+            ("&quot;", "\""),
+            ("&apos;", "'"),
+            ("&nbsp;", " "),
+            ("&#39;", "'"),
+            ("&#x27;", "'"),
+            ("&#x2F;", "/"),
+            ("&#8220;", "\u{201C}"),
+            ("&#8221;", "\u{201D}"),
+            ("&#8217;", "\u{2019}"),
+            ("&#8211;", "\u{2013}"),
+            ("&#8212;", "\u{2014}"),
+            ("&#8230;", "\u{2026}"),
+            ("&mdash;", "\u{2014}"),
+            ("&ndash;", "\u{2013}"),
+            ("&ldquo;", "\u{201C}"),
+            ("&rdquo;", "\u{201D}"),
+            ("&lsquo;", "\u{2018}"),
+            ("&rsquo;", "\u{2019}"),
+            ("&hellip;", "\u{2026}"),
+            ("&#160;", " "),
+            ("&#8594;", "\u{2192}"),
+            ("&#8592;", "\u{2190}"),
+            ("&#8593;", "\u{2191}"),
+            ("&#8595;", "\u{2193}"),
+        ];
+
+        for (entity, expected) in cases {
+            assert_eq!(decode(entity), expected, "decoding {}", entity);
+        }
+    }
+
+    #[test]
+    fn decode_handles_numeric_entities() {
+        assert_eq!(decode("&#233;t&#xE9; &#x1F600;"), "\u{e9}t\u{e9} \u{1F600}");
+        assert_eq!(decode("caf&eacute; &copy; 2024"), "caf\u{e9} \u{a9} 2024");
+    }
+
+    #[test]
+    fn decode_leaves_unknown_entities_alone() {
+        assert_eq!(decode("&notanentity; & more"), "&notanentity; & more");
+    }
+
+    #[test]
+    fn strip_tracking_removes_tracking_params() {
+        assert_eq!(
+            strip_tracking(
+                "https://example.com/post?utm_source=rss&id=42&fbclid=abc&UTM_Medium=x&gclid=1"
+            ),
+            "https://example.com/post?id=42"
+        );
+    }
+
+    #[test]
+    fn strip_tracking_keeps_other_params_and_fragments() {
+        assert_eq!(
+            strip_tracking("https://example.com/post?page=2&sort=new#comments"),
+            "https://example.com/post?page=2&sort=new#comments"
+        );
+    }
+
+    #[test]
+    fn strip_tracking_leaves_no_dangling_question_mark() {
+        assert_eq!(
+            strip_tracking("https://example.com/post?utm_source=rss&utm_campaign=feed"),
+            "https://example.com/post"
+        );
+        assert_eq!(
+            strip_tracking("https://example.com/post?fbclid=abc#top"),
+            "https://example.com/post#top"
+        );
+    }
+
+    #[test]
+    fn canonical_url_drops_tracking_fragment_and_trailing_slash() {
+        assert_eq!(
+            canonical_url("https://example.com/post/?utm_source=rss&id=42#comments"),
+            "https://example.com/post/?id=42"
+        );
+        assert_eq!(
+            canonical_url("https://example.com/post/?gclid=1#top"),
+            "https://example.com/post"
+        );
+        assert_eq!(
+            canonical_url("https://example.com/post?utm_source=a"),
+            canonical_url("https://example.com/post?utm_source=b")
+        );
+    }
+
+    #[test]
+    fn normalize_feed_url_lowercases_scheme_and_host() {
+        assert_eq!(
+            normalize_feed_url("HTTPS://Example.COM/Feed.xml"),
+            "https://example.com/Feed.xml"
+        );
+    }
+
+    #[test]
+    fn normalize_feed_url_drops_trailing_slashes() {
+        assert_eq!(
+            normalize_feed_url("https://example.com/feed/"),
+            "https://example.com/feed"
+        );
+        assert_eq!(
+            normalize_feed_url("https://example.com/"),
+            "https://example.com"
+        );
+        assert_eq!(
+            normalize_feed_url("https://example.com"),
+            "https://example.com"
+        );
+    }
+
+    #[test]
+    fn normalize_feed_url_drops_default_ports_only() {
+        assert_eq!(
+            normalize_feed_url("https://example.com:443/feed"),
+            "https://example.com/feed"
+        );
+        assert_eq!(
+            normalize_feed_url("http://example.com:80/feed"),
+            "http://example.com/feed"
+        );
+        assert_eq!(
+            normalize_feed_url("https://example.com:8443/feed"),
+            "https://example.com:8443/feed"
+        );
+    }
+
+    #[test]
+    fn normalize_feed_url_keeps_the_scheme() {
+        assert_eq!(
+            normalize_feed_url("http://example.com/feed"),
+            "http://example.com/feed"
+        );
+    }
+
+    #[test]
+    fn normalize_feed_url_sorts_the_query_and_drops_the_fragment() {
+        assert_eq!(
+            normalize_feed_url(" https://example.com/feed?b=2&a=1#latest "),
+            "https://example.com/feed?a=1&b=2"
+        );
+    }
+
+    #[test]
+    fn truncate_counts_chars_and_never_splits_one() {
+        let text = "é".repeat(5000);
+        let truncated = truncate(&text, 4096);
+
+        assert_eq!(truncated.chars().count(), 4096);
+        assert!(truncated.ends_with('…'));
+
+        for text in ["日本語のテキスト", "🎧🎬📰🎧🎬📰🎧🎬", "aé日🎧aé日🎧aé日🎧"]
+        {
+            for max_length in 1..text.chars().count() {
+                let truncated = truncate(text, max_length);
+                assert!(
+                    truncated.chars().count() <= max_length,
+                    "{:?} at {}",
+                    truncated,
+                    max_length
+                );
+            }
         }
     }
 
-    if let Some(last_punct) = truncated.rfind(&['.', '!', '?', ',', ';']) {
-        if last_punct > max_length * 3 / 4 {
-            return format!("{}…", &truncated[..=last_punct]);
+    #[test]
+    fn truncate_keeps_text_that_fits() {
+        assert_eq!(truncate("日本語", 3), "日本語");
+        assert_eq!(truncate("short", 10), "short");
+    }
+
+    #[test]
+    fn truncate_prefers_sentence_and_word_boundaries() {
+        assert_eq!(
+            truncate("First sentence here. Second one follows", 24),
+            "First sentence here."
+        );
+        assert_eq!(
+            truncate("alpha beta gamma delta epsilon", 20),
+            "alpha beta gamma…"
+        );
+    }
+
+    #[test]
+    fn description_cuts_long_multibyte_text_safely() {
+        let rss = format!(
+            r#"<?xml version="1.0"?><rss version="2.0"><channel><title>t</title>
+<item><title>x</title><description>{}</description></item></channel></rss>"#,
+            "ü".repeat(2000)
+        );
+        let entry = parse(&rss).unwrap().entries.remove(0);
+
+        assert_eq!(description(&entry).chars().count(), 1800);
+    }
+
+    #[test]
+    fn neutralize_mentions_breaks_mass_and_user_mentions() {
+        let cases = [
+            ("@everyone look", "@\u{200b}everyone look"),
+            ("hi @here!", "hi @\u{200b}here!"),
+            ("<@123456789>", "<@\u{200b}123456789>"),
+            ("<@!123456789>", "<@\u{200b}!123456789>"),
+            ("<@&987654321> ping", "<@\u{200b}&987654321> ping"),
+            ("@everyones", "@\u{200b}everyones"),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(
+                neutralize_mentions(input),
+                expected,
+                "neutralizing {:?}",
+                input
+            );
         }
     }
 
-    format!("{}…", &truncated[..max_length.saturating_sub(1)])
+    #[test]
+    fn neutralize_mentions_normalizes_zero_width_variants() {
+        let cases = [
+            "@\u{200d}everyone",
+            "@\u{2060}\u{200c}everyone",
+            "@\u{feff}here",
+        ];
+        for input in cases {
+            let neutralized = neutralize_mentions(input);
+            assert!(
+                neutralized == "@\u{200b}everyone" || neutralized == "@\u{200b}here",
+                "neutralizing {:?} gave {:?}",
+                input,
+                neutralized
+            );
+        }
+        assert_eq!(neutralize_mentions("<@\u{200d}&42>"), "<@\u{200b}&42>");
+        assert_eq!(neutralize_mentions("everyone here"), "everyone here");
+    }
 }
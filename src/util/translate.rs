@@ -0,0 +1,169 @@
+use std::{
+    sync::{LazyLock, OnceLock},
+    time::Duration,
+};
+
+use anyhow::Result;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::json;
+
+static TRANSLATOR: OnceLock<Translator> = OnceLock::new();
+
+// The backend is chosen by the operator and often runs on localhost, so it
+// gets a plain client rather than the feed client that refuses private
+// addresses and goes through the feed proxy.
+static CLIENT: LazyLock<Client> = LazyLock::new(Client::new);
+
+#[derive(Debug, Clone, Copy)]
+pub enum Backend {
+    LibreTranslate,
+    DeepL,
+}
+
+#[derive(Debug, Clone)]
+pub struct Translator {
+    pub backend: Backend,
+    pub url: String,
+    pub api_key: Option<String>,
+}
+
+pub struct Translation {
+    pub texts: Vec<String>,
+    pub source_language: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct LibreResponse {
+    #[serde(rename = "translatedText")]
+    translated_text: Vec<String>,
+    #[serde(rename = "detectedLanguage")]
+    detected_language: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct DeepLResponse {
+    translations: Vec<DeepLTranslation>,
+}
+
+#[derive(Deserialize)]
+struct DeepLTranslation {
+    text: String,
+    detected_source_language: Option<String>,
+}
+
+impl Backend {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "libretranslate" => Some(Self::LibreTranslate),
+            "deepl" => Some(Self::DeepL),
+            _ => None,
+        }
+    }
+}
+
+pub fn init(translator: Translator) {
+    let _ = TRANSLATOR.set(translator);
+}
+
+pub fn enabled() -> bool {
+    TRANSLATOR.get().is_some()
+}
+
+pub async fn translate(texts: &[&str], target: &str) -> Result<Translation> {
+    let translator = TRANSLATOR
+        .get()
+        .ok_or_else(|| anyhow::anyhow!("No translation backend configured"))?;
+
+    let translation = match translator.backend {
+        Backend::LibreTranslate => libre(&CLIENT, translator, texts, target).await?,
+        Backend::DeepL => deepl(&CLIENT, translator, texts, target).await?,
+    };
+
+    if translation.texts.len() != texts.len() {
+        return Err(anyhow::anyhow!(
+            "Expected {} translations, got {}",
+            texts.len(),
+            translation.texts.len()
+        ));
+    }
+    Ok(translation)
+}
+
+async fn libre(
+    client: &Client,
+    translator: &Translator,
+    texts: &[&str],
+    target: &str,
+) -> Result<Translation> {
+    let body = json!({
+        "q": texts,
+        "source": "auto",
+        "target": target.to_lowercase(),
+        "format": "text",
+        "api_key": translator.api_key.as_deref().unwrap_or_default(),
+    });
+
+    let response = client
+        .post(format!(
+            "{}/translate",
+            translator.url.trim_end_matches('/')
+        ))
+        .json(&body)
+        .timeout(Duration::from_secs(10))
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!("HTTP {}", response.status()));
+    }
+
+    let response: LibreResponse = response.json().await?;
+    Ok(Translation {
+        texts: response.translated_text,
+        source_language: response.detected_language.and_then(|detected| {
+            let detected = detected.get(0).unwrap_or(&detected);
+            detected
+                .get("language")
+                .and_then(|language| language.as_str())
+                .map(|language| language.to_string())
+        }),
+    })
+}
+
+async fn deepl(
+    client: &Client,
+    translator: &Translator,
+    texts: &[&str],
+    target: &str,
+) -> Result<Translation> {
+    let body = json!({
+        "text": texts,
+        "target_lang": target.to_uppercase(),
+    });
+
+    let mut request = client
+        .post(format!(
+            "{}/v2/translate",
+            translator.url.trim_end_matches('/')
+        ))
+        .json(&body)
+        .timeout(Duration::from_secs(10));
+    if let Some(key) = &translator.api_key {
+        request = request.header("Authorization", format!("DeepL-Auth-Key {}", key));
+    }
+
+    let response = request.send().await?;
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!("HTTP {}", response.status()));
+    }
+
+    let response: DeepLResponse = response.json().await?;
+    let source_language = response
+        .translations
+        .first()
+        .and_then(|t| t.detected_source_language.clone());
+    Ok(Translation {
+        texts: response.translations.into_iter().map(|t| t.text).collect(),
+        source_language,
+    })
+}
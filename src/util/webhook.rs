@@ -0,0 +1,22 @@
+use anyhow::Result;
+use serenity::{all::Http, utils::parse_webhook};
+use tracing::info;
+use url::Url;
+
+pub async fn delete(http: &Http, url: &str) -> Result<()> {
+    let parsed_url = Url::parse(url)?;
+    let (webhook_id, token) =
+        parse_webhook(&parsed_url).ok_or_else(|| anyhow::anyhow!("Invalid webhook URL"))?;
+
+    match http
+        .delete_webhook_with_token(webhook_id, token, Some("RSS feed removed"))
+        .await
+    {
+        Ok(()) => Ok(()),
+        Err(serenity::Error::Http(e)) if e.status_code().map(|s| s.as_u16()) == Some(404) => {
+            info!("Webhook {} was already deleted", webhook_id);
+            Ok(())
+        }
+        Err(e) => Err(e.into()),
+    }
+}